@@ -98,6 +98,7 @@ struct ValitronData {
     height: f32,
 }
 
+#[allow(clippy::ptr_arg)]
 fn valid_url(url: &mut String) -> Result<(), Message> {
     match Url::parse(url) {
         Ok(_) => Ok(()),
@@ -15,7 +15,7 @@ use actix_web::{
 };
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use valitron::{available::Required, register::ValidatorError, Validatable, Validator};
+use valitron::{available::Required, register::ValidateError, Validatable, Validator};
 
 #[derive(Deserialize, Serialize)]
 struct Info {
@@ -45,7 +45,7 @@ async fn main() -> std::io::Result<()> {
 #[derive(Debug, Error)]
 pub enum ServerError {
     #[error(transparent)]
-    ValidationError(#[from] ValidatorError<String>),
+    ValidationError(#[from] ValidateError<String>),
     //
     // other ...
 }
@@ -57,7 +57,7 @@ impl ResponseError for ServerError {
 
     fn error_response(&self) -> HttpResponse<BoxBody> {
         match self {
-            ServerError::ValidationError(msg) => {
+            ServerError::ValidationError(ValidateError::Invalid(msg)) => {
                 let mut result = String::new();
                 for (name, msg_vec) in msg.iter() {
                     result.push_str(&format!("[{}]", name.as_str()));
@@ -72,6 +72,10 @@ impl ResponseError for ServerError {
                 let message = format!("Input validation error: [{}]", result);
                 HttpResponse::with_body(self.status_code(), message).map_into_boxed_body()
             }
+            ServerError::ValidationError(err) => {
+                HttpResponse::with_body(StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+                    .map_into_boxed_body()
+            }
         }
     }
 }
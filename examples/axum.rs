@@ -23,7 +23,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use valitron::{
     available::{Required, StartWith},
-    register::ValidatorError,
+    register::ValidateError,
     RuleExt, Validatable, Validator,
 };
 
@@ -62,7 +62,7 @@ async fn handler(Form(input): Form<BlogInput>) -> Result<Html<String>, ServerErr
 #[derive(Debug, Error)]
 pub enum ServerError {
     #[error(transparent)]
-    ValidationError(#[from] ValidatorError<String>),
+    ValidationError(#[from] ValidateError<String>),
 
     #[error(transparent)]
     AxumFormRejection(#[from] FormRejection),
@@ -71,7 +71,7 @@ pub enum ServerError {
 impl IntoResponse for ServerError {
     fn into_response(self) -> Response {
         match self {
-            ServerError::ValidationError(msg) => {
+            ServerError::ValidationError(ValidateError::Invalid(msg)) => {
                 let mut result = String::new();
                 for (name, msg_vec) in msg.iter() {
                     result.push_str(&format!("[{}]", name.as_str()));
@@ -86,6 +86,9 @@ impl IntoResponse for ServerError {
                 let message = format!("Input validation error: [{}]", result);
                 (StatusCode::BAD_REQUEST, message)
             }
+            ServerError::ValidationError(err) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+            }
             ServerError::AxumFormRejection(_) => (StatusCode::BAD_REQUEST, self.to_string()),
         }
         .into_response()
@@ -25,13 +25,17 @@ fn main() {
         name: "bar".into(),
         num: 9,
     };
-    let res = validator.validate(&input).unwrap_err();
+    let res = validator
+        .validate(&input)
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
 
     assert_eq!(res.get("name").unwrap()[0], MyMessage::NameStartWith);
     assert_eq!(res.get("num").unwrap()[0], MyMessage::Gt10);
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 enum MyMessage {
     NameRequierd,
     NameStartWith,
@@ -19,6 +19,7 @@ use valitron::{
 extern crate rocket;
 
 #[get("/?<name>&<second>")]
+#[allow(clippy::useless_format)]
 fn index(name: String, second: String) -> String {
     match (name, second).validate_mut(
         Validator::new()
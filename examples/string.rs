@@ -1,3 +1,6 @@
+// the example only shows the registration, some fields and arguments are not read
+#![allow(dead_code, unused_variables)]
+
 use valitron::{
     available::{Email, Message, Required, Trim},
     register::string::Validator,
@@ -54,6 +57,7 @@ impl Input {
     }
 }
 
+#[allow(clippy::ptr_arg)]
 fn validate_password(pass: &mut String) -> Result<(), Message> {
     let upper = pass.find(char::is_uppercase);
     let lower = pass.find(char::is_lowercase);
@@ -67,6 +71,7 @@ fn validate_password(pass: &mut String) -> Result<(), Message> {
     }
 }
 
+#[allow(clippy::ptr_arg)]
 fn validate_gender(gender: &mut String) -> Result<(), Message> {
     Ok(())
 }
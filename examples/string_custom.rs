@@ -1,3 +1,6 @@
+// the example only shows the registration, some fields and arguments are not read
+#![allow(dead_code, unused_variables)]
+
 use diesel::{Connection, PgConnection, Queryable, Selectable};
 use valitron::{
     available::{Email, Trim},
@@ -82,6 +85,7 @@ struct UniqueEmail;
 impl StringRule for UniqueEmail {
     type Message = String;
     const NAME: &'static str = "unique_email";
+    #[allow(clippy::len_zero)]
     fn call(&mut self, data: &mut String) -> bool {
         use self::inputs::dsl::*;
         use diesel::prelude::*;
@@ -98,6 +102,7 @@ impl StringRule for UniqueEmail {
         results.len() == 0
     }
 
+    #[allow(clippy::useless_format)]
     fn message(&self) -> Self::Message {
         format!("email is existing")
     }
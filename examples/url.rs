@@ -23,12 +23,14 @@ struct Data {
     ip: String,
 }
 
+#[allow(clippy::ptr_arg)]
 fn valid_url(s: &mut String) -> Result<(), String> {
     Url::parse(s)
         .map_err(|_| "error parsing url".into())
         .map(|_| ())
 }
 
+#[allow(clippy::ptr_arg)]
 fn valid_ip(ip: &mut String) -> Result<(), String> {
     IpAddr::from_str(ip)
         .map(|_| ())
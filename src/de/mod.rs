@@ -5,7 +5,10 @@ use serde::de::{
     Unexpected, VariantAccess, Visitor,
 };
 
-use crate::value::Value;
+use crate::{
+    register::{FieldName, FieldNames},
+    value::Value,
+};
 
 #[cfg(test)]
 mod test;
@@ -20,7 +23,7 @@ impl Value {
     }
 
     #[cold]
-    fn unexpected(&self) -> Unexpected {
+    fn unexpected(&self) -> Unexpected<'_> {
         match self {
             Value::Uint8(n) => Unexpected::Unsigned(*n as u64),
             Value::Uint16(n) => Unexpected::Unsigned(*n as u64),
@@ -56,29 +59,60 @@ impl Value {
     }
 }
 
-#[derive(Debug)]
+/// error of deserializing data from [`Value`], it carries the message and the path being deserialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Error {
-    is_str: bool,
+    message: String,
+    path: Vec<FieldName>,
+}
+
+impl Error {
+    /// the message reported by `Deserialize` implementation
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// the path of the value being deserialized, e.g. `items[1].name`
+    pub fn path(&self) -> FieldNames {
+        self.path.clone().into()
+    }
+
+    /// prepend the name of parent value
+    fn at(mut self, name: FieldName) -> Self {
+        self.path.insert(0, name);
+        self
+    }
 }
 
 impl serde::de::Error for Error {
-    fn custom<T>(_: T) -> Self {
-        Self { is_str: false }
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Self {
+            message: msg.to_string(),
+            path: Vec::new(),
+        }
     }
 }
 
 impl std::error::Error for Error {}
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.is_str {
-            "valitron unsupport &str deserializer, use #[serde(skip_deserializing)] ignore it"
-                .fmt(f)
+        if self.path.is_empty() {
+            self.message.fmt(f)
         } else {
-            "deseralize error".fmt(f)
+            write!(f, "{} at `{}`", self.message, self.path().as_str())
         }
     }
 }
 
+/// the field name of the map key, used by error path
+fn key_name(key: &Value) -> FieldName {
+    match key {
+        Value::StructKey(s) => FieldName::Literal(s.clone()),
+        Value::StructVariantKey(s) => FieldName::StructVariant(s.clone()),
+        other => FieldName::Key(other.to_string()),
+    }
+}
+
 macro_rules! deserialize_primitive {
     ($method:ident, $type:ident, $visit:ident) => {
         fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -146,7 +180,9 @@ impl<'de> Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
-        Err(Error { is_str: true })
+        Err(serde::de::Error::custom(
+            "valitron unsupport &str deserializer, use #[serde(skip_deserializing)] ignore it",
+        ))
     }
 
     fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -184,7 +220,9 @@ impl<'de> Deserializer<'de> for Value {
     {
         if let Value::Option(val) = self {
             match *val {
-                Some(value) => visitor.visit_some(value),
+                Some(value) => visitor
+                    .visit_some(value)
+                    .map_err(|e| e.at(FieldName::Option)),
                 None => visitor.visit_none(),
             }
         } else {
@@ -223,7 +261,7 @@ impl<'de> Deserializer<'de> for Value {
         V: Visitor<'de>,
     {
         if let Value::NewtypeStruct(vec) = self {
-            visit_array(vec, visitor)
+            visit_array(vec, visitor, FieldName::tuple)
         } else {
             Err(self.invalid_type(&visitor))
         }
@@ -234,7 +272,7 @@ impl<'de> Deserializer<'de> for Value {
         V: Visitor<'de>,
     {
         if let Value::Array(vec) = self {
-            visit_array(vec, visitor)
+            visit_array(vec, visitor, FieldName::Array)
         } else {
             Err(self.invalid_type(&visitor))
         }
@@ -245,7 +283,7 @@ impl<'de> Deserializer<'de> for Value {
         V: Visitor<'de>,
     {
         if let Value::Tuple(vec) = self {
-            visit_array(vec, visitor)
+            visit_array(vec, visitor, FieldName::tuple)
         } else {
             Err(self.invalid_type(&visitor))
         }
@@ -261,7 +299,7 @@ impl<'de> Deserializer<'de> for Value {
         V: Visitor<'de>,
     {
         if let Value::TupleStruct(vec) = self {
-            visit_array(vec, visitor)
+            visit_array(vec, visitor, FieldName::tuple)
         } else {
             Err(self.invalid_type(&visitor))
         }
@@ -350,12 +388,16 @@ impl<'de> Deserializer<'de> for Value {
 
 struct SeqDeserializer {
     iter: IntoIter<Value>,
+    index: usize,
+    name: fn(usize) -> FieldName,
 }
 
 impl SeqDeserializer {
-    fn new(vec: Vec<Value>) -> Self {
+    fn new(vec: Vec<Value>, name: fn(usize) -> FieldName) -> Self {
         SeqDeserializer {
             iter: vec.into_iter(),
+            index: 0,
+            name,
         }
     }
 }
@@ -368,7 +410,11 @@ impl<'de> SeqAccess<'de> for SeqDeserializer {
         T: DeserializeSeed<'de>,
     {
         match self.iter.next() {
-            Some(value) => seed.deserialize(value).map(Some),
+            Some(value) => {
+                let name = (self.name)(self.index);
+                self.index += 1;
+                seed.deserialize(value).map(Some).map_err(|e| e.at(name))
+            }
             None => Ok(None),
         }
     }
@@ -381,11 +427,15 @@ impl<'de> SeqAccess<'de> for SeqDeserializer {
     }
 }
 
-fn visit_array<'de, V>(array: Vec<Value>, visitor: V) -> Result<V::Value, Error>
+fn visit_array<'de, V>(
+    array: Vec<Value>,
+    visitor: V,
+    name: fn(usize) -> FieldName,
+) -> Result<V::Value, Error>
 where
     V: Visitor<'de>,
 {
-    let mut deserializer = SeqDeserializer::new(array);
+    let mut deserializer = SeqDeserializer::new(array, name);
     visitor.visit_seq(&mut deserializer)
 }
 
@@ -450,7 +500,7 @@ impl<'de> VariantAccess<'de> for VariantDeserializer {
         // debug_assert!(self.tree.len()==0);
         let mut value = self.value;
         match value.pop() {
-            Some(v) => seed.deserialize(v),
+            Some(v) => seed.deserialize(v).map_err(|e| e.at(FieldName::Tuple(0))),
             None => Err(serde::de::Error::invalid_type(
                 Unexpected::UnitVariant,
                 &"newtype variant",
@@ -466,7 +516,7 @@ impl<'de> VariantAccess<'de> for VariantDeserializer {
             // TODO
             visitor.visit_unit()
         } else {
-            visit_array(self.value, visitor)
+            visit_array(self.value, visitor, FieldName::tuple)
         }
     }
 
@@ -485,7 +535,7 @@ impl<'de> VariantAccess<'de> for VariantDeserializer {
 
 struct MapDeserializer {
     iter: <BTreeMap<Value, Value> as IntoIterator>::IntoIter,
-    value: Option<Value>,
+    value: Option<(FieldName, Value)>,
 }
 
 impl MapDeserializer {
@@ -506,8 +556,9 @@ impl<'de> MapAccess<'de> for MapDeserializer {
     {
         match self.iter.next() {
             Some((key, value)) => {
-                self.value = Some(value);
-                seed.deserialize(key).map(Some)
+                let name = key_name(&key);
+                self.value = Some((name.clone(), value));
+                seed.deserialize(key).map(Some).map_err(|e| e.at(name))
             }
             None => Ok(None),
         }
@@ -518,7 +569,7 @@ impl<'de> MapAccess<'de> for MapDeserializer {
         T: DeserializeSeed<'de>,
     {
         match self.value.take() {
-            Some(value) => seed.deserialize(value),
+            Some((name, value)) => seed.deserialize(value).map_err(|e| e.at(name)),
             None => Err(serde::de::Error::custom("value is missing")),
         }
     }
@@ -171,3 +171,69 @@ fn skip_str() {
     let a = A::deserialize(value).unwrap();
     assert!(a.str.is_empty());
 }
+
+#[test]
+fn error_path() {
+    #[derive(Deserialize, Debug)]
+    struct Order {
+        #[allow(dead_code)]
+        items: Vec<Option<(u8, u8)>>,
+    }
+
+    let value = Value::Struct({
+        let mut map = BTreeMap::new();
+        map.insert(
+            Value::StructKey("items".into()),
+            Value::Array(vec![
+                Value::Option(Box::new(None)),
+                Value::Option(Box::new(Some(Value::Tuple(vec![
+                    Value::Uint8(1),
+                    Value::String("foo".into()),
+                ])))),
+            ]),
+        );
+        map
+    });
+
+    let err = Order::deserialize(value).unwrap_err();
+    assert_eq!(err.path().as_str(), "items[1]?.1");
+    assert_eq!(
+        err.to_string(),
+        "invalid type: string \"foo\", expected u8 at `items[1]?.1`"
+    );
+}
+
+#[test]
+fn error_path_long_tuple() {
+    use serde::de::{Deserializer, SeqAccess, Visitor};
+
+    struct Long;
+
+    impl<'de> Deserialize<'de> for Long {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct LongVisitor;
+
+            impl<'de> Visitor<'de> for LongVisitor {
+                type Value = Long;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    f.write_str("a long tuple")
+                }
+
+                fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Long, A::Error> {
+                    while seq.next_element::<u8>()?.is_some() {}
+                    Ok(Long)
+                }
+            }
+
+            deserializer.deserialize_tuple(300, LongVisitor)
+        }
+    }
+
+    let mut items = vec![Value::Uint8(1); 300];
+    items[256] = Value::String("foo".into());
+
+    // the position out of `u8` is not truncated to `.0`
+    let err = Long::deserialize(Value::Tuple(items)).err().unwrap();
+    assert_eq!(err.path().as_str(), "[256]");
+}
@@ -33,7 +33,11 @@
 //!     weight: 20.0,
 //! };
 //!
-//! let res = validator.validate(person).unwrap_err();
+//! let res = validator
+//!     .validate(person)
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
 //! assert!(res.len() == 2);
 //! # }
 //!
@@ -55,17 +59,22 @@
 //!
 //! ## Available Rules
 //!
+//! - [`Coerce`]: `ToInt`, `ToFloat`, `ToBool` and `ToChar`
+//! - [`Collection`]: `Unique`, `DistinctBy`, `ContainsItem` and `Subset`
 //! - [`Compare`]
+//! - [`Conditional`]
 //! - [`Confirm`]
 //! - [`Contains`]
 //! - [`Email`]
 //! - [`EndWith`]
 //! - [`Length`]
 //! - [`Not`]
+//! - [`Nullable`]
 //! - [`Range`]
 //! - [`Regex`]
 //! - [`Required`]
 //! - [`StartWith`]
+//! - [`Transform`]: `Lowercase`, `Truncate`, `Clamp` and so on
 //! - [`Trim`]
 //! - customizable
 //!
@@ -76,6 +85,20 @@
 //! valitron = { version = "0.1", features = ["full"] }
 //! ```
 //!
+//! ## JSON
+//!
+//! With `serde_json` feature, `serde_json::Value` can be validated directly by
+//! [`validate_json`], the JSON object keys are matched by field names, e.g. `user.name`.
+//!
+//! [`validate_json`]: crate::register::Validator::validate_json
+//!
+//! ## Derive
+//!
+//! With `derive` feature, the validator can be generated from field attributes
+//! by `#[derive(Validate)]`, see [`Validate`] for more.
+//!
+//! [`Validate`]: https://docs.rs/valitron-derive/latest/valitron_derive/derive.Validate.html
+//!
 //! ## Closure Rule
 //!
 //! This is support closure with a primitive type mutable reference arguments and returning `message type`.
@@ -93,14 +116,19 @@
 //! [`Message`]: crate::available::Message
 //! [`Required`]: crate::available::required
 //! [`Email`]: crate::available::email
+//! [`Coerce`]: crate::available::coerce
+//! [`Collection`]: crate::available::collection
 //! [`Compare`]: crate::available::compare
 //! [`Contains`]: crate::available::contains
 //! [`StartWith`]: crate::available::start_with
 //! [`EndWith`]: crate::available::end_with
+//! [`Conditional`]: crate::available::conditional
 //! [`Confirm`]: crate::available::confirm
+//! [`Transform`]: crate::available::transform
 //! [`Trim`]: crate::available::trim
 //! [`Length`]: crate::available::length
 //! [`Not`]: crate::available::not
+//! [`Nullable`]: crate::available::nullable
 //! [`Range`]: crate::available::range
 //! [`Regex`]: crate::available::regex
 //! [string]: crate::register::string
@@ -120,8 +148,11 @@ pub mod value;
 pub(crate) mod macros;
 
 pub use register::{ValidPhrase, Validatable, Validator};
-pub use rule::{custom, Rule, RuleExt};
+pub use rule::{custom, AsyncRule, Rule, RuleExt};
 pub use value::{FromValue, Value, ValueMap};
 
 #[cfg(feature = "full")]
 pub use rule::available;
+
+#[cfg(feature = "derive")]
+pub use valitron_derive::Validate;
@@ -0,0 +1,151 @@
+//! typed errors of building validator and validating data
+
+use std::{error::Error, fmt::Display};
+
+use super::ValidatorError;
+
+pub use crate::{
+    de::Error as DeserializeError,
+    ser::Error as SerializeError,
+    value::json_text::{Location, SyntaxError},
+};
+
+/// error of building validator, it is returned by [`try_rule`] and [`try_message`]
+///
+/// [`try_rule`]: super::InnerValidator::try_rule
+/// [`try_message`]: super::Validator::try_message
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// the field path syntax is invalid
+    InvalidField { field: String, reason: String },
+
+    /// the rule name is invalid, only `a-z` | `A-Z` | `0-9` | `_` are allowed, and not start with `0-9`
+    InvalidRuleName { field: String, rule: String },
+
+    /// the message key is not formatted as `field_name.rule_name`
+    InvalidMessageKey { key: String },
+
+    /// the message key refers to a field which is not registered
+    FieldNotFound { field: String },
+
+    /// the message key refers to a rule which is not registered on the field
+    RuleNotFound { field: String, rule: String },
+
+    /// the rule configuration is invalid, `index` is the position in the rule list of the field,
+    /// it is returned by `Validator::from_spec`
+    InvalidRule {
+        field: String,
+        index: usize,
+        reason: String,
+    },
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidField { field, reason } => {
+                write!(f, "field `{field}` is invalid: {reason}")
+            }
+            Self::InvalidRuleName { field, rule } => {
+                write!(f, "rule name `{rule}` of field `{field}` is invalid")
+            }
+            Self::InvalidMessageKey { key } => write!(f, "message key `{key}` is invalid"),
+            Self::FieldNotFound { field } => write!(f, "field `{field}` is not found in validator"),
+            Self::RuleNotFound { field, rule } => {
+                write!(f, "rule `{rule}` is not found in field `{field}`")
+            }
+            Self::InvalidRule {
+                field,
+                index,
+                reason,
+            } => write!(f, "rule #{index} of field `{field}` is invalid: {reason}"),
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+/// error of validating data, it is returned by [`validate`] and [`validate_mut`]
+///
+/// [`validate`]: super::Validator::validate
+/// [`validate_mut`]: super::Validator::validate_mut
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ValidateError<M> {
+    /// the data is not passed by rules
+    Invalid(ValidatorError<M>),
+
+    /// the registered field is not found in the data
+    FieldNotFound { field: String },
+
+    /// the async rules are registered on the field, the data should be validated by
+    /// [`validate_async`](super::Validator::validate_async) or
+    /// [`validate_mut_async`](super::Validator::validate_mut_async)
+    AsyncRules { field: String },
+
+    /// the rule of the field requires a context of the `expected` type, but it is not passed,
+    /// or the passed context is other type, see [`validate_with`](super::Validator::validate_with)
+    MissingContext {
+        field: String,
+        expected: &'static str,
+    },
+
+    /// the data can not be serialized, it carries the message and the path
+    Serialize(SerializeError),
+
+    /// the modified data can not be deserialized, it carries the message and the path
+    Deserialize(DeserializeError),
+
+    /// the JSON text is malformed, it carries the reason and the location,
+    /// it is returned by [`validate_json_str`](super::Validator::validate_json_str)
+    Syntax(SyntaxError),
+}
+
+impl<M> ValidateError<M> {
+    /// the messages of rules, it is `None` for the other errors
+    pub fn invalid(&self) -> Option<&ValidatorError<M>> {
+        match self {
+            Self::Invalid(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// take the messages of rules, it is `None` for the other errors
+    pub fn into_invalid(self) -> Option<ValidatorError<M>> {
+        match self {
+            Self::Invalid(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl<M> From<ValidatorError<M>> for ValidateError<M> {
+    fn from(value: ValidatorError<M>) -> Self {
+        Self::Invalid(value)
+    }
+}
+
+impl<M> Display for ValidateError<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(err) => err.fmt(f),
+            Self::FieldNotFound { field } => write!(f, "field `{field}` is not found"),
+            Self::AsyncRules { field } => {
+                write!(
+                    f,
+                    "field `{field}` has async rules, please use `validate_async`"
+                )
+            }
+            Self::MissingContext { field, expected } => write!(
+                f,
+                "field `{field}` requires a context of `{expected}`, please use `validate_with`"
+            ),
+            Self::Serialize(err) => write!(f, "serialize error: {err}"),
+            Self::Deserialize(err) => write!(f, "deserialize error: {err}"),
+            Self::Syntax(err) => write!(f, "syntax error: {err}"),
+        }
+    }
+}
+
+impl<M: std::fmt::Debug> Error for ValidateError<M> {}
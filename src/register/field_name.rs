@@ -1,7 +1,8 @@
 use std::{
-    convert::Infallible,
+    borrow::Cow,
     fmt::Display,
     hash::{Hash, Hasher},
+    sync::Arc,
 };
 
 use serde::Serialize;
@@ -20,6 +21,12 @@ pub enum FieldName {
 
     /// get `g` on enum A { Color{ r:u8, g:u8, b:u8}}
     StructVariant(String),
+
+    /// match every item of array or every value of map, written as `[*]`
+    Wildcard,
+
+    /// get value of map by key, written as `["key"]`
+    Key(String),
 }
 
 impl FieldName {
@@ -27,9 +34,15 @@ impl FieldName {
         match self {
             FieldName::Literal(s) => s.as_str(),
             FieldName::StructVariant(s) => s.as_str(),
+            FieldName::Key(s) => s.as_str(),
             _ => "",
         }
     }
+
+    /// the tuple index is `u8`, the position of longer tuple is kept as array index
+    pub(crate) fn tuple(index: usize) -> Self {
+        u8::try_from(index).map_or(FieldName::Array(index), FieldName::Tuple)
+    }
 }
 
 impl Display for FieldName {
@@ -40,6 +53,8 @@ impl Display for FieldName {
             FieldName::Tuple(n) => n.fmt(f),
             FieldName::Option => "?".fmt(f),
             FieldName::StructVariant(s) => s.fmt(f),
+            FieldName::Wildcard => "*".fmt(f),
+            FieldName::Key(s) => s.fmt(f),
         }
     }
 }
@@ -71,14 +86,44 @@ fn names_to_string(vec: &[FieldName]) -> String {
                 string.push_str(s);
                 string.push(']');
             }
+            FieldName::Wildcard => string.push_str("[*]"),
+            FieldName::Key(s) => {
+                string.push_str("[\"");
+                for ch in s.chars() {
+                    if ch == '"' || ch == '\\' {
+                        string.push('\\');
+                    }
+                    string.push(ch);
+                }
+                string.push_str("\"]");
+            }
         }
     }
     string
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[derive(Clone, Default)]
 pub struct FieldNames {
     string: String,
+    /// the parsed names, they are kept when the names are created, so the path
+    /// is not parsed again on every validation, it is `None` for the invalid path
+    names: Option<Arc<[FieldName]>>,
+}
+
+impl PartialEq for FieldNames {
+    fn eq(&self, other: &Self) -> bool {
+        self.string == other.string
+    }
+}
+
+impl Eq for FieldNames {}
+
+impl std::fmt::Debug for FieldNames {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FieldNames")
+            .field("string", &self.string)
+            .finish()
+    }
 }
 
 impl Serialize for FieldNames {
@@ -97,8 +142,29 @@ impl Hash for FieldNames {
 }
 
 impl FieldNames {
+    /// the names are parsed once and kept, the invalid names are parsed again when they are used
     pub(crate) fn new(string: String) -> Self {
-        Self { string }
+        let names = parse(&string).ok().map(Into::into);
+        Self { string, names }
+    }
+
+    /// check the names are valid, it is called when the names are registered
+    pub(crate) fn into_parsed(self) -> Result<Self, ParserError> {
+        match self.names {
+            Some(_) => Ok(self),
+            None => parse(&self.string).map(|names| Self {
+                string: self.string,
+                names: Some(names.into()),
+            }),
+        }
+    }
+
+    /// the parsed names, the path is parsed when they are not kept
+    pub(crate) fn names(&self) -> Result<Cow<'_, [FieldName]>, ParserError> {
+        match &self.names {
+            Some(names) => Ok(Cow::Borrowed(names)),
+            None => parse(&self.string).map(Cow::Owned),
+        }
     }
 
     // pub fn iter(&self) -> Iter<'_, FieldName> {
@@ -107,21 +173,46 @@ impl FieldNames {
     pub fn as_str(&self) -> &str {
         &self.string
     }
+
+    /// check `self` is `prefix` or under it, e.g. `home.street` is under `home`,
+    /// the invalid names are not under any prefix
+    pub(crate) fn starts_with(&self, prefix: &FieldNames) -> bool {
+        match (self.names(), prefix.names()) {
+            (Ok(names), Ok(prefix)) => names.starts_with(&prefix),
+            _ => false,
+        }
+    }
+
+    /// rebase the `other` names under `self`, e.g. `home` and `street` is `home.street`,
+    /// `home` and `[0]` is `home[0]`
+    pub(crate) fn join(&self, other: &FieldNames) -> FieldNames {
+        if self.string.is_empty() {
+            return other.clone();
+        }
+        let string = if other.string.starts_with('[') {
+            format!("{}{}", self.string, other.string)
+        } else {
+            format!("{}.{}", self.string, other.string)
+        };
+        let names = match (&self.names, &other.names) {
+            (Some(names), Some(other)) => Some(names.iter().chain(other.iter()).cloned().collect()),
+            _ => None,
+        };
+        Self { string, names }
+    }
 }
 
 impl From<Vec<FieldName>> for FieldNames {
     fn from(value: Vec<FieldName>) -> Self {
         Self {
             string: names_to_string(&value),
+            names: Some(value.into()),
         }
     }
 }
 impl From<FieldName> for FieldNames {
     fn from(value: FieldName) -> Self {
-        let vec = vec![value];
-        Self {
-            string: names_to_string(&vec),
-        }
+        Self::from(vec![value])
     }
 }
 impl<const N: usize> From<[FieldName; N]> for FieldNames {
@@ -133,61 +224,43 @@ impl<const N: usize> From<[FieldName; N]> for FieldNames {
 
 impl From<String> for FieldNames {
     fn from(string: String) -> Self {
-        Self { string }
+        Self::new(string)
     }
 }
 impl From<&str> for FieldNames {
     fn from(string: &str) -> Self {
-        Self {
-            string: string.to_owned(),
-        }
+        Self::new(string.to_owned())
     }
 }
 
 /// Convert to FieldName trait
 pub trait IntoFieldName {
-    type Error: std::fmt::Display;
-    fn into_field(self) -> Result<FieldNames, Self::Error>;
+    fn into_field(self) -> FieldNames;
 }
 
 impl IntoFieldName for &str {
-    type Error = Infallible;
-    fn into_field(self) -> Result<FieldNames, Self::Error> {
-        Ok(FieldNames {
-            string: self.to_string(),
-        })
+    fn into_field(self) -> FieldNames {
+        FieldNames::new(self.to_string())
     }
 }
 impl IntoFieldName for u8 {
-    type Error = Infallible;
-    fn into_field(self) -> Result<FieldNames, Self::Error> {
-        Ok(FieldNames {
-            string: self.to_string(),
-        })
+    fn into_field(self) -> FieldNames {
+        FieldNames::new(self.to_string())
     }
 }
 impl IntoFieldName for (u8, u8) {
-    type Error = Infallible;
-    fn into_field(self) -> Result<FieldNames, Self::Error> {
-        Ok(FieldNames {
-            string: format!("{}.{}", self.0, self.1),
-        })
+    fn into_field(self) -> FieldNames {
+        FieldNames::new(format!("{}.{}", self.0, self.1))
     }
 }
 impl IntoFieldName for (u8, u8, u8) {
-    type Error = Infallible;
-    fn into_field(self) -> Result<FieldNames, Self::Error> {
-        Ok(FieldNames {
-            string: format!("{}.{}.{}", self.0, self.1, self.2),
-        })
+    fn into_field(self) -> FieldNames {
+        FieldNames::new(format!("{}.{}.{}", self.0, self.1, self.2))
     }
 }
 impl IntoFieldName for [usize; 1] {
-    type Error = Infallible;
-    fn into_field(self) -> Result<FieldNames, Self::Error> {
-        Ok(FieldNames {
-            string: format!("[{}]", self[0]),
-        })
+    fn into_field(self) -> FieldNames {
+        FieldNames::new(format!("[{}]", self[0]))
     }
 }
 // impl IntoFieldName for [&str; 1] {
@@ -201,9 +274,7 @@ impl<T> IntoFieldName for &'_ T
 where
     T: IntoFieldName + Copy,
 {
-    type Error = T::Error;
-
-    fn into_field(self) -> Result<FieldNames, Self::Error> {
+    fn into_field(self) -> FieldNames {
         T::into_field(*self)
     }
 }
@@ -242,6 +313,7 @@ impl<'a> Parser<'a> {
                 Ok(Some(res))
             }
             TokenKind::RightBracket => Err(ParserError::BracketRight),
+            TokenKind::Wildcard | TokenKind::Str => Err(ParserError::Undefined),
             TokenKind::Index => {
                 let index_str;
                 (index_str, self.source) = self.source.split_at(token.len);
@@ -265,7 +337,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    /// parse `[0]` or `[abc]`
+    /// parse `[0]`, `[abc]`, `[*]` or `["abc"]`
     fn parse_bracket(&mut self) -> Result<FieldName, ParserError> {
         let mut peek = self.token.clone();
         let t = peek.advance();
@@ -327,6 +399,58 @@ impl<'a> Parser<'a> {
                     return Ok(name);
                 }
             }
+            TokenKind::Str => {
+                if let Token {
+                    kind: TokenKind::RightBracket,
+                    ..
+                } = peek.advance()
+                {
+                    let str;
+                    (str, self.source) = self.source.split_at(t.len);
+                    let name = FieldName::Key(unescape(&str[1..str.len() - 1]));
+
+                    // eat string
+                    self.token.advance();
+                    // eat `]`
+                    self.token.advance();
+                    self.source = &self.source[1..];
+
+                    if !(self.expect(TokenKind::Dot)
+                        || self.expect(TokenKind::LeftBracket)
+                        || self.expect(TokenKind::Eof)
+                        || self.expect(TokenKind::Option))
+                    {
+                        return Err(ParserError::ArrayClose);
+                    }
+
+                    self.eat_dot()?;
+                    return Ok(name);
+                }
+            }
+            TokenKind::Wildcard => {
+                if let Token {
+                    kind: TokenKind::RightBracket,
+                    ..
+                } = peek.advance()
+                {
+                    // eat `*`
+                    self.token.advance();
+                    // eat `]`
+                    self.token.advance();
+                    self.source = &self.source[2..];
+
+                    if !(self.expect(TokenKind::Dot)
+                        || self.expect(TokenKind::LeftBracket)
+                        || self.expect(TokenKind::Eof)
+                        || self.expect(TokenKind::Option))
+                    {
+                        return Err(ParserError::ArrayClose);
+                    }
+
+                    self.eat_dot()?;
+                    return Ok(FieldName::Wildcard);
+                }
+            }
             _ => return Err(ParserError::BracketSyntaxError),
         }
 
@@ -359,7 +483,19 @@ impl<'a> Parser<'a> {
     }
 }
 
-#[cfg(test)]
+/// remove escape `\` of quoted string
+fn unescape(source: &str) -> String {
+    let mut string = String::with_capacity(source.len());
+    let mut chars = source.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => string.extend(chars.next()),
+            ch => string.push(ch),
+        }
+    }
+    string
+}
+
 pub(crate) fn parse(source: &str) -> Result<Vec<FieldName>, ParserError> {
     let mut parser = Parser::new(source);
 
@@ -372,7 +508,7 @@ pub(crate) fn parse(source: &str) -> Result<Vec<FieldName>, ParserError> {
     }
 }
 
-pub fn parse_message(source: &str) -> Result<MessageKey, String> {
+pub fn parse_message(source: &str) -> Result<MessageKey<'_>, String> {
     let (name_str, string) = source
         .rsplit_once('.')
         .ok_or("not found message".to_owned())?;
@@ -472,4 +608,60 @@ fn test_parse() {
             FieldName::Tuple(0),
         ]
     );
+
+    let names = parse("items[*].name").unwrap();
+    assert_eq!(
+        names,
+        vec![
+            FieldName::Literal("items".into()),
+            FieldName::Wildcard,
+            FieldName::Literal("name".into()),
+        ]
+    );
+    assert_eq!(FieldNames::from(names).as_str(), "items[*].name");
+
+    let names = parse("[*][*]?").unwrap();
+    assert_eq!(
+        names,
+        vec![FieldName::Wildcard, FieldName::Wildcard, FieldName::Option]
+    );
+
+    let names = parse(r#"settings["max.conn"].value"#).unwrap();
+    assert_eq!(
+        names,
+        vec![
+            FieldName::Literal("settings".into()),
+            FieldName::Key("max.conn".into()),
+            FieldName::Literal("value".into()),
+        ]
+    );
+
+    let names = parse(r#"["a \"b\" \\ c"][*]"#).unwrap();
+    assert_eq!(
+        names,
+        vec![FieldName::Key(r#"a "b" \ c"#.into()), FieldName::Wildcard]
+    );
+    assert_eq!(FieldNames::from(names).as_str(), r#"["a \"b\" \\ c"][*]"#);
+
+    parse(r#"settings."foo""#).unwrap_err();
+    parse(r#"settings["foo"#).unwrap_err();
+    parse(r#"settings["foo"]bar"#).unwrap_err();
+    parse("items.*").unwrap_err();
+    parse("items[*]name").unwrap_err();
+    parse("items[*").unwrap_err();
+}
+
+#[test]
+fn test_join() {
+    let home = FieldNames::new("home".into());
+
+    assert_eq!(home.join(&"street".into()).as_str(), "home.street");
+    assert_eq!(home.join(&"[0]".into()).as_str(), "home[0]");
+    assert_eq!(home.join(&"0.1".into()).as_str(), "home.0.1");
+    assert_eq!(
+        FieldNames::new("work?".into())
+            .join(&"street".into())
+            .as_str(),
+        "work?.street"
+    );
 }
@@ -0,0 +1,73 @@
+//! introspection of registered fields and rules, see [`Validator::rules`]
+//!
+//! [`Validator::rules`]: super::Validator::rules
+
+use crate::{rule::Params, Value};
+
+/// Information of a registered rule, it is used to build admin screens or documents
+/// from a validator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleInfo<'a, M> {
+    name: &'static str,
+    params: Params,
+    is_bail: bool,
+    is_async: bool,
+    message: Option<&'a M>,
+}
+
+impl<'a, M> RuleInfo<'a, M> {
+    pub(crate) fn new(name: &'static str, params: Params, is_bail: bool) -> Self {
+        Self {
+            name,
+            params,
+            is_bail,
+            is_async: false,
+            message: None,
+        }
+    }
+
+    pub(crate) fn set_async(mut self) -> Self {
+        self.is_async = true;
+        self
+    }
+
+    pub(crate) fn set_message(mut self, message: Option<&'a M>) -> Self {
+        self.message = message;
+        self
+    }
+
+    /// the rule name, e.g. `required`, `custom` for closure rules
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// the parameters of rule, see [`Params`]
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// get the parameter by name, e.g. `min` of [`Length`]
+    ///
+    /// [`Length`]: crate::available::Length
+    pub fn param(&self, name: &str) -> Option<&Value> {
+        self.params
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// the rules of the field are stopped at the first failure
+    pub fn is_bail(&self) -> bool {
+        self.is_bail
+    }
+
+    /// the rule is registered by `rule_async`
+    pub fn is_async(&self) -> bool {
+        self.is_async
+    }
+
+    /// the custom message registered by `message`
+    pub fn message(&self) -> Option<&'a M> {
+        self.message
+    }
+}
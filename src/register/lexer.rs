@@ -20,6 +20,12 @@ pub enum TokenKind {
     /// match `]`
     RightBracket,
 
+    /// match `*`
+    Wildcard,
+
+    /// match quoted string, e.g. `"foo.bar"`, supported escape `\"` and `\\`
+    Str,
+
     /// undefined
     Undefined,
 
@@ -78,6 +84,20 @@ impl<'a> Cursor<'a> {
             '[' => (TokenKind::LeftBracket, 1),
             ']' => (TokenKind::RightBracket, 1),
             '?' => (TokenKind::Option, 1),
+            '*' => (TokenKind::Wildcard, 1),
+            '"' => {
+                let mut escape = false;
+                loop {
+                    match self.char.next() {
+                        Some((_, '\\')) if !escape => escape = true,
+                        Some((end_usize, '"')) if !escape => {
+                            break (TokenKind::Str, end_usize - start_usize + 1);
+                        }
+                        Some(_) => escape = false,
+                        None => break (TokenKind::Undefined, 1),
+                    }
+                }
+            }
             'a'..='z' | 'A'..='Z' | '_' => {
                 let mut iter = self.char.clone().peekable();
                 let mut current_usize = start_usize;
@@ -163,5 +183,25 @@ mod test {
         assert_eq!(vec.advance().kind(), &TokenKind::Index);
         assert_eq!(vec.advance().kind(), &TokenKind::RightBracket);
         assert_eq!(vec.advance().kind(), &TokenKind::Eof);
+
+        let mut vec = Cursor::new(r#"abc["d.e\"f"]"#);
+        assert_eq!(vec.advance().kind(), &TokenKind::Ident);
+        assert_eq!(vec.advance().kind(), &TokenKind::LeftBracket);
+        let str = vec.advance();
+        assert_eq!(str.kind(), &TokenKind::Str);
+        assert_eq!(str.len, 8);
+        assert_eq!(vec.advance().kind(), &TokenKind::RightBracket);
+        assert_eq!(vec.advance().kind(), &TokenKind::Eof);
+
+        let mut vec = Cursor::new(r#"["abc"#);
+        assert_eq!(vec.advance().kind(), &TokenKind::LeftBracket);
+        assert_eq!(vec.advance().kind(), &TokenKind::Undefined);
+
+        let mut vec = Cursor::new("abc[*]");
+        assert_eq!(vec.advance().kind(), &TokenKind::Ident);
+        assert_eq!(vec.advance().kind(), &TokenKind::LeftBracket);
+        assert_eq!(vec.advance().kind(), &TokenKind::Wildcard);
+        assert_eq!(vec.advance().kind(), &TokenKind::RightBracket);
+        assert_eq!(vec.advance().kind(), &TokenKind::Eof);
     }
 }
@@ -2,15 +2,22 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{rule::IntoRuleList, ser::Serializer, Validatable, Value, ValueMap};
+use crate::{
+    rule::{IntoRuleList, Local, Shared},
+    Validatable, Value, ValueMap,
+};
 
-use super::{field_name, FieldNames, InnerValidator, IntoFieldName, MessageKey, ValidatorError};
+use super::{
+    field_name, BuildError, FieldNames, InnerValidator, IntoFieldName, MessageKey, Presence,
+    ValidateError, ValidatorError,
+};
 
 pub trait IntoMessage {
     fn into_message(rule: &'static str, field: &FieldNames, value: &Value) -> Self;
 }
 
-type CoreValidator<'v> = InnerValidator<String, HashMap<FieldNames, HashMap<&'v str, &'v str>>>;
+type CoreValidator<'v, K> =
+    InnerValidator<String, HashMap<FieldNames, HashMap<&'v str, &'v str>>, K>;
 
 /// register a string message validator
 /// ## This is an example:
@@ -41,7 +48,11 @@ type CoreValidator<'v> = InnerValidator<String, HashMap<FieldNames, HashMap<&'v
 ///         age: 18,
 ///         weight: 20.0,
 ///     };
-///     let res = validator.validate(person).unwrap_err();
+///     let res = validator
+///         .validate(person)
+///         .unwrap_err()
+///         .into_invalid()
+///         .unwrap();
 ///     assert!(res.len() == 2);
 ///     assert_eq!(res.get("introduce").unwrap()[0], "introduce should be starts with `I am`");
 ///     assert_eq!(res.get("age").unwrap()[0], "age 18 is not in the range");
@@ -94,47 +105,54 @@ type CoreValidator<'v> = InnerValidator<String, HashMap<FieldNames, HashMap<&'v
 /// }
 /// ```
 #[derive(Default, Clone)]
-pub struct ValidPhrase<'v>(CoreValidator<'v>);
+pub struct ValidPhrase<'v, K = Shared>(CoreValidator<'v, K>);
 
-impl<'v> ValidPhrase<'v> {
+impl ValidPhrase<'_> {
     /// init a new ValidPhrase
     pub fn new() -> Self {
         Self::default()
     }
+}
+
+impl ValidPhrase<'_, Local> {
+    /// init a new ValidPhrase accepting the rules which are not `Send + Sync`,
+    /// same as [`Validator::local`]
+    ///
+    /// [`Validator::local`]: crate::Validator::local
+    pub fn local() -> Self {
+        Self::default()
+    }
+}
 
-    /// validate given data
-    pub fn validate<T>(self, data: T) -> Result<(), ValidatorError<String>>
+impl<'v, K> ValidPhrase<'v, K> {
+    /// validate given data, same as [`Validator::validate`]
+    ///
+    /// [`Validator::validate`]: crate::Validator::validate
+    pub fn validate<T>(&self, data: T) -> Result<(), ValidateError<String>>
     where
         T: Serialize,
     {
-        let value = data.serialize(Serializer).unwrap();
+        let mut value_map = self.0.try_value_map(&data)?;
 
-        debug_assert!(self.0.exist_field(&value));
-
-        let mut value_map = ValueMap::new(value);
-
-        self.inner_validate(&mut value_map).ok()
+        self.inner_validate(&mut value_map)?.ok()?;
+        Ok(())
     }
 
     /// validate given data and can modify it
-    pub fn validate_mut<'de, T>(self, data: T) -> Result<T, ValidatorError<String>>
+    pub fn validate_mut<'de, T>(&self, data: T) -> Result<T, ValidateError<String>>
     where
         T: Serialize + serde::de::Deserialize<'de>,
     {
-        let value = data.serialize(Serializer).unwrap();
-
-        debug_assert!(self.0.exist_field(&value));
+        let mut value_map = self.0.try_value_map(&data)?;
 
-        let mut value_map = ValueMap::new(value);
+        self.inner_validate(&mut value_map)?.ok()?;
 
-        self.inner_validate(&mut value_map)
-            .ok()
-            .map(|_| T::deserialize(value_map.value()).unwrap())
+        T::deserialize(value_map.value()).map_err(ValidateError::Deserialize)
     }
 
     /// custom validation message
     pub fn message<const N: usize>(mut self, list: [(&'v str, &'v str); N]) -> Self {
-        list.map(|(key_str, v)| {
+        for (key_str, v) in list {
             let MessageKey { fields, rule } =
                 crate::panic_on_err!(field_name::parse_message(key_str));
 
@@ -148,25 +166,54 @@ impl<'v> ValidPhrase<'v> {
                 "rule \"{rule}\" is not found in rules"
             );
 
-            self.0
-                .message
-                .entry(fields)
-                .and_modify(|field| {
-                    field
-                        .entry(rule)
-                        .and_modify(|msg| {
-                            *msg = v;
-                        })
-                        .or_insert(v);
-                })
-                .or_insert({
-                    let mut map = HashMap::new();
-                    map.insert(rule, v);
-                    map
-                });
-        });
-
-        Self(self.0)
+            self.insert_message(fields, rule, v);
+        }
+
+        self
+    }
+
+    /// custom validation message, without panic
+    ///
+    /// same as [`message`], but return [`BuildError`] when the key format is invalid,
+    /// or the field or rule is not existing.
+    ///
+    /// [`message`]: Self::message
+    pub fn try_message<const N: usize>(
+        mut self,
+        list: [(&'v str, &'v str); N],
+    ) -> Result<Self, BuildError> {
+        for (key_str, v) in list {
+            let msg_key =
+                field_name::parse_message(key_str).map_err(|_| BuildError::InvalidMessageKey {
+                    key: key_str.to_owned(),
+                })?;
+
+            self.0.check_message(&msg_key)?;
+
+            let MessageKey { fields, rule } = msg_key;
+            self.insert_message(fields, rule, v);
+        }
+
+        Ok(self)
+    }
+
+    fn insert_message(&mut self, fields: FieldNames, rule: &'v str, v: &'v str) {
+        self.0
+            .message
+            .entry(fields)
+            .and_modify(|field| {
+                field
+                    .entry(rule)
+                    .and_modify(|msg| {
+                        *msg = v;
+                    })
+                    .or_insert(v);
+            })
+            .or_insert({
+                let mut map = HashMap::new();
+                map.insert(rule, v);
+                map
+            });
     }
 
     // pub fn map<M2>(self, f: fn(message: &'v str) -> M2) -> CoreValidator<'v, M2>
@@ -180,63 +227,91 @@ impl<'v> ValidPhrase<'v> {
     pub fn rule<F, R>(self, field: F, rule: R) -> Self
     where
         F: IntoFieldName,
-        R: IntoRuleList<ValueMap, String>,
+        R: IntoRuleList<ValueMap, String, K>,
     {
         Self(self.0.rule(field, rule))
     }
 
+    /// register rules, without panic, same as [`Validator::try_rule`]
+    ///
+    /// [`Validator::try_rule`]: crate::Validator::try_rule
+    pub fn try_rule<F, R>(self, field: F, rule: R) -> Result<Self, BuildError>
+    where
+        F: IntoFieldName,
+        R: IntoRuleList<ValueMap, String, K>,
+    {
+        self.0.try_rule(field, rule).map(Self)
+    }
+
+    /// how to handle the field absent in the data, same as [`Validator::presence`]
+    ///
+    /// [`Validator::presence`]: crate::Validator::presence
+    pub fn presence<F>(self, field: F, presence: Presence) -> Self
+    where
+        F: IntoFieldName,
+    {
+        Self(self.0.presence(field, presence))
+    }
+
+    /// run the rules of the field only if it is present, same as [`Validator::sometimes`]
+    ///
+    /// [`Validator::sometimes`]: crate::Validator::sometimes
+    pub fn sometimes<F>(self, field: F) -> Self
+    where
+        F: IntoFieldName,
+    {
+        Self(self.0.sometimes(field))
+    }
+
     /// when first validate error is encountered, right away return Err(message).
     pub fn bail(self) -> Self {
         Self(self.0.bail())
     }
 
-    fn inner_validate(self, value_map: &mut ValueMap) -> ValidatorError<String> {
-        let mut resp_message = ValidatorError::with_capacity(self.0.rules.len());
-
-        let ValidPhrase(InnerValidator {
-            rules,
-            message,
-            is_bail,
-        }) = self;
-
+    fn inner_validate(
+        &self,
+        value_map: &mut ValueMap,
+    ) -> Result<ValidatorError<String>, ValidateError<String>> {
         let default_map = HashMap::new();
 
-        for (mut names, mut rules) in rules.into_iter() {
-            if is_bail {
-                rules.set_bail();
-            }
-
-            let msgs = message.get(&names).unwrap_or(&default_map);
-
-            value_map.index(names);
-
-            let field_msg = rules.call_string_message(value_map, msgs);
-
-            names = value_map.take_index();
-
-            resp_message.push(names, field_msg);
-
-            if is_bail && !resp_message.is_empty() {
-                resp_message.shrink_to(1);
-                return resp_message;
-            }
-        }
+        self.0.iter_validate(
+            value_map,
+            |names, rules, data, message| {
+                rules.call_string_message(data, message.get(names).unwrap_or(&default_map))
+            },
+            |_, _, _| self.0.required.iter().map(|f| f()).collect(),
+        )
+    }
+}
 
-        resp_message.shrink_to_fit();
+impl<'v, T, K> Validatable<ValidPhrase<'v, K>, ValidateError<String>> for T
+where
+    T: Serialize,
+{
+    fn validate(&self, validator: ValidPhrase<'v, K>) -> Result<(), ValidateError<String>> {
+        validator.validate(self)
+    }
 
-        resp_message
+    fn validate_mut<'de>(self, validator: ValidPhrase<'v, K>) -> Result<Self, ValidateError<String>>
+    where
+        Self: Deserialize<'de>,
+    {
+        validator.validate_mut(self)
     }
 }
 
-impl<'v, T> Validatable<ValidPhrase<'v>, ValidatorError<String>> for T
+impl<'a, 'v, T, K> Validatable<&'a ValidPhrase<'v, K>, ValidateError<String>> for T
 where
     T: Serialize,
 {
-    fn validate(&self, validator: ValidPhrase<'v>) -> Result<(), ValidatorError<String>> {
+    fn validate(&self, validator: &'a ValidPhrase<'v, K>) -> Result<(), ValidateError<String>> {
         validator.validate(self)
     }
 
-    fn validate_mut<'de>(self, validator: ValidPhrase<'v>) -> Result<Self, ValidatorError<String>>
+    fn validate_mut<'de>(
+        self,
+        validator: &'a ValidPhrase<'v, K>,
+    ) -> Result<Self, ValidateError<String>>
     where
         Self: Deserialize<'de>,
     {
@@ -264,6 +339,7 @@ mod tests {
             "{field} is default msg".to_string()
         }
 
+        #[allow(clippy::needless_bool)]
         fn call(&mut self, data: &mut Value) -> bool {
             if *data == 8_i8 {
                 true
@@ -300,7 +376,7 @@ mod tests {
             .rule("0", Required)
             .message([("0.required", "foo_message")]);
 
-        let res = validator.validate(num).unwrap_err();
+        let res = validator.validate(num).unwrap_err().into_invalid().unwrap();
 
         let (filed, msg) = res.into_iter().next().unwrap();
 
@@ -319,6 +395,120 @@ mod tests {
         num.validate(validator).unwrap_err();
     }
 
+    #[test]
+    fn reuse() {
+        let validator = ValidPhrase::new()
+            .rule("0", Required)
+            .message([("0.required", "foo_message")]);
+
+        for _ in 0..2 {
+            let res = validator
+                .validate((10_i8, 11_i8))
+                .unwrap_err()
+                .into_invalid()
+                .unwrap();
+            assert_eq!(res["0"][0], "foo_message");
+        }
+        (8_i8, 11_i8).validate(&validator).unwrap();
+    }
+
+    #[test]
+    fn option_field() {
+        use serde::Serialize;
+
+        use crate::custom;
+
+        #[derive(Serialize)]
+        struct Home {
+            number: u8,
+        }
+        #[derive(Serialize)]
+        struct Person {
+            home: Option<Home>,
+        }
+
+        let validator = ValidPhrase::new().rule(
+            "home?.number",
+            custom(|number: &mut u8| {
+                if *number > 0 {
+                    Ok(())
+                } else {
+                    Err("{field} should be positive".to_string())
+                }
+            }),
+        );
+
+        validator.validate(Person { home: None }).unwrap();
+        validator
+            .validate(Person {
+                home: Some(Home { number: 1 }),
+            })
+            .unwrap();
+
+        let res = validator
+            .validate(Person {
+                home: Some(Home { number: 0 }),
+            })
+            .unwrap_err()
+            .into_invalid()
+            .unwrap();
+        assert_eq!(res["home?.number"][0], "home?.number should be positive");
+
+        let validator = ValidPhrase::new()
+            .rule("0", Required)
+            .message([("0.required", "{value} is not 8")]);
+        let res = validator
+            .validate((Some(10_i8), 11_i8))
+            .unwrap_err()
+            .into_invalid()
+            .unwrap();
+        assert_eq!(res["0"][0], "10 is not 8");
+    }
+
+    #[test]
+    fn presence() {
+        use serde::Serialize;
+
+        #[derive(Serialize)]
+        struct Patch {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            name: Option<i8>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            email: Option<&'static str>,
+        }
+
+        let validator = ValidPhrase::new()
+            .rule("name", Required)
+            .rule("email", StartWith("foo"))
+            .sometimes("name")
+            .presence("email", Presence::Required);
+
+        let patch = Patch {
+            name: None,
+            email: None,
+        };
+        let res = validator
+            .validate(patch)
+            .unwrap_err()
+            .into_invalid()
+            .unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res["email"], ["this field is required"]);
+
+        let patch = Patch {
+            name: Some(10),
+            email: Some("bar@example.com"),
+        };
+        let res = validator
+            .validate(patch)
+            .unwrap_err()
+            .into_invalid()
+            .unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(res["name"], ["name is default msg"]);
+        assert_eq!(res["email"], ["this field must start with foo"]);
+    }
+
     #[test]
     fn field() {
         let num = (10_i8, 11_i8);
@@ -327,7 +517,7 @@ mod tests {
             .rule("0", Required)
             .message([("0.required", "{field} is required")]);
 
-        let res = validator.validate(num).unwrap_err();
+        let res = validator.validate(num).unwrap_err().into_invalid().unwrap();
 
         let (filed, msg) = res.into_iter().next().unwrap();
 
@@ -342,7 +532,7 @@ mod tests {
 
         let validator = ValidPhrase::new().rule("0", Required);
 
-        let res = validator.validate(num).unwrap_err();
+        let res = validator.validate(num).unwrap_err().into_invalid().unwrap();
 
         let (filed, msg) = res.into_iter().next().unwrap();
 
@@ -359,7 +549,7 @@ mod tests {
             .rule("0", Required)
             .message([("0.required", "{value} is error value, 8 is true value")]);
 
-        let res = validator.validate(num).unwrap_err();
+        let res = validator.validate(num).unwrap_err().into_invalid().unwrap();
 
         let (filed, msg) = res.into_iter().next().unwrap();
 
@@ -18,31 +18,46 @@
 //! [rules]: crate::available
 
 use std::{
-    collections::{
-        hash_map::{IntoIter, Iter, IterMut, Keys},
-        HashMap,
-    },
+    collections::HashMap,
     error::Error,
     fmt::Display,
     hash::{Hash, Hasher},
     ops::Index,
+    sync::Arc,
 };
 
 use crate::{
-    rule::{IntoRuleList, RuleList},
+    rule::{
+        asynchronous::{join_all, AsyncRuleList, BoxFuture, IntoAsyncRuleList},
+        Context, IntoRuleList, Local, Params, RuleList, Shared,
+    },
     ser::Serializer,
-    value::ValueMap,
+    value::{json_text, ValueMap},
     Value,
 };
 
+pub use error::{
+    BuildError, DeserializeError, Location, SerializeError, SyntaxError, ValidateError,
+};
+pub(crate) use field_name::{parse as parse_names, IntoFieldName};
 pub use field_name::{FieldName, FieldNames};
-pub(crate) use field_name::{IntoFieldName, Parser};
+use indexmap::{
+    map::{IntoIter, Iter, IterMut, Keys},
+    IndexMap,
+};
+pub use info::RuleInfo;
 pub use message::{IntoMessage, ValidPhrase};
+pub use schema::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+mod error;
 mod field_name;
+mod info;
 mod lexer;
 mod message;
+pub mod schema;
+#[cfg(feature = "full")]
+pub mod spec;
 pub mod string;
 #[cfg(test)]
 mod tests;
@@ -50,7 +65,8 @@ mod tests;
 /// register a validator
 /// ## This is an example:
 ///
-/// ```rust
+#[cfg_attr(feature = "full", doc = "```rust")]
+#[cfg_attr(not(feature = "full"), doc = "```ignore")]
 /// # use serde::{Deserialize, Serialize};
 /// # use valitron::{
 /// # available::{Required, StartWith, Message},
@@ -81,7 +97,11 @@ mod tests;
 ///     weight: 20.0,
 /// };
 ///
-/// let res = validator.validate(person).unwrap_err();
+/// let res = validator
+///     .validate(person)
+///     .unwrap_err()
+///     .into_invalid()
+///     .unwrap();
 /// assert!(res.len() == 2);
 /// # }
 ///
@@ -93,7 +113,46 @@ mod tests;
 ///     }
 /// }
 /// ```
-pub type Validator<'v, M> = InnerValidator<M, HashMap<MessageKey<'v>, M>>;
+///
+/// ## Share between threads
+///
+/// The rules of the validator created by [`new`] should be `Send + Sync`, so it is
+/// `Send + Sync` when the message type is. It can be declared once as a global and used
+/// from many worker threads, the rules which are not `Send + Sync`, e.g. capturing `Rc`,
+/// can be registered on the validator created by [`local`], see [`kind`].
+///
+/// [`new`]: InnerValidator::new
+/// [`local`]: InnerValidator::local
+/// [`kind`]: crate::rule::kind
+///
+#[cfg_attr(feature = "full", doc = "```rust")]
+#[cfg_attr(not(feature = "full"), doc = "```ignore")]
+/// # use std::sync::OnceLock;
+/// # use serde::Serialize;
+/// # use valitron::{available::{Message, Required}, Validator};
+/// #[derive(Serialize)]
+/// struct Input {
+///     name: String,
+/// }
+///
+/// fn validator() -> &'static Validator<'static, Message> {
+///     static VALIDATOR: OnceLock<Validator<'static, Message>> = OnceLock::new();
+///     VALIDATOR.get_or_init(|| Validator::new().rule("name", Required))
+/// }
+///
+/// let handles: Vec<_> = ["foo", ""]
+///     .into_iter()
+///     .map(|name| {
+///         std::thread::spawn(move || {
+///             validator().validate(Input { name: name.into() }).is_ok()
+///         })
+///     })
+///     .collect();
+///
+/// let res: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+/// assert_eq!(res, [true, false]);
+/// ```
+pub type Validator<'v, M, K = Shared> = InnerValidator<M, HashMap<MessageKey<'v>, M>, K>;
 
 /// # A validator for build messages
 /// build message with rule name, field name and value
@@ -101,82 +160,408 @@ pub type Validator<'v, M> = InnerValidator<M, HashMap<MessageKey<'v>, M>>;
 /// *message need to implement [`IntoMessage`]*
 ///
 /// [`IntoMessage`]: message::IntoMessage
-pub type ValidatorRefine<M> = InnerValidator<M, ()>;
+pub type ValidatorRefine<M, K = Shared> = InnerValidator<M, (), K>;
 
 #[doc(hidden)]
-pub struct InnerValidator<M, List> {
-    rules: HashMap<FieldNames, RuleList<ValueMap, M>>,
+pub struct InnerValidator<M, List, K = Shared> {
+    rules: IndexMap<FieldNames, RuleList<ValueMap, M, K>>,
+    async_rules: IndexMap<FieldNames, AsyncRuleList<M>>,
+    presence: HashMap<FieldNames, Presence>,
+    /// the message of the absent field without `required` rule in [`Presence::Required`] mode
+    required: Option<RequiredFn<M>>,
+    /// the fields mounted by [`nested`] from a bail validator, the validation of their
+    /// sub-fields stops at the first error
+    ///
+    /// [`nested`]: Validator::nested
+    bail_under: Vec<FieldNames>,
     message: List,
     is_bail: bool,
+    is_concurrent: bool,
+}
+
+type RequiredFn<M> = Arc<dyn Fn() -> M + Send + Sync>;
+
+/// How to handle a registered field which is absent in the data, e.g. an optional JSON key
+/// or a field skipped by `#[serde(skip_serializing_if = "...")]`, see [`presence`].
+///
+/// [`presence`]: InnerValidator::presence
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Presence {
+    /// the field must be present, otherwise `validate` returns
+    /// [`ValidateError::FieldNotFound`], it is the default mode
+    #[default]
+    Strict,
+
+    /// the rules of the field are run only if it is present, like `sometimes` of Laravel
+    Sometimes,
+
+    /// the absent field fails with its `required` rule, e.g. [`Required`], other rules are skipped,
+    /// and it fails with [`RequiredMessage`] when the field has no `required` rule
+    ///
+    /// For the field with `[*]`, e.g. `tags[*]`, the absent array or map fails at its own path, e.g. `tags`.
+    ///
+    /// [`Required`]: crate::rule::available::Required
+    Required,
+}
+
+/// The message of the absent field in [`Presence::Required`] mode, it is used when the field
+/// has no `required` rule, e.g. the field only has `Length`.
+pub trait RequiredMessage {
+    fn required_message() -> Self;
+}
+
+impl RequiredMessage for String {
+    fn required_message() -> Self {
+        "this field is required".to_owned()
+    }
 }
 
+impl RequiredMessage for &'static str {
+    fn required_message() -> Self {
+        "this field is required"
+    }
+}
+
+/// the name of `required` rule, it is run on the absent field in [`Presence::Required`] mode
+const REQUIRED: &str = "required";
+
 impl<M> Validator<'_, M> {
     pub fn new() -> Self {
         Self::default()
     }
+}
 
+impl<M> Validator<'_, M, Local> {
+    /// create the validator accepting the rules which are not `Send + Sync`, e.g. capturing
+    /// `Rc` or `RefCell`, it can only be used in the current thread, see [`kind`]
+    ///
+    /// [`kind`]: crate::rule::kind
+    pub fn local() -> Self {
+        Self::default()
+    }
+}
+
+impl<M, K> Validator<'_, M, K>
+where
+    M: Clone,
+{
     /// run validate without modifiable
-    pub fn validate<T>(self, data: T) -> Result<(), ValidatorError<M>>
+    ///
+    /// the validator is not consumed, so it can be built once and used on any number of inputs.
+    /// The custom messages registered by [`message`](Self::message) are returned on every run,
+    /// instead of being moved out of the validator, so the message type should be `Clone`.
+    ///
+    /// Besides the messages of rules as [`ValidateError::Invalid`], the serialization failures
+    /// and the fields not existing in the data are returned as the other variants. The async
+    /// rules can not be run here, so the validator having them returns
+    /// [`ValidateError::AsyncRules`], use [`validate_async`](Self::validate_async) instead.
+    pub fn validate<T>(&self, data: T) -> Result<(), ValidateError<M>>
+    where
+        T: Serialize,
+    {
+        self.validate_context(data, Context::default())
+    }
+
+    /// run validate without modifiable, and pass an external context to rules
+    ///
+    /// The context is available in closures, e.g. `custom(|v: &mut String, ctx: &AppCtx| ...)`,
+    /// and in [`Rule::call_with_context`] or [`CoreRule::call_with_context`].
+    ///
+    /// The context is borrowed only while validating, so the validator can be built once, and a
+    /// request-scoped handle is passed by reference, e.g. `validate_with(data, &conn)`. Its type
+    /// is matched by [`TypeId`](std::any::TypeId), so it should not borrow other values, wrap them
+    /// into `Arc` instead. When the closures requiring a context are validated without it, or
+    /// with other type, [`ValidateError::MissingContext`] is returned before running the rules.
+    ///
+    /// [`Rule::call_with_context`]: crate::Rule::call_with_context
+    /// [`CoreRule::call_with_context`]: crate::rule::CoreRule::call_with_context
+    pub fn validate_with<T, C>(&self, data: T, context: &C) -> Result<(), ValidateError<M>>
     where
         T: Serialize,
+        C: 'static,
+    {
+        self.validate_context(data, Context::new(context))
+    }
+
+    /// run validate with modifiable
+    ///
+    /// The modified data which can not be deserialized is returned as
+    /// [`ValidateError::Deserialize`].
+    pub fn validate_mut<'de, T>(&self, data: T) -> Result<T, ValidateError<M>>
+    where
+        T: Serialize + serde::de::Deserialize<'de>,
     {
-        let value = data.serialize(Serializer).unwrap();
+        self.validate_mut_context(data, Context::default())
+    }
+
+    /// run validate with modifiable, and pass an external context to rules
+    pub fn validate_mut_with<'de, T, C>(&self, data: T, context: &C) -> Result<T, ValidateError<M>>
+    where
+        T: Serialize + serde::de::Deserialize<'de>,
+        C: 'static,
+    {
+        self.validate_mut_context(data, Context::new(context))
+    }
+
+    /// run validate on JSON value, without defining struct
+    ///
+    /// The value is converted without serialization, and the JSON object is converted to
+    /// struct, so its keys are matched by field names, e.g. `user.name`. The integer is
+    /// converted to `u64` or `i64` (when it is negative), and the float to `f64`, `null` is
+    /// same as `None`.
+    #[cfg_attr(feature = "full", doc = "```rust")]
+    #[cfg_attr(not(feature = "full"), doc = "```ignore")]
+    /// # use serde_json::json;
+    /// # use valitron::{available::{Length, Range, Required}, RuleExt, Validator};
+    /// let validator = Validator::new()
+    ///     .rule("user.name", Required.and(Length(2..)))
+    ///     .rule("user.age", Range::new(18_u64..));
+    ///
+    /// let err = validator
+    ///     .validate_json(json!({ "user": { "name": "foo", "age": 16 } }))
+    ///     .unwrap_err()
+    ///     .into_invalid()
+    ///     .unwrap();
+    ///
+    /// assert!(err.get("user.name").is_none());
+    /// assert!(err.get("user.age").is_some());
+    /// ```
+    #[cfg(feature = "serde_json")]
+    pub fn validate_json(&self, json: serde_json::Value) -> Result<(), ValidateError<M>> {
+        self.validate_value(json.into(), Context::default())
+            .map(|_| ())
+    }
+
+    /// run validate on JSON value with modifiable, see [`validate_json`](Self::validate_json)
+    #[cfg(feature = "serde_json")]
+    pub fn validate_json_mut(
+        &self,
+        json: serde_json::Value,
+    ) -> Result<serde_json::Value, ValidateError<M>> {
+        self.validate_value(json.into(), Context::default())
+            .map(serde_json::Value::from)
+    }
+
+    /// run validate on JSON text, and without panic
+    ///
+    /// The malformed text is returned as [`ValidateError::Syntax`] with the location, and every
+    /// field of [`ValidatorError`] carries the location of its value, see
+    /// [`ValidatorError::location`]. The JSON object is converted to struct, so its keys are
+    /// matched by field names, e.g. `user.name`.
+    #[cfg_attr(feature = "full", doc = "```rust")]
+    #[cfg_attr(not(feature = "full"), doc = "```ignore")]
+    /// # use valitron::{available::Range, register::ValidateError, Validator};
+    /// let validator = Validator::new().rule("items[*].price", Range::new(1_u64..));
+    ///
+    /// let text = r#"{
+    ///     "items": [{ "price": 10 }, { "price": 0 }]
+    /// }"#;
+    /// let Err(ValidateError::Invalid(err)) = validator.validate_json_str(text) else {
+    ///     panic!("price should be invalid");
+    /// };
+    /// let location = err.location("items[1].price").unwrap();
+    /// assert_eq!((location.line(), location.column()), (2, 43));
+    ///
+    /// let Err(ValidateError::Syntax(err)) = validator.validate_json_str(r#"{"items": [}"#) else {
+    ///     panic!("text should be malformed");
+    /// };
+    /// assert_eq!(err.to_string(), "expected value at line 1 column 12");
+    /// ```
+    pub fn validate_json_str(&self, text: &str) -> Result<(), ValidateError<M>> {
+        self.check_sync()?;
+        self.check_context(Context::default())?;
+
+        let (value, spanned) = json_text::parse(text).map_err(ValidateError::Syntax)?;
 
-        debug_assert!(self.exist_field(&value));
+        if let Some(field) = self.missing_field(&value) {
+            return Err(ValidateError::FieldNotFound {
+                field: field.as_str().to_owned(),
+            });
+        }
 
         let mut value_map = ValueMap::new(value);
+        let mut err = self.inner_validate(&mut value_map, Context::default())?;
+        err.location = err
+            .keys()
+            .map(|names| (names.clone(), spanned.locate(names)))
+            .collect();
+
+        err.ok()?;
+        Ok(())
+    }
 
-        self.inner_validate(&mut value_map).ok()
+    fn validate_context<T>(&self, data: T, context: Context<'_>) -> Result<(), ValidateError<M>>
+    where
+        T: Serialize,
+    {
+        let value = data
+            .serialize(Serializer)
+            .map_err(ValidateError::Serialize)?;
+
+        self.validate_value(value, context).map(|_| ())
     }
 
-    /// run validate with modifiable
-    pub fn validate_mut<'de, T>(self, data: T) -> Result<T, ValidatorError<M>>
+    /// validate the value, and return it which may be modified by rules
+    fn validate_value(
+        &self,
+        value: Value,
+        context: Context<'_>,
+    ) -> Result<Value, ValidateError<M>> {
+        self.check_sync()?;
+
+        let mut value_map = self.value_map(value, context)?;
+
+        self.inner_validate(&mut value_map, context)?.ok()?;
+        Ok(value_map.value())
+    }
+
+    fn validate_mut_context<'de, T>(
+        &self,
+        data: T,
+        context: Context<'_>,
+    ) -> Result<T, ValidateError<M>>
     where
         T: Serialize + serde::de::Deserialize<'de>,
     {
-        let value = data.serialize(Serializer).unwrap();
+        let value = data
+            .serialize(Serializer)
+            .map_err(ValidateError::Serialize)?;
 
-        debug_assert!(self.exist_field(&value));
+        let value = self.validate_value(value, context)?;
+        T::deserialize(value).map_err(ValidateError::Deserialize)
+    }
 
-        let mut value_map = ValueMap::new(value);
+    fn inner_validate(
+        &self,
+        value_map: &mut ValueMap,
+        context: Context<'_>,
+    ) -> Result<ValidatorError<M>, ValidateError<M>> {
+        self.iter_validate(
+            value_map,
+            |names, rules, value_map, message| {
+                let list = rules.call(value_map, context)?;
+                Ok(replace_message(names, list, message))
+            },
+            |names, _, message| {
+                let list = self.required.iter().map(|f| (REQUIRED, f())).collect();
+                replace_message(names, list, message)
+            },
+        )
+    }
+}
+
+fn replace_message<M: Clone>(
+    names: &FieldNames,
+    list: Vec<(&'static str, M)>,
+    message: &HashMap<MessageKey<'_>, M>,
+) -> Vec<M> {
+    list.into_iter()
+        .map(|(rule, msg)| {
+            message
+                .get(&MessageKey::new(names.clone(), rule))
+                .cloned()
+                .unwrap_or(msg)
+        })
+        .collect()
+}
+
+impl<M, K> Validator<'_, M, K>
+where
+    M: Clone + Send + 'static,
+{
+    /// run validate with async rules, without modifiable
+    ///
+    /// The sync rules are run first, then the async rules of the fields which passed
+    /// the sync rules are awaited, one by one or concurrently, see [`concurrent`].
+    ///
+    /// [`concurrent`]: Self::concurrent
+    pub async fn validate_async<T>(&self, data: T) -> Result<(), ValidateError<M>>
+    where
+        T: Serialize,
+    {
+        let mut value_map = self.try_value_map(&data)?;
 
-        self.inner_validate(&mut value_map)
-            .ok()
-            .map(|_| T::deserialize(value_map.value()).unwrap())
+        self.inner_validate_async(&mut value_map).await?.ok()?;
+        Ok(())
     }
 
-    fn inner_validate(self, value_map: &mut ValueMap) -> ValidatorError<M> {
-        fn handle_msg<M>(
-            rules: RuleList<ValueMap, M>,
-            value_map: &mut ValueMap,
-            message: &mut HashMap<MessageKey<'_>, M>,
-        ) -> Vec<M> {
-            rules
-                .call(value_map)
-                .into_iter()
-                .map(|(rule, msg)| {
-                    message
-                        .remove(&MessageKey::new(value_map.as_index().clone(), rule))
-                        .unwrap_or(msg)
-                })
-                .collect()
-        }
-        self.iter_validate(value_map, handle_msg)
+    /// run validate with async rules and modifiable
+    pub async fn validate_mut_async<'de, T>(&self, data: T) -> Result<T, ValidateError<M>>
+    where
+        T: Serialize + serde::de::Deserialize<'de>,
+    {
+        let mut value_map = self.try_value_map(&data)?;
+
+        self.inner_validate_async(&mut value_map).await?.ok()?;
+
+        T::deserialize(value_map.value()).map_err(ValidateError::Deserialize)
     }
 
-    fn exit_message(&self, MessageKey { fields, rule }: &MessageKey) -> bool {
-        debug_assert!(
-            self.rule_get(fields).is_some(),
-            "the field \"{}\" not found in validator",
-            fields.as_str()
-        );
+    async fn inner_validate_async(
+        &self,
+        value_map: &mut ValueMap,
+    ) -> Result<ValidatorError<M>, ValidateError<M>> {
+        let resp_message = self.inner_validate(value_map, Context::default())?;
+
+        let resp_message = self
+            .iter_validate_async(value_map, resp_message, |names, list, _, _, message| {
+                replace_message(names, list, message)
+            })
+            .await;
+        Ok(resp_message)
+    }
+}
 
-        debug_assert!(
-            self.rule_get(fields).unwrap().contains(rule),
-            "rule \"{rule}\" is not found in rules"
-        );
+impl<'v, M, K> Validator<'v, M, K> {
+    /// # Iterate the registered fields and their rules
+    ///
+    /// The fields and rules are yielded in registration order, the async rules follow
+    /// the sync rules of the same field.
+    #[cfg_attr(feature = "full", doc = "```rust")]
+    #[cfg_attr(not(feature = "full"), doc = "```ignore")]
+    /// # use valitron::{available::{Length, Required}, RuleExt, Validator, Value};
+    /// let validator = Validator::new()
+    ///     .rule("name", Required.and(Length(6..=12)))
+    ///     .message([("name.required", "name is required")]);
+    ///
+    /// let (field, rules) = validator.rules().next().unwrap();
+    /// assert_eq!(field.as_str(), "name");
+    /// assert_eq!(rules[0].name(), "required");
+    /// assert_eq!(rules[0].message().unwrap().to_string(), "name is required");
+    /// assert_eq!(rules[1].name(), "length");
+    /// assert_eq!(rules[1].param("min"), Some(&Value::Uint64(6)));
+    /// assert_eq!(rules[1].param("max"), Some(&Value::Uint64(12)));
+    /// ```
+    pub fn rules(&self) -> impl Iterator<Item = (&FieldNames, Vec<RuleInfo<'_, M>>)> {
+        let async_only = self
+            .async_rules
+            .keys()
+            .filter(|names| !self.rules.contains_key(*names));
+
+        self.rules
+            .keys()
+            .chain(async_only)
+            .map(|names| (names, self.rule_info(names)))
+    }
 
-        true
+    fn rule_info(&self, names: &FieldNames) -> Vec<RuleInfo<'_, M>> {
+        let message = |rule| self.message.get(&MessageKey::new(names.clone(), rule));
+
+        let sync_rules = self.rule_get(names).into_iter().flat_map(|list| {
+            list.iter().map(move |rule| {
+                RuleInfo::new(rule.name(), rule.params(), list.is_bail())
+                    .set_message(message(rule.name()))
+            })
+        });
+        let async_rules = self.async_rules.get(names).into_iter().flat_map(|list| {
+            list.names().map(move |name| {
+                RuleInfo::new(name, Params::new(), list.is_bail())
+                    .set_async()
+                    .set_message(message(name))
+            })
+        });
+
+        sync_rules.chain(async_rules).collect()
     }
 }
 
@@ -184,49 +569,112 @@ impl<M> ValidatorRefine<M> {
     pub fn new() -> Self {
         Self::default()
     }
+}
+
+impl<M> ValidatorRefine<M, Local> {
+    /// create the validator accepting the rules which are not `Send + Sync`,
+    /// same as [`Validator::local`]
+    pub fn local() -> Self {
+        Self::default()
+    }
+}
 
+impl<M, K> ValidatorRefine<M, K> {
     /// run validate without modifiable
-    pub fn validate<T, M2>(self, data: T) -> Result<(), ValidatorError<M2>>
+    pub fn validate<T, M2>(&self, data: T) -> Result<(), ValidateError<M2>>
     where
         T: Serialize,
         M2: IntoMessage,
     {
-        let value = data.serialize(Serializer).unwrap();
+        self.check_sync()?;
 
-        debug_assert!(self.exist_field(&value));
+        let mut value_map = self.try_value_map(&data)?;
 
-        let mut value_map = ValueMap::new(value);
-
-        self.inner_validate(&mut value_map).ok()
+        self.inner_validate(&mut value_map)?.ok()?;
+        Ok(())
     }
 
     /// run validate with modifiable
-    pub fn validate_mut<'de, T, M2>(self, data: T) -> Result<T, ValidatorError<M2>>
+    pub fn validate_mut<'de, T, M2>(&self, data: T) -> Result<T, ValidateError<M2>>
     where
         T: Serialize + serde::de::Deserialize<'de>,
         M2: IntoMessage,
     {
-        let value = data.serialize(Serializer).unwrap();
+        self.check_sync()?;
 
-        debug_assert!(self.exist_field(&value));
+        let mut value_map = self.try_value_map(&data)?;
 
-        let mut value_map = ValueMap::new(value);
+        self.inner_validate(&mut value_map)?.ok()?;
 
-        self.inner_validate(&mut value_map)
-            .ok()
-            .map(|_| T::deserialize(value_map.value()).unwrap())
+        T::deserialize(value_map.value()).map_err(ValidateError::Deserialize)
     }
 
     /// inner creating message by field name and current value.
-    fn inner_validate<M2>(self, value_map: &mut ValueMap) -> ValidatorError<M2>
+    fn inner_validate<M2>(
+        &self,
+        value_map: &mut ValueMap,
+    ) -> Result<ValidatorError<M2>, ValidateError<M2>>
+    where
+        M2: IntoMessage,
+    {
+        self.iter_validate(
+            value_map,
+            |_, rules, data, _| rules.call_gen_message(data),
+            |_, data, _| vec![M2::into_message(REQUIRED, data.as_index(), &Value::Unit)],
+        )
+    }
+}
+
+impl<M, K> ValidatorRefine<M, K>
+where
+    M: Send + 'static,
+{
+    /// run validate with async rules, without modifiable, see [`Validator::validate_async`]
+    pub async fn validate_async<T, M2>(&self, data: T) -> Result<(), ValidateError<M2>>
+    where
+        T: Serialize,
+        M2: IntoMessage,
+    {
+        let mut value_map = self.try_value_map(&data)?;
+
+        self.inner_validate_async(&mut value_map).await?.ok()?;
+        Ok(())
+    }
+
+    /// run validate with async rules and modifiable
+    pub async fn validate_mut_async<'de, T, M2>(&self, data: T) -> Result<T, ValidateError<M2>>
+    where
+        T: Serialize + serde::de::Deserialize<'de>,
+        M2: IntoMessage,
+    {
+        let mut value_map = self.try_value_map(&data)?;
+
+        self.inner_validate_async(&mut value_map).await?.ok()?;
+
+        T::deserialize(value_map.value()).map_err(ValidateError::Deserialize)
+    }
+
+    async fn inner_validate_async<M2>(
+        &self,
+        value_map: &mut ValueMap,
+    ) -> Result<ValidatorError<M2>, ValidateError<M2>>
     where
         M2: IntoMessage,
     {
-        self.iter_validate(value_map, |rules, data, _| rules.call_gen_message(data))
+        let resp_message = self.inner_validate(value_map)?;
+
+        let resp_message = self
+            .iter_validate_async(value_map, resp_message, |_, list, field, value, _| {
+                list.into_iter()
+                    .map(|(rule, _)| M2::into_message(rule, field, value))
+                    .collect()
+            })
+            .await;
+        Ok(resp_message)
     }
 }
 
-impl<'v, M> Validator<'v, M> {
+impl<'v, M, K> Validator<'v, M, K> {
     /// Custom validate error message
     ///
     /// Every rule has a default message, the method should be replace it with your need.
@@ -238,25 +686,225 @@ impl<'v, M> Validator<'v, M> {
     ///
     /// # Panic
     ///
-    /// When field or rule is not existing ,this will panic
+    /// - Message key format error will be panic
+    /// - When field or rule is not existing, this will panic in debug build,
+    ///   use [`try_message`] to check it in release build
+    ///
+    /// [`try_message`]: Self::try_message
     pub fn message<const N: usize, Msg>(mut self, list: [(&'v str, Msg); N]) -> Self
     where
         Msg: Into<M>,
     {
-        self.message.extend(list.map(|(key_str, v)| {
+        for (key_str, v) in list {
             let msg_key = crate::panic_on_err!(field_name::parse_message(key_str));
 
-            debug_assert!(self.exit_message(&msg_key));
+            // the unknown field or rule is only checked in debug build
+            if cfg!(debug_assertions) {
+                crate::panic_on_err!(self.check_message(&msg_key));
+            }
+
+            self.message.insert(msg_key, v.into());
+        }
+        self
+    }
+
+    /// Custom validate error message, without panic
+    ///
+    /// same as [`message`], but return [`BuildError`] when the key format is invalid,
+    /// or the field or rule is not existing.
+    ///
+    /// [`message`]: Self::message
+    pub fn try_message<const N: usize, Msg>(
+        mut self,
+        list: [(&'v str, Msg); N],
+    ) -> Result<Self, BuildError>
+    where
+        Msg: Into<M>,
+    {
+        for (key_str, v) in list {
+            let msg_key =
+                field_name::parse_message(key_str).map_err(|_| BuildError::InvalidMessageKey {
+                    key: key_str.to_owned(),
+                })?;
+
+            self.check_message(&msg_key)?;
+
+            self.message.insert(msg_key, v.into());
+        }
+        Ok(self)
+    }
+
+    /// # Mount other validator under the field
+    ///
+    /// The rules and custom messages of `validator` are rebased under `field`,
+    /// so one validator of sub-structure can be reused in many places, and its
+    /// errors are reported with full path, e.g. `home.street`.
+    ///
+    /// The other fields of relational rules, e.g. [`Confirm`], [`Gt`] and [`required_if`],
+    /// are resolved under `field` too, and when `validator` is [`bail`], the validation
+    /// of its fields stops at the first error.
+    #[cfg_attr(feature = "full", doc = "```rust")]
+    #[cfg_attr(not(feature = "full"), doc = "```ignore")]
+    /// # use serde::Serialize;
+    /// # use valitron::{Validator, available::Required};
+    /// #[derive(Serialize)]
+    /// struct Address {
+    ///     street: String,
+    /// }
+    ///
+    /// #[derive(Serialize)]
+    /// struct User {
+    ///     home: Address,
+    ///     work: Address,
+    /// }
+    ///
+    /// let address = Validator::new()
+    ///     .rule("street", Required)
+    ///     .message([("street.required", "street is required")]);
+    ///
+    /// let validator = Validator::new()
+    ///     .nested("home", address.clone())
+    ///     .nested("work", address);
+    ///
+    /// let user = User {
+    ///     home: Address { street: "foo".into() },
+    ///     work: Address { street: String::new() },
+    /// };
+    /// let err = validator
+    ///     .validate(user)
+    ///     .unwrap_err()
+    ///     .into_invalid()
+    ///     .unwrap();
+    ///
+    /// assert!(err.get("home.street").is_none());
+    /// assert_eq!(err.get("work.street").unwrap()[0].to_string(), "street is required");
+    /// ```
+    ///
+    /// [`Confirm`]: crate::available::Confirm
+    /// [`Gt`]: crate::available::Gt
+    /// [`required_if`]: crate::available::required_if
+    /// [`bail`]: Self::bail
+    ///
+    /// # Panic
+    ///
+    /// Field format error will be panic
+    pub fn nested<F>(mut self, field: F, mut validator: Validator<'v, M, K>) -> Self
+    where
+        F: IntoFieldName,
+        M: 'static,
+        K: 'static,
+    {
+        let prefix = crate::panic_on_err!(into_names(field));
+
+        validator.rules = validator
+            .rules
+            .into_iter()
+            .map(|(names, rules)| (names, rules.rebase(&prefix)))
+            .collect();
+        self.extend_under(Some(&prefix), validator);
+        self
+    }
 
-            (msg_key, v.into())
-        }));
+    /// # Merge other validator
+    ///
+    /// The rules and custom messages of `other` are combined into `self`, e.g. sharing
+    /// the validator of base fields in many request types. The same-name rules in one field
+    /// are replaced by the rules of `other`, same as registering them twice by [`rule`],
+    /// and so as the custom messages.
+    ///
+    /// The `bail` and `concurrent` settings of `self` are kept.
+    #[cfg_attr(feature = "full", doc = "```rust")]
+    #[cfg_attr(not(feature = "full"), doc = "```ignore")]
+    /// # use serde::Serialize;
+    /// # use valitron::{Validator, available::{Range, Required}};
+    /// #[derive(Serialize)]
+    /// struct CreatePost {
+    ///     id: u32,
+    ///     title: String,
+    /// }
+    ///
+    /// let base = Validator::new().rule("id", Range::new(1..));
+    ///
+    /// let validator = Validator::new()
+    ///     .rule("title", Required)
+    ///     .merge(base);
+    ///
+    /// let err = validator
+    ///     .validate(CreatePost { id: 0, title: String::new() })
+    ///     .unwrap_err()
+    ///     .into_invalid()
+    ///     .unwrap();
+    /// assert_eq!(err.len(), 2);
+    /// ```
+    ///
+    /// [`rule`]: Self::rule
+    pub fn merge(mut self, other: Validator<'v, M, K>) -> Self {
+        self.extend(other);
         self
     }
 
+    /// Same as [`merge`], but extend `self` in place
+    ///
+    /// [`merge`]: Self::merge
+    pub fn extend(&mut self, other: Validator<'v, M, K>) {
+        self.extend_under(None, other);
+    }
+
+    /// combine other validator, and rebase its fields under the prefix
+    fn extend_under(&mut self, prefix: Option<&FieldNames>, other: Validator<'v, M, K>) {
+        let rebase = |names: FieldNames| match prefix {
+            Some(prefix) => prefix.join(&names),
+            None => names,
+        };
+        let Validator {
+            rules,
+            async_rules,
+            presence,
+            required,
+            bail_under,
+            message,
+            is_bail,
+            ..
+        } = other;
+
+        for (names, mut rules) in rules {
+            if prefix.is_some() && is_bail {
+                rules.set_bail();
+            }
+            self.merge_rules(rebase(names), rules);
+        }
+        if self.required.is_none() {
+            self.required = required;
+        }
+        self.bail_under.extend(bail_under.into_iter().map(rebase));
+        if let (Some(prefix), true) = (prefix, is_bail) {
+            self.bail_under.push(prefix.clone());
+        }
+        self.presence.extend(
+            presence
+                .into_iter()
+                .map(|(names, presence)| (rebase(names), presence)),
+        );
+        for (names, mut rules) in async_rules {
+            if prefix.is_some() && is_bail {
+                rules.set_bail();
+            }
+            self.merge_async_rules(rebase(names), rules);
+        }
+        self.message.extend(
+            message
+                .into_iter()
+                .map(|(MessageKey { fields, rule }, msg)| {
+                    (MessageKey::new(rebase(fields), rule), msg)
+                }),
+        );
+    }
+
     /// # convert `Validator<M1>` to `Validator<M2>`
     ///
     /// Using build-in rules and returning custom validator message type is able:
-    /// ```rust
+    #[cfg_attr(feature = "full", doc = "```rust")]
+    #[cfg_attr(not(feature = "full"), doc = "```ignore")]
     /// # use valitron::{Validator, available::{Message, MessageKind, Required}};
     /// let validator = Validator::new()
     ///     .rule("introduce", Required)
@@ -279,10 +927,11 @@ impl<'v, M> Validator<'v, M> {
     /// }
     /// ```
     #[must_use]
-    pub fn map<M2>(self, f: fn(message: M) -> M2) -> Validator<'v, M2>
+    pub fn map<M2>(self, f: fn(message: M) -> M2) -> Validator<'v, M2, K>
     where
-        M: 'static,
-        M2: 'static,
+        M: Send + 'static,
+        M2: Send + 'static,
+        K: 'static,
     {
         Validator {
             rules: self
@@ -290,43 +939,64 @@ impl<'v, M> Validator<'v, M> {
                 .into_iter()
                 .map(|(field, list)| (field, list.map(f)))
                 .collect(),
+            async_rules: self
+                .async_rules
+                .into_iter()
+                .map(|(field, list)| (field, list.map(f)))
+                .collect(),
+            presence: self.presence,
+            required: self
+                .required
+                .map(|required| -> RequiredFn<M2> { Arc::new(move || f(required())) }),
+            bail_under: self.bail_under,
             message: self
                 .message
                 .into_iter()
                 .map(|(key, msg)| (key, f(msg)))
                 .collect(),
             is_bail: self.is_bail,
+            is_concurrent: self.is_concurrent,
         }
     }
 }
 
-impl<M, List> Default for InnerValidator<M, List>
+impl<M, List, K> Default for InnerValidator<M, List, K>
 where
     List: Default,
 {
     fn default() -> Self {
         Self {
-            rules: HashMap::new(),
+            rules: IndexMap::new(),
+            async_rules: IndexMap::new(),
+            presence: HashMap::new(),
+            required: None,
+            bail_under: Vec::new(),
             message: List::default(),
             is_bail: false,
+            is_concurrent: false,
         }
     }
 }
 
-impl<M, List> Clone for InnerValidator<M, List>
+impl<M, List, K> Clone for InnerValidator<M, List, K>
 where
     List: Clone,
 {
     fn clone(&self) -> Self {
         Self {
             rules: self.rules.clone(),
+            async_rules: self.async_rules.clone(),
+            presence: self.presence.clone(),
+            required: self.required.clone(),
+            bail_under: self.bail_under.clone(),
             message: self.message.clone(),
             is_bail: self.is_bail,
+            is_concurrent: self.is_concurrent,
         }
     }
 }
 
-impl<M, List> InnerValidator<M, List> {
+impl<M, List, K> InnerValidator<M, List, K> {
     /// # Register rules
     ///
     /// **Feild support multiple formats:**
@@ -334,6 +1004,11 @@ impl<M, List> InnerValidator<M, List> {
     /// - `0`,`1`.. used to matching tuple item or tuple struct field
     /// - `[0]`,`[1]` used to matching array item
     /// - `[foo]` used to matching struct variant, e.g. `enum Foo{ Color { r: u8, g: u8, b: u8 } }`
+    /// - `["foo"]` used to matching map value by key, e.g. `settings["max.conn"]`, the key
+    ///   support escape `\"` and `\\`, `[foo]` can also be used when key is a identifier,
+    ///   and it also matches struct field renamed by serde, e.g. `["first-name"]`
+    /// - `[*]` used to matching every array item or map value, e.g. `items[*].name`, the error
+    ///   is reported with concrete index, e.g. `items[3].name` or `settings["foo"]`
     ///
     /// fields support nest:
     /// - `field1.0`
@@ -348,18 +1023,23 @@ impl<M, List> InnerValidator<M, List> {
     ///                          | <array_index>
     ///                          | <ident>
     ///                          | <struct_variant_index>
+    ///                          | <map_key>
     ///                          | <fields> '.' <tuple_index>
     ///                          | <fields> '.' <ident>
     ///                          | <fields> <array_index>
     ///                          | <fields> <struct_variant_index>
+    ///                          | <fields> <map_key>
     ///                          | <fields> <option_flag> [<sub_fields>]
     /// sub_fields             ::= '.' <tuple_index>
     ///                          | '.' <ident>
     ///                          | <array_index>
     ///                          | <struct_variant_index>
+    ///                          | <map_key>
     /// tuple_index            ::= <u8>
     /// array_index            ::= '[' <usize> ']'
+    ///                          | '[' '*' ']'
     /// struct_variant_index   ::= '[' <ident> ']'
+    /// map_key                ::= '[' '"' <string> '"' ']'
     /// option_flag            ::= ?
     /// ```
     ///
@@ -370,6 +1050,8 @@ impl<M, List> InnerValidator<M, List> {
     /// - `RuleFoo.custom(handler)` type and closure
     /// - `custom(handler).and(RuleFoo)` closure and type
     /// - `RuleFoo.and(RuleBar).bail()` when first validate error, immediately return error with one message.
+    /// - `Each(RuleFoo)` apply to every element of array, tuple or map, it can not be combined
+    ///   with other rules by `and`, register it separately, see [`Each`]
     ///
     /// *Available Rules*
     /// - [`Required`]
@@ -382,84 +1064,456 @@ impl<M, List> InnerValidator<M, List> {
     /// # Panic
     ///
     /// - Field format error will be panic
-    /// - Invalid rule name will be panic
+    /// - Invalid rule name will be panic in debug build, use [`try_rule`] to check it in
+    ///   release build
     ///
+    /// [`try_rule`]: Self::try_rule
     /// [`Required`]: crate::available::required
     /// [`StartWith`]: crate::available::start_with
     /// [`Confirm`]: crate::available::confirm
     /// [`Trim`]: crate::available::trim
     /// [`Range`]: crate::available::range
-    pub fn rule<F, R>(mut self, field: F, rule: R) -> Self
+    /// [`Each`]: crate::rule::Each
+    pub fn rule<F, R>(self, field: F, rule: R) -> Self
+    where
+        F: IntoFieldName,
+        R: IntoRuleList<ValueMap, M, K>,
+    {
+        let names = crate::panic_on_err!(into_names(field));
+        let rules = rule.into_list();
+
+        debug_assert!(rules.invalid_name().is_none(), "invalid rule name");
+
+        self.insert_rules(names, rules)
+    }
+
+    /// # Register rules, without panic
+    ///
+    /// same as [`rule`], but return [`BuildError`] when the field format or rule name is invalid,
+    /// it is useful for validators built from user-supplied config.
+    ///
+    /// [`rule`]: Self::rule
+    pub fn try_rule<F, R>(self, field: F, rule: R) -> Result<Self, BuildError>
+    where
+        F: IntoFieldName,
+        R: IntoRuleList<ValueMap, M, K>,
+    {
+        let names = into_names(field)?;
+        let rules = rule.into_list();
+
+        if let Some(rule) = rules.invalid_name() {
+            return Err(BuildError::InvalidRuleName {
+                field: names.as_str().to_owned(),
+                rule: rule.to_owned(),
+            });
+        }
+
+        Ok(self.insert_rules(names, rules))
+    }
+
+    fn insert_rules(mut self, names: FieldNames, rules: RuleList<ValueMap, M, K>) -> Self {
+        for (each, rules) in rules.split_each() {
+            let names = if each > 0 {
+                FieldNames::new(format!("{}{}", names.as_str(), "[*]".repeat(each)))
+            } else {
+                names.clone()
+            };
+            self.merge_rules(names, rules);
+        }
+        self
+    }
+
+    /// # Register async rules
+    ///
+    /// The field format is same as [`rule`], and the registered async rules are run by
+    /// [`validate_async`] or [`validate_mut_async`].
+    ///
+    /// [`rule`]: Self::rule
+    /// [`validate_async`]: Validator::validate_async
+    /// [`validate_mut_async`]: Validator::validate_mut_async
+    pub fn rule_async<F, R>(mut self, field: F, rule: R) -> Self
+    where
+        F: IntoFieldName,
+        R: IntoAsyncRuleList<M>,
+    {
+        let names = crate::panic_on_err!(into_names(field));
+
+        self.merge_async_rules(names, rule.into_list());
+        self
+    }
+
+    /// # Set how to handle the field when it is absent in the data
+    ///
+    /// By default every registered field must be present, see [`Presence`].
+    #[cfg_attr(feature = "full", doc = "```rust")]
+    #[cfg_attr(not(feature = "full"), doc = "```ignore")]
+    /// # use serde::Serialize;
+    /// # use valitron::{available::{Length, Required}, register::Presence, RuleExt, Validator};
+    /// #[derive(Serialize)]
+    /// struct Patch {
+    ///     #[serde(skip_serializing_if = "Option::is_none")]
+    ///     name: Option<String>,
+    ///     #[serde(skip_serializing_if = "Option::is_none")]
+    ///     email: Option<String>,
+    /// }
+    ///
+    /// let validator = Validator::new()
+    ///     .rule("name?", Length(2..))
+    ///     .rule("email", Required)
+    ///     .sometimes("name?")
+    ///     .presence("email", Presence::Required);
+    ///
+    /// let err = validator
+    ///     .validate(Patch { name: None, email: None })
+    ///     .unwrap_err()
+    ///     .into_invalid()
+    ///     .unwrap();
+    /// assert_eq!(err.len(), 1);
+    /// assert!(err.get("email").is_some());
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// When the field format is invalid
+    pub fn presence<F>(mut self, field: F, presence: Presence) -> Self
     where
         F: IntoFieldName,
-        R: IntoRuleList<ValueMap, M>,
+        M: RequiredMessage + 'static,
     {
-        let names = crate::panic_on_err!(field.into_field());
-        let mut rules = rule.into_list();
+        if presence == Presence::Required && self.required.is_none() {
+            self.required = Some(Arc::new(M::required_message));
+        }
+        self.insert_presence(field, presence)
+    }
+
+    /// run the rules of the field only if it is present, it is a shortcut of
+    /// `presence(field, Presence::Sometimes)`
+    pub fn sometimes<F>(self, field: F) -> Self
+    where
+        F: IntoFieldName,
+    {
+        self.insert_presence(field, Presence::Sometimes)
+    }
+
+    fn insert_presence<F>(mut self, field: F, presence: Presence) -> Self
+    where
+        F: IntoFieldName,
+    {
+        let names = crate::panic_on_err!(into_names(field));
+
+        self.presence.insert(names, presence);
+        self
+    }
+
+    fn presence_get(&self, names: &FieldNames) -> Presence {
+        self.presence.get(names).copied().unwrap_or_default()
+    }
 
-        debug_assert!(rules.valid_name(), "invalid rule name");
+    /// await the async rules of all fields concurrently, by default they are awaited one by one.
+    pub fn concurrent(mut self) -> Self {
+        self.is_concurrent = true;
+        self
+    }
+
+    fn merge_async_rules(&mut self, names: FieldNames, mut rules: AsyncRuleList<M>) {
+        if self.is_bail {
+            rules.set_bail();
+        }
+
+        self.async_rules
+            .entry(names)
+            .and_modify(|list| list.merge(&mut rules))
+            .or_insert(rules);
+    }
+
+    fn merge_rules(&mut self, names: FieldNames, mut rules: RuleList<ValueMap, M, K>) {
+        if self.is_bail {
+            rules.set_bail();
+        }
 
         self.rules
             .entry(names)
             .and_modify(|list| list.merge(&mut rules))
             .or_insert(rules);
-        self
     }
 
     /// when first validate error is encountered, right away return Err(message).
     pub fn bail(mut self) -> Self {
         self.is_bail = true;
+        self.rules.values_mut().for_each(RuleList::set_bail);
+        self.async_rules
+            .values_mut()
+            .for_each(AsyncRuleList::set_bail);
         self
     }
 
-    fn exist_field(&self, value: &Value) -> bool {
-        for (field, _) in self.rules.iter() {
-            if value.get_with_names(field).is_none() {
-                panic!("field `{}` is not found", field.as_str());
+    /// check the field and rule of message key are registered
+    pub(crate) fn check_message(
+        &self,
+        MessageKey { fields, rule }: &MessageKey,
+    ) -> Result<(), BuildError> {
+        let sync_rules = self.rule_get(fields);
+        let async_rules = self.async_rules.get(fields);
+
+        if sync_rules.is_none() && async_rules.is_none() {
+            return Err(BuildError::FieldNotFound {
+                field: fields.as_str().to_owned(),
+            });
+        }
+
+        if sync_rules.is_some_and(|list| list.contains(rule))
+            || async_rules.is_some_and(|list| list.contains(rule))
+        {
+            Ok(())
+        } else {
+            Err(BuildError::RuleNotFound {
+                field: fields.as_str().to_owned(),
+                rule: rule.to_string(),
+            })
+        }
+    }
+
+    /// serialize the data, and check the registered fields are existing,
+    /// it is validated without context
+    pub(crate) fn try_value_map<T, E>(&self, data: &T) -> Result<ValueMap, ValidateError<E>>
+    where
+        T: Serialize,
+    {
+        let value = data
+            .serialize(Serializer)
+            .map_err(ValidateError::Serialize)?;
+
+        self.value_map(value, Context::default())
+    }
+
+    /// check the registered fields are existing in the value, and the context required
+    /// by rules is passed
+    fn value_map<E>(
+        &self,
+        value: Value,
+        context: Context<'_>,
+    ) -> Result<ValueMap, ValidateError<E>> {
+        self.check_context(context)?;
+
+        if let Some(field) = self.missing_field(&value) {
+            return Err(ValidateError::FieldNotFound {
+                field: field.as_str().to_owned(),
+            });
+        }
+
+        Ok(ValueMap::new(value))
+    }
+
+    /// find the first registered field which is not existing in the value,
+    /// the fields not in [`Presence::Strict`] mode, or passing through a `?` of `None`,
+    /// are ignored.
+    fn missing_field(&self, value: &Value) -> Option<FieldNames> {
+        for field in self.rules.keys().chain(self.async_rules.keys()) {
+            if self.presence_get(field) != Presence::Strict {
+                continue;
+            }
+            let list = match value.expand_names(field) {
+                Some(list) => list,
+                None if value.is_none_at(field) => continue,
+                None => return Some(field.clone()),
+            };
+            for names in list {
+                if value.get_with_names(&names).is_none() && !value.is_none_at(&names) {
+                    return Some(names);
+                }
             }
         }
 
-        true
+        None
+    }
+
+    /// the rules requiring a context are rejected instead of panicking, when the context
+    /// is not passed, or its type is not matched
+    fn check_context<E>(&self, context: Context<'_>) -> Result<(), ValidateError<E>> {
+        for (names, rules) in self.rules.iter() {
+            if let Some(expected) = rules.missing_context(context) {
+                return Err(ValidateError::MissingContext {
+                    field: names.as_str().to_owned(),
+                    expected,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// the sync validation can not run the async rules, they are rejected instead of skipped
+    fn check_sync<E>(&self) -> Result<(), ValidateError<E>> {
+        match self.async_rules.keys().next() {
+            Some(field) => Err(ValidateError::AsyncRules {
+                field: field.as_str().to_owned(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// the mounted field of bail validator, which `names` is under
+    fn bailed_prefix(&self, names: &FieldNames) -> Option<&FieldNames> {
+        self.bail_under
+            .iter()
+            .filter(|prefix| names.starts_with(prefix))
+            .min_by_key(|prefix| prefix.as_str().len())
     }
 
     #[inline(always)]
-    fn rule_get(&self, names: &FieldNames) -> Option<&RuleList<ValueMap, M>> {
+    fn rule_get(&self, names: &FieldNames) -> Option<&RuleList<ValueMap, M, K>> {
         self.rules.get(names)
     }
 
-    fn iter_validate<F, T>(self, value_map: &mut ValueMap, handle_msg: F) -> ValidatorError<T>
+    /// `handle_required` is called for the absent field without `required` rule
+    /// in [`Presence::Required`] mode
+    fn iter_validate<F, R, T>(
+        &self,
+        value_map: &mut ValueMap,
+        handle_msg: F,
+        handle_required: R,
+    ) -> Result<ValidatorError<T>, ValidateError<T>>
     where
-        F: Fn(RuleList<ValueMap, M>, &mut ValueMap, &mut List) -> Vec<T>,
+        F: Fn(
+            &FieldNames,
+            &RuleList<ValueMap, M, K>,
+            &mut ValueMap,
+            &List,
+        ) -> Result<Vec<T>, &'static str>,
+        R: Fn(&FieldNames, &ValueMap, &List) -> Vec<T>,
     {
         let mut resp_message = ValidatorError::with_capacity(self.rules.len());
+        // the mounted fields of bail validators which already have an error
+        let mut bailed: Vec<&FieldNames> = Vec::new();
 
-        let Self {
-            rules,
-            mut message,
-            is_bail,
-        } = self;
-
-        for (mut names, mut rules) in rules.into_iter() {
-            if is_bail {
-                rules.set_bail();
+        for (names, rules) in self.rules.iter() {
+            if bailed.iter().any(|prefix| names.starts_with(prefix)) {
+                continue;
             }
+            let presence = self.presence_get(names);
+
+            for field in value_map.expand(names) {
+                // the field passing through a `?` of `None` is always skipped, e.g. `home?.number`
+                let is_absent = value_map.get(&field).is_none();
+                if is_absent && (presence == Presence::Sometimes || value_map.is_none_at(&field)) {
+                    continue;
+                }
+
+                value_map.index(field);
+
+                let field_msg = if is_absent && presence == Presence::Required {
+                    let rules = rules.only(REQUIRED);
+                    if rules.is_empty() {
+                        Ok(handle_required(names, value_map, &self.message))
+                    } else {
+                        handle_msg(names, &rules, value_map, &self.message)
+                    }
+                } else {
+                    handle_msg(names, rules, value_map, &self.message)
+                };
+                let field_msg = field_msg.map_err(|expected| ValidateError::MissingContext {
+                    field: names.as_str().to_owned(),
+                    expected,
+                })?;
+
+                let field = value_map.take_index();
+                let is_err = !field_msg.is_empty();
+
+                resp_message.push(field, field_msg);
+
+                if self.is_bail && !resp_message.is_empty() {
+                    resp_message.shrink_to(1);
+                    return Ok(resp_message);
+                }
+                if let Some(prefix) = self.bailed_prefix(names).filter(|_| is_err) {
+                    bailed.push(prefix);
+                    break;
+                }
+            }
+        }
+
+        resp_message.shrink_to_fit();
+
+        Ok(resp_message)
+    }
 
-            value_map.index(names);
+    /// await the async rules of the fields which have no message in `resp_message`,
+    /// `handle_msg` converts the failed rules of the field into messages
+    async fn iter_validate_async<F, T>(
+        &self,
+        value_map: &ValueMap,
+        mut resp_message: ValidatorError<T>,
+        handle_msg: F,
+    ) -> ValidatorError<T>
+    where
+        M: Send + 'static,
+        F: Fn(&FieldNames, Vec<(&'static str, M)>, &FieldNames, &Value, &List) -> Vec<T>,
+    {
+        if self.is_bail && !resp_message.is_empty() {
+            return resp_message;
+        }
 
-            let field_msg = handle_msg(rules, value_map, &mut message);
+        let mut fields = Vec::new();
+        for (names, rules) in self.async_rules.iter() {
+            // the mounted field of bail validator already has an error
+            if let Some(prefix) = self.bailed_prefix(names) {
+                if resp_message
+                    .message
+                    .keys()
+                    .any(|key| key.starts_with(prefix))
+                {
+                    continue;
+                }
+            }
+            for field in value_map.expand(names) {
+                if resp_message.message.contains_key(&field) {
+                    continue;
+                }
+                if let Some(value) = value_map.get(&field).cloned() {
+                    fields.push((names, rules, field, value));
+                }
+            }
+        }
 
-            names = value_map.take_index();
+        let results = if self.is_concurrent {
+            let futures = fields
+                .iter()
+                .map(|(_, rules, _, value)| -> BoxFuture<'_, _> { Box::pin(rules.call(value)) });
+            join_all(futures.collect()).await
+        } else {
+            let mut results = Vec::with_capacity(fields.len());
+            let mut bailed = Vec::new();
+            for (names, rules, _, value) in fields.iter() {
+                let prefix = self.bailed_prefix(names);
+                if prefix.is_some_and(|prefix| bailed.contains(&prefix)) {
+                    results.push(Vec::new());
+                    continue;
+                }
+                let msg = rules.call(value).await;
+                let is_err = !msg.is_empty();
+                results.push(msg);
+
+                if self.is_bail && is_err {
+                    break;
+                }
+                if let Some(prefix) = prefix.filter(|_| is_err) {
+                    bailed.push(prefix);
+                }
+            }
+            results
+        };
 
-            resp_message.push(names, field_msg);
+        for ((names, _, field, value), list) in fields.iter().zip(results) {
+            resp_message.push(
+                field.clone(),
+                handle_msg(names, list, field, value, &self.message),
+            );
 
-            if is_bail && !resp_message.is_empty() {
+            if self.is_bail && !resp_message.is_empty() {
                 resp_message.shrink_to(1);
-                return resp_message;
+                break;
             }
         }
 
-        resp_message.shrink_to_fit();
-
         resp_message
     }
 
@@ -469,13 +1523,38 @@ impl<M, List> InnerValidator<M, List> {
     }
 }
 
-impl<M> From<Validator<'_, M>> for ValidatorRefine<M> {
-    fn from(value: Validator<'_, M>) -> Self {
-        let Validator { rules, is_bail, .. } = value;
+/// convert field to names, and check the syntax of names
+fn into_names<F: IntoFieldName>(field: F) -> Result<FieldNames, BuildError> {
+    let names = field.into_field();
+
+    let field = names.as_str().to_owned();
+    names.into_parsed().map_err(|err| BuildError::InvalidField {
+        field,
+        reason: err.to_string(),
+    })
+}
+
+impl<M, K> From<Validator<'_, M, K>> for ValidatorRefine<M, K> {
+    fn from(value: Validator<'_, M, K>) -> Self {
+        let Validator {
+            rules,
+            async_rules,
+            presence,
+            required,
+            bail_under,
+            is_bail,
+            is_concurrent,
+            ..
+        } = value;
         Self {
             rules,
+            async_rules,
+            presence,
+            required,
+            bail_under,
             message: (),
             is_bail,
+            is_concurrent,
         }
     }
 }
@@ -491,16 +1570,51 @@ pub trait Validatable<V, E> {
         Self: Deserialize<'de>;
 }
 
-impl<T, M> Validatable<Validator<'_, M>, ValidatorError<M>> for T
+impl<T, M, K> Validatable<Validator<'_, M, K>, ValidateError<M>> for T
+where
+    T: Serialize,
+    M: Clone + 'static,
+{
+    fn validate(&self, validator: Validator<M, K>) -> Result<(), ValidateError<M>> {
+        validator.validate(self)
+    }
+
+    fn validate_mut<'de>(self, validator: Validator<M, K>) -> Result<Self, ValidateError<M>>
+    where
+        Self: Deserialize<'de>,
+    {
+        validator.validate_mut(self)
+    }
+}
+
+impl<'a, T, M, K> Validatable<&'a Validator<'_, M, K>, ValidateError<M>> for T
+where
+    T: Serialize,
+    M: Clone + 'static,
+{
+    fn validate(&self, validator: &'a Validator<M, K>) -> Result<(), ValidateError<M>> {
+        validator.validate(self)
+    }
+
+    fn validate_mut<'de>(self, validator: &'a Validator<M, K>) -> Result<Self, ValidateError<M>>
+    where
+        Self: Deserialize<'de>,
+    {
+        validator.validate_mut(self)
+    }
+}
+
+impl<T, M, M2, K> Validatable<ValidatorRefine<M, K>, ValidateError<M2>> for T
 where
     T: Serialize,
     M: 'static,
+    M2: IntoMessage,
 {
-    fn validate(&self, validator: Validator<M>) -> Result<(), ValidatorError<M>> {
+    fn validate(&self, validator: ValidatorRefine<M, K>) -> Result<(), ValidateError<M2>> {
         validator.validate(self)
     }
 
-    fn validate_mut<'de>(self, validator: Validator<M>) -> Result<Self, ValidatorError<M>>
+    fn validate_mut<'de>(self, validator: ValidatorRefine<M, K>) -> Result<Self, ValidateError<M2>>
     where
         Self: Deserialize<'de>,
     {
@@ -508,17 +1622,20 @@ where
     }
 }
 
-impl<T, M, M2> Validatable<ValidatorRefine<M>, ValidatorError<M2>> for T
+impl<'a, T, M, M2, K> Validatable<&'a ValidatorRefine<M, K>, ValidateError<M2>> for T
 where
     T: Serialize,
     M: 'static,
     M2: IntoMessage,
 {
-    fn validate(&self, validator: ValidatorRefine<M>) -> Result<(), ValidatorError<M2>> {
+    fn validate(&self, validator: &'a ValidatorRefine<M, K>) -> Result<(), ValidateError<M2>> {
         validator.validate(self)
     }
 
-    fn validate_mut<'de>(self, validator: ValidatorRefine<M>) -> Result<Self, ValidatorError<M2>>
+    fn validate_mut<'de>(
+        self,
+        validator: &'a ValidatorRefine<M, K>,
+    ) -> Result<Self, ValidateError<M2>>
     where
         Self: Deserialize<'de>,
     {
@@ -530,13 +1647,15 @@ where
 pub type ValidatorError<M> = InnerValidatorError<FieldNames, M>;
 
 pub struct InnerValidatorError<F, M> {
-    message: HashMap<F, Vec<M>>,
+    message: IndexMap<F, Vec<M>>,
+    location: HashMap<F, Location>,
 }
 
 impl<F: Clone, M: Clone> Clone for InnerValidatorError<F, M> {
     fn clone(&self) -> Self {
         Self {
             message: self.message.clone(),
+            location: self.location.clone(),
         }
     }
 }
@@ -553,9 +1672,12 @@ where
     F: std::fmt::Debug,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ValidatorError")
-            .field("message", &self.message)
-            .finish()
+        let mut debug = f.debug_struct("ValidatorError");
+        debug.field("message", &self.message);
+        if !self.location.is_empty() {
+            debug.field("location", &self.location);
+        }
+        debug.finish()
     }
 }
 
@@ -564,7 +1686,7 @@ impl<M> Index<&str> for ValidatorError<M> {
 
     fn index(&self, index: &str) -> &Self::Output {
         self.message
-            .get(&(index.into()))
+            .get(&FieldNames::from(index))
             .expect("this field is not found")
     }
 }
@@ -608,12 +1730,14 @@ where
 {
     pub fn new() -> Self {
         Self {
-            message: HashMap::new(),
+            message: IndexMap::new(),
+            location: HashMap::new(),
         }
     }
     fn with_capacity(capacity: usize) -> Self {
         Self {
-            message: HashMap::with_capacity(capacity),
+            message: IndexMap::with_capacity(capacity),
+            location: HashMap::new(),
         }
     }
     fn shrink_to_fit(&mut self) {
@@ -632,6 +1756,7 @@ where
                 .into_iter()
                 .map(|(name, msg)| (name, msg.into_iter().map(f).collect()))
                 .collect(),
+            location: self.location,
         }
     }
 
@@ -652,6 +1777,22 @@ where
         self.message.values().map(|msg| msg.len()).sum()
     }
 
+    /// Combine the errors of other validator, the messages of the same field are appended
+    pub fn merge(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+
+    /// Same as [`merge`], but extend `self` in place
+    ///
+    /// [`merge`]: Self::merge
+    pub fn extend(&mut self, other: Self) {
+        for (field, mut msg) in other.message {
+            self.message.entry(field).or_default().append(&mut msg);
+        }
+        self.location.extend(other.location);
+    }
+
     fn ok(self) -> Result<(), Self> {
         if self.message.is_empty() {
             Ok(())
@@ -667,21 +1808,81 @@ impl<M> ValidatorError<M> {
         }
     }
 
+    /// Rebase all fields under the path, e.g. `street` to `home.street`,
+    /// it is used by combining the errors of sub-structure validator.
+    ///
+    #[cfg_attr(feature = "full", doc = "```rust")]
+    #[cfg_attr(not(feature = "full"), doc = "```ignore")]
+    /// # use serde::Serialize;
+    /// # use valitron::{Validator, available::Required};
+    /// #[derive(Serialize)]
+    /// struct Address {
+    ///     street: String,
+    /// }
+    ///
+    /// let validator = Validator::new().rule("street", Required);
+    /// let home = Address { street: String::new() };
+    /// let work = Address { street: String::new() };
+    ///
+    /// let home_err = validator
+    ///     .validate(home)
+    ///     .unwrap_err()
+    ///     .into_invalid()
+    ///     .unwrap()
+    ///     .prefix("home");
+    /// let work_err = validator
+    ///     .validate(work)
+    ///     .unwrap_err()
+    ///     .into_invalid()
+    ///     .unwrap()
+    ///     .prefix("work");
+    /// let err = home_err.merge(work_err);
+    ///
+    /// assert!(err.get("home.street").is_some());
+    /// assert!(err.get("work.street").is_some());
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// When the path format is invalid
+    pub fn prefix<P: IntoFieldName>(self, path: P) -> Self {
+        let prefix = crate::panic_on_err!(into_names(path));
+
+        Self {
+            message: self
+                .message
+                .into_iter()
+                .map(|(field, msg)| (prefix.join(&field), msg))
+                .collect(),
+            location: self
+                .location
+                .into_iter()
+                .map(|(field, location)| (prefix.join(&field), location))
+                .collect(),
+        }
+    }
+
     pub fn get<K: IntoFieldName>(&self, key: K) -> Option<&Vec<M>> {
-        let k = key.into_field().ok()?;
+        let k = key.into_field();
         self.message.get(&k)
     }
 
+    /// the location of the field value in JSON text, it is only available for the errors
+    /// returned by [`validate_json_str`], and the absent field is located at its parent
+    ///
+    /// [`validate_json_str`]: Validator::validate_json_str
+    pub fn location<K: IntoFieldName>(&self, key: K) -> Option<&Location> {
+        let k = key.into_field();
+        self.location.get(&k)
+    }
+
     pub fn get_key_value<K: IntoFieldName>(&self, key: K) -> Option<(&FieldNames, &Vec<M>)> {
-        let k = key.into_field().ok()?;
+        let k = key.into_field();
         self.message.get_key_value(&k)
     }
 
     pub fn contains_key<K: IntoFieldName>(&self, key: K) -> bool {
-        match key.into_field() {
-            Ok(k) => self.message.contains_key(&k),
-            Err(_) => false,
-        }
+        self.message.contains_key(&key.into_field())
     }
 
     pub fn keys(&self) -> Keys<'_, FieldNames, Vec<M>> {
@@ -0,0 +1,422 @@
+//! generate JSON Schema (draft 2020-12) from a validator, see [`Validator::json_schema`]
+//!
+//! The registered paths are converted to nested `properties`, `items` and `prefixItems`,
+//! the `[*]` matches the items of array and the values of map, so it is converted to
+//! `items` when the other paths show the field is array, `additionalProperties` when they
+//! show it is object, or both when it is unknown. The build-in rules are converted to keywords:
+//!
+//! | rule | keyword |
+//! | --- | --- |
+//! | `Required` | `required` of the parent object |
+//! | `Length` | `minLength` / `maxLength`, `minItems` / `maxItems` or `minProperties` / `maxProperties` |
+//! | `Range` | `minimum` / `maximum` / `exclusiveMinimum` / `exclusiveMaximum`, only numbers |
+//! | `Regex` | `pattern` |
+//! | `Email` | `format: email` |
+//! | `Nullable`, `?` in path | `null` in `type` |
+//! | `Not(rule)` | `not` |
+//!
+//! Other rules, e.g. closures, async rules or conditional rules, are emitted as extensions
+//! named `x-{rule name}` with their parameters, e.g. `"x-custom": {}`,
+//! `"x-confirm": {"other": "password"}`.
+//!
+//! [`Validator::json_schema`]: super::Validator::json_schema
+
+use std::collections::BTreeMap;
+
+use indexmap::IndexMap;
+use serde::{ser::SerializeMap, Serialize};
+
+use crate::{rule::Params, Value};
+
+use super::{parse_names, FieldName, Presence, RuleInfo, Validator, REQUIRED};
+
+/// the `$schema` of generated document
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// JSON Schema document, it is generated by [`Validator::json_schema`], and
+/// can be serialized by any serde format, e.g. `serde_json`.
+///
+/// [`Validator::json_schema`]: super::Validator::json_schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonSchema(Json);
+
+impl Serialize for JsonSchema {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<M> Validator<'_, M> {
+    /// # Generate JSON Schema from the registered fields and rules
+    ///
+    /// see [`schema`](crate::register::schema) for how rules are converted.
+    #[cfg_attr(feature = "full", doc = "```rust")]
+    #[cfg_attr(not(feature = "full"), doc = "```ignore")]
+    /// # use valitron::{available::{Email, Length, Required}, RuleExt, Validator};
+    /// let validator = Validator::new()
+    ///     .rule("name", Required.and(Length(6..=12)))
+    ///     .rule("email", Email);
+    ///
+    /// let schema = serde_json::to_value(validator.json_schema()).unwrap();
+    ///
+    /// assert_eq!(schema["required"], serde_json::json!(["name"]));
+    /// assert_eq!(schema["properties"]["name"]["minLength"], 6);
+    /// assert_eq!(schema["properties"]["name"]["maxLength"], 12);
+    /// assert_eq!(schema["properties"]["email"]["format"], "email");
+    /// ```
+    pub fn json_schema(&self) -> JsonSchema {
+        let mut root = Node::default();
+
+        for (names, rules) in self.rules() {
+            // the registered names are checked by `rule`, so it is always parsed
+            let Ok(path) = parse_names(names.as_str()) else {
+                continue;
+            };
+            let node = root.get_mut(path);
+            node.is_sometimes |= self.presence_get(names) == Presence::Sometimes;
+            node.rules.extend(rules.iter().map(RuleSchema::new));
+        }
+
+        let mut schema = vec![("$schema".to_owned(), Json::String(DRAFT.to_owned()))];
+        if let Json::Object(keywords) = root.to_json() {
+            schema.extend(keywords);
+        }
+        JsonSchema(Json::Object(schema))
+    }
+}
+
+/// the rule of field, only the name and parameters are used
+#[derive(Debug)]
+struct RuleSchema {
+    name: &'static str,
+    params: Params,
+}
+
+impl RuleSchema {
+    fn new<M>(info: &RuleInfo<'_, M>) -> Self {
+        Self {
+            name: info.name(),
+            params: info.params().clone(),
+        }
+    }
+
+    fn param(&self, name: &str) -> Option<&Value> {
+        self.params
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    fn is_flagged(&self, name: &str) -> bool {
+        self.param(name) == Some(&Value::Boolean(true))
+    }
+
+    fn is_required(&self) -> bool {
+        self.name == REQUIRED && !self.is_flagged("not") && !self.is_flagged("when")
+    }
+
+    /// the type implied by the rule, the rule always fails on other types
+    fn implied_type(&self) -> Option<&'static str> {
+        if self.is_flagged("not") {
+            return None;
+        }
+        match self.name {
+            "email" | "regex" | "start_with" | "end_with" | "contains" => Some("string"),
+            "range" => match self.param("min").or(self.param("max")) {
+                Some(Value::Float32(_) | Value::Float64(_)) => Some("number"),
+                Some(Value::Char(_)) => Some("string"),
+                Some(_) => Some("integer"),
+                None => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// the keywords of the rule, `None` when the rule is not supported by JSON Schema
+    fn keywords(&self, types: &[&str]) -> Option<Vec<(String, Json)>> {
+        if self.is_flagged("when") {
+            return None;
+        }
+
+        let keywords = match self.name {
+            "length" => {
+                let min = self.bound("min", "exclusive_min", 1);
+                let max = self.bound("max", "exclusive_max", -1);
+                let mut prefixes: Vec<_> = [
+                    ("string", "Length"),
+                    ("array", "Items"),
+                    ("object", "Properties"),
+                ]
+                .into_iter()
+                .filter(|(ty, _)| types.contains(ty))
+                .map(|(_, prefix)| prefix)
+                .collect();
+                if prefixes.is_empty() {
+                    prefixes = vec!["Length", "Items"];
+                }
+                prefixes
+                    .iter()
+                    .flat_map(|prefix| {
+                        let min = min.map(|min| (format!("min{prefix}"), Json::Uint(min)));
+                        let max = max.map(|max| (format!("max{prefix}"), Json::Uint(max)));
+                        min.into_iter().chain(max)
+                    })
+                    .collect()
+            }
+            // the bounds of other types, e.g. `char`, are not supported
+            "range" if self.params.iter().any(|(_, value)| !is_number(value)) => return None,
+            "range" => [
+                ("min", "minimum"),
+                ("max", "maximum"),
+                ("exclusive_min", "exclusiveMinimum"),
+                ("exclusive_max", "exclusiveMaximum"),
+            ]
+            .into_iter()
+            .filter_map(|(param, keyword)| {
+                self.param(param)
+                    .map(|value| (keyword.to_owned(), Json::from(value)))
+            })
+            .collect(),
+            "regex" => vec![("pattern".to_owned(), Json::from(self.param("pattern")?))],
+            "email" => vec![("format".to_owned(), Json::String("email".to_owned()))],
+            _ => return None,
+        };
+        Some(keywords)
+    }
+
+    /// the inclusive bound of `Length`, the exclusive bound is shifted by `offset`
+    fn bound(&self, inclusive: &str, exclusive: &str, offset: i64) -> Option<u64> {
+        let to_int = |value: &Value| match value {
+            Value::Uint64(n) => i64::try_from(*n).ok(),
+            _ => None,
+        };
+        self.param(inclusive)
+            .and_then(to_int)
+            .or_else(|| self.param(exclusive).and_then(to_int).map(|n| n + offset))
+            .map(|n| n.max(0) as u64)
+    }
+
+    /// the extension of the unsupported rule, e.g. `"x-custom": {}`
+    fn extension(&self) -> (String, Json) {
+        let params = self
+            .params
+            .iter()
+            .map(|(key, value)| (key.to_string(), Json::from(value)))
+            .collect();
+        (format!("x-{}", self.name), Json::Object(params))
+    }
+}
+
+fn is_number(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::Uint8(_)
+            | Value::Int8(_)
+            | Value::Uint16(_)
+            | Value::Int16(_)
+            | Value::Uint32(_)
+            | Value::Int32(_)
+            | Value::Uint64(_)
+            | Value::Int64(_)
+            | Value::Float32(_)
+            | Value::Float64(_)
+    )
+}
+
+/// the schema of one path
+#[derive(Debug, Default)]
+struct Node {
+    rules: Vec<RuleSchema>,
+    is_nullable: bool,
+    is_sometimes: bool,
+    properties: IndexMap<String, Node>,
+    prefix_items: BTreeMap<usize, Node>,
+    items: Option<Box<Node>>,
+}
+
+impl Node {
+    fn get_mut(&mut self, path: Vec<FieldName>) -> &mut Node {
+        path.into_iter().fold(self, |node, name| match name {
+            FieldName::Literal(key) | FieldName::StructVariant(key) | FieldName::Key(key) => {
+                node.properties.entry(key).or_default()
+            }
+            FieldName::Array(index) => node.prefix_items.entry(index).or_default(),
+            FieldName::Tuple(index) => node.prefix_items.entry(index as usize).or_default(),
+            FieldName::Wildcard => node.items.get_or_insert_with(Box::default),
+            FieldName::Option => {
+                node.is_nullable = true;
+                node
+            }
+        })
+    }
+
+    fn types(&self) -> Vec<&'static str> {
+        let mut types = Vec::new();
+        if !self.properties.is_empty() {
+            types.push("object");
+        }
+        if !self.prefix_items.is_empty() {
+            types.push("array");
+        }
+        for ty in self.rules.iter().filter_map(RuleSchema::implied_type) {
+            if !types.contains(&ty) {
+                types.push(ty);
+            }
+        }
+        types
+    }
+
+    fn to_json(&self) -> Json {
+        let mut keywords = Vec::new();
+
+        let types = self.types();
+        let is_nullable = self.is_nullable || self.rules.iter().any(|rule| rule.name == "nullable");
+        match (types.as_slice(), is_nullable) {
+            ([], _) => (),
+            ([ty], false) => keywords.push(("type".to_owned(), Json::String(ty.to_string()))),
+            (_, _) => {
+                let list = types
+                    .iter()
+                    .copied()
+                    .chain(is_nullable.then_some("null"))
+                    .map(|ty| Json::String(ty.to_owned()))
+                    .collect();
+                keywords.push(("type".to_owned(), Json::Array(list)));
+            }
+        }
+
+        // the `[*]` matches array or map, both are possible when the type is unknown
+        let mut possible_types = types.clone();
+        if self.items.is_some() && !types.contains(&"array") && !types.contains(&"object") {
+            possible_types.extend(["array", "object"]);
+        }
+
+        let mut extensions = Vec::new();
+        let mut not = Vec::new();
+        for rule in &self.rules {
+            if rule.is_required() || rule.name == "nullable" {
+                continue;
+            }
+            match rule.keywords(&possible_types) {
+                Some(list) if rule.is_flagged("not") => not.push(Json::Object(list)),
+                Some(list) => keywords.extend(list),
+                None => extensions.push(rule.extension()),
+            }
+        }
+        match not.len() {
+            0 => (),
+            1 => keywords.push(("not".to_owned(), not.remove(0))),
+            _ => {
+                let all_of = not
+                    .into_iter()
+                    .map(|schema| Json::Object(vec![("not".to_owned(), schema)]))
+                    .collect();
+                keywords.push(("allOf".to_owned(), Json::Array(all_of)));
+            }
+        }
+
+        if !self.properties.is_empty() {
+            let properties = self
+                .properties
+                .iter()
+                .map(|(key, node)| (key.clone(), node.to_json()))
+                .collect();
+            keywords.push(("properties".to_owned(), Json::Object(properties)));
+
+            let required: Vec<_> = self
+                .properties
+                .iter()
+                .filter(|(_, node)| {
+                    !node.is_sometimes && node.rules.iter().any(RuleSchema::is_required)
+                })
+                .map(|(key, _)| Json::String(key.clone()))
+                .collect();
+            if !required.is_empty() {
+                keywords.push(("required".to_owned(), Json::Array(required)));
+            }
+        }
+
+        if let Some(last) = self.prefix_items.keys().last() {
+            let prefix_items = (0..=*last)
+                .map(|index| match self.prefix_items.get(&index) {
+                    Some(node) => node.to_json(),
+                    None => Json::Object(Vec::new()),
+                })
+                .collect();
+            keywords.push(("prefixItems".to_owned(), Json::Array(prefix_items)));
+        }
+        if let Some(items) = &self.items {
+            let items = items.to_json();
+            if possible_types.contains(&"array") {
+                keywords.push(("items".to_owned(), items.clone()));
+            }
+            if possible_types.contains(&"object") {
+                keywords.push(("additionalProperties".to_owned(), items));
+            }
+        }
+
+        keywords.extend(extensions);
+        Json::Object(keywords)
+    }
+}
+
+/// JSON value of the generated document, the keywords are kept in insertion order
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Uint(u64),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl From<&Value> for Json {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Uint8(n) => Json::Uint(*n as u64),
+            Value::Uint16(n) => Json::Uint(*n as u64),
+            Value::Uint32(n) => Json::Uint(*n as u64),
+            Value::Uint64(n) => Json::Uint(*n),
+            Value::Int8(n) => Json::Int(*n as i64),
+            Value::Int16(n) => Json::Int(*n as i64),
+            Value::Int32(n) => Json::Int(*n as i64),
+            Value::Int64(n) => Json::Int(*n),
+            Value::Float32(n) => Json::Float(n.get() as f64),
+            Value::Float64(n) => Json::Float(n.get()),
+            Value::String(s) => Json::String(s.clone()),
+            Value::Char(c) => Json::String(c.to_string()),
+            Value::Boolean(b) => Json::Bool(*b),
+            _ => Json::Null,
+        }
+    }
+}
+
+impl Serialize for Json {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Json::Null => serializer.serialize_unit(),
+            Json::Bool(b) => serializer.serialize_bool(*b),
+            Json::Uint(n) => serializer.serialize_u64(*n),
+            Json::Int(n) => serializer.serialize_i64(*n),
+            Json::Float(n) => serializer.serialize_f64(*n),
+            Json::String(s) => serializer.serialize_str(s),
+            Json::Array(list) => list.serialize(serializer),
+            Json::Object(map) => {
+                let mut state = serializer.serialize_map(Some(map.len()))?;
+                for (key, value) in map {
+                    state.serialize_entry(key, value)?;
+                }
+                state.end()
+            }
+        }
+    }
+}
@@ -0,0 +1,582 @@
+//! Load validator from rule configuration, e.g. JSON, TOML or YAML files,
+//! so the rules can be changed without recompiling.
+//!
+//! [`ValidatorSpec`] maps field paths to rule lists and messages, it is deserialized by
+//! any serde format, and can be serialized back. The validator is converted back into
+//! configuration by [`Validator::to_spec`].
+//!
+//! ```
+//! # use serde::Serialize;
+//! # use valitron::{
+//! #     available::{parser::RuleParser, Message},
+//! #     custom,
+//! #     register::spec::ValidatorSpec,
+//! #     Validator,
+//! # };
+//! #[derive(Serialize)]
+//! struct Input {
+//!     name: String,
+//!     age: u8,
+//! }
+//!
+//! let spec: ValidatorSpec = serde_json::from_str(r#"{
+//!     "fields": {
+//!         "name": {
+//!             "rules": [
+//!                 { "rule": "required" },
+//!                 { "rule": "length", "min": 3, "max": 8 }
+//!             ],
+//!             "messages": { "length": "name should be 3 to 8 chars" }
+//!         },
+//!         "age": {
+//!             "rules": [{ "rule": "custom", "name": "adult" }]
+//!         }
+//!     }
+//! }"#).unwrap();
+//!
+//! fn adult(age: &mut u8) -> Result<(), Message> {
+//!     if *age >= 18 {
+//!         Ok(())
+//!     } else {
+//!         Err("age should be adult".into())
+//!     }
+//! }
+//! let registry = RuleParser::new().register("adult", custom(adult));
+//!
+//! let validator = Validator::from_spec_with(&spec, &registry).unwrap();
+//!
+//! let err = validator
+//!     .validate(Input { name: "Jo".into(), age: 16 })
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
+//! assert_eq!(err.get("name").unwrap()[0].to_string(), "name should be 3 to 8 chars");
+//! assert_eq!(err.get("age").unwrap()[0].to_string(), "age should be adult");
+//! ```
+
+use std::fmt::Display;
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    available::{
+        conditional::present,
+        parser::{as_num, Num, NumRange, RuleParser},
+        Confirm, Contains, Egt, Elt, Email, EndsWith, Gt, Length, Lt, Message, MessageKind, Not,
+        Nullable, Regex, Required, StartWith, Trim,
+    },
+    rule::{IntoRuleList, Params, RuleList},
+    Rule, Value, ValueMap,
+};
+
+use super::{into_names, parse_names, BuildError, FieldNames, MessageKey, RuleInfo, Validator};
+
+/// the rule configuration of validator
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorSpec {
+    /// stop at the first failure of all fields, see [`bail`](super::InnerValidator::bail)
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub bail: bool,
+
+    /// the field path and its rules, the field path format is same as [`rule`]
+    ///
+    /// [`rule`]: super::InnerValidator::rule
+    #[serde(default)]
+    pub fields: IndexMap<String, FieldSpec>,
+}
+
+/// the rule configuration of one field
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FieldSpec {
+    #[serde(default)]
+    pub rules: Vec<RuleSpec>,
+
+    /// stop at the first failure of the field
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub bail: bool,
+
+    /// the custom messages, keyed by rule name, e.g. `"required": "name is required"`
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub messages: IndexMap<String, String>,
+}
+
+/// the rule configuration, it is tagged by `rule`, e.g. `{ "rule": "length", "min": 3 }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum RuleSpec {
+    /// [`Required`]
+    Required,
+
+    /// [`Nullable`]
+    Nullable,
+
+    /// [`Email`]
+    Email,
+
+    /// [`Trim`]
+    Trim,
+
+    /// [`Length`], the bounds are inclusive, and at least one is needed
+    Length {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<usize>,
+    },
+
+    /// the number is in the range, any number type is supported, the bounds are inclusive,
+    /// and at least one is needed
+    Range {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<Number>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<Number>,
+    },
+
+    /// [`Confirm`]
+    Confirm { other: String },
+
+    /// [`Lt`] with other field
+    Lt { other: String },
+
+    /// [`Elt`] with other field
+    Elt { other: String },
+
+    /// [`Gt`] with other field
+    Gt { other: String },
+
+    /// [`Egt`] with other field
+    Egt { other: String },
+
+    /// [`StartWith`]
+    StartWith { text: String },
+
+    /// [`EndsWith`]
+    EndWith { text: String },
+
+    /// [`Contains`]
+    Contains { text: String },
+
+    /// [`Regex`]
+    Regex { pattern: String },
+
+    /// required when the other field is equal to the value, the value is compared as text,
+    /// e.g. `"1"` or `"true"`
+    RequiredIf { field: String, value: String },
+
+    /// required unless the other field is equal to the value, the value is compared as text
+    RequiredUnless { field: String, value: String },
+
+    /// [`required_with`]
+    RequiredWith { field: String },
+
+    /// [`Not`], only the rules without condition can be negated
+    Not { inner: Box<RuleSpec> },
+
+    /// the rule registered in [`RuleParser`] by name
+    Custom {
+        name: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+    },
+}
+
+/// into rule list, and negate it when `$not` is true
+macro_rules! negate {
+    ($not:expr, $rule:expr) => {
+        if $not {
+            Not($rule).into_list()
+        } else {
+            $rule.into_list()
+        }
+    };
+}
+
+/// the bound of [`RuleSpec::Range`], the integer is kept as integer, so the integer
+/// above 2^53 is compared exactly
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Number {
+    Int(i64),
+    Uint(u64),
+    Float(f64),
+}
+
+impl From<Number> for Num {
+    fn from(num: Number) -> Self {
+        match num {
+            Number::Int(n) => Num::Int(n as i128),
+            Number::Uint(n) => Num::Int(n as i128),
+            Number::Float(n) => Num::Float(n),
+        }
+    }
+}
+
+impl From<Num> for Number {
+    fn from(num: Num) -> Self {
+        match num {
+            Num::Int(n) => i64::try_from(n)
+                .map(Number::Int)
+                .or_else(|_| u64::try_from(n).map(Number::Uint))
+                .unwrap_or(Number::Float(n as f64)),
+            Num::Float(n) => Number::Float(n),
+        }
+    }
+}
+
+impl RuleSpec {
+    fn to_list(
+        &self,
+        registry: &RuleParser,
+        not: bool,
+    ) -> Result<RuleList<ValueMap, Message>, String> {
+        let list = match self {
+            RuleSpec::Not { .. } if not => return Err("the rule is negated twice".to_owned()),
+            RuleSpec::Not { inner } => inner.to_list(registry, true)?,
+            RuleSpec::Required => negate!(not, Required),
+            RuleSpec::Email => negate!(not, Email),
+            RuleSpec::Length { min, max } => match (*min, *max) {
+                (Some(min), Some(max)) => negate!(not, Length(min..=max)),
+                (Some(min), None) => negate!(not, Length(min..)),
+                (None, Some(max)) => negate!(not, Length(..=max)),
+                (None, None) => return Err("the length need a bound".to_owned()),
+            },
+            RuleSpec::Range { min, max } => {
+                if min.is_none() && max.is_none() {
+                    return Err("the range need a bound".to_owned());
+                }
+                negate!(
+                    not,
+                    NumRange {
+                        min: min.map(Num::from),
+                        max: max.map(Num::from),
+                    }
+                )
+            }
+            RuleSpec::Confirm { other } => negate!(not, Confirm(field_path(other)?)),
+            RuleSpec::Lt { other } => negate!(not, Lt(field_path(other)?)),
+            RuleSpec::Elt { other } => negate!(not, Elt(field_path(other)?)),
+            RuleSpec::Gt { other } => negate!(not, Gt(field_path(other)?)),
+            RuleSpec::Egt { other } => negate!(not, Egt(field_path(other)?)),
+            RuleSpec::StartWith { text } => negate!(not, StartWith(text.clone())),
+            RuleSpec::EndWith { text } => negate!(not, EndsWith(text.clone())),
+            RuleSpec::Contains { text } => negate!(not, Contains(text.clone())),
+            RuleSpec::Regex { pattern } => {
+                let regex = Regex::try_new(pattern.clone()).map_err(|err| err.to_string())?;
+                negate!(not, regex)
+            }
+            _ if not => return Err("the rule can not be negated".to_owned()),
+            RuleSpec::Nullable => Nullable.into_list(),
+            RuleSpec::Trim => Trim.into_list(),
+            RuleSpec::RequiredIf { field, value } => {
+                RequiredWhen::new(field, Condition::If(value.clone()))?.into_list()
+            }
+            RuleSpec::RequiredUnless { field, value } => {
+                RequiredWhen::new(field, Condition::Unless(value.clone()))?.into_list()
+            }
+            RuleSpec::RequiredWith { field } => {
+                RequiredWhen::new(field, Condition::With)?.into_list()
+            }
+            RuleSpec::Custom { name, args } => {
+                let args: Vec<_> = args.iter().map(String::as_str).collect();
+                registry
+                    .registered(name, &args)
+                    .unwrap_or_else(|| Err(format!("custom rule `{name}` is not registered")))?
+            }
+        };
+
+        Ok(list)
+    }
+
+    /// the reverse of `to_list`, the rule is read from its name and parameters
+    fn from_info<M>(info: &RuleInfo<'_, M>) -> Result<Self, String> {
+        if info.is_async() {
+            return Err("the async rule can not be serialized".to_owned());
+        }
+        if info.param("when").is_some() {
+            return Err("the rule with condition can not be serialized".to_owned());
+        }
+        if info.param("exclusive_min").is_some() || info.param("exclusive_max").is_some() {
+            return Err("the exclusive bound can not be serialized".to_owned());
+        }
+
+        let text = |name| match info.param(name) {
+            Some(Value::String(text)) => Ok(text.clone()),
+            _ => Err(format!("the parameter `{name}` is missing")),
+        };
+        let length = |name| match info.param(name) {
+            Some(Value::Uint64(n)) => usize::try_from(*n).map(Some).map_err(|e| e.to_string()),
+            Some(_) => Err(format!("the parameter `{name}` should be unsigned integer")),
+            None => Ok(None),
+        };
+        let number = |name| match info.param(name) {
+            Some(value) => as_num(value)
+                .map(|num| Some(Number::from(num)))
+                .ok_or_else(|| format!("the parameter `{name}` should be number")),
+            None => Ok(None),
+        };
+
+        let spec = match info.name() {
+            "required" if info.param("required_if").is_some() => RuleSpec::RequiredIf {
+                field: text("required_if")?,
+                value: text("value")?,
+            },
+            "required" if info.param("required_unless").is_some() => RuleSpec::RequiredUnless {
+                field: text("required_unless")?,
+                value: text("value")?,
+            },
+            "required" if info.param("required_with").is_some() => RuleSpec::RequiredWith {
+                field: text("required_with")?,
+            },
+            "required" => RuleSpec::Required,
+            "nullable" => RuleSpec::Nullable,
+            "email" => RuleSpec::Email,
+            "trim" => RuleSpec::Trim,
+            "length" => RuleSpec::Length {
+                min: length("min")?,
+                max: length("max")?,
+            },
+            "range" => RuleSpec::Range {
+                min: number("min")?,
+                max: number("max")?,
+            },
+            "confirm" => RuleSpec::Confirm {
+                other: text("other")?,
+            },
+            "lt" => RuleSpec::Lt {
+                other: text("other")?,
+            },
+            "elt" => RuleSpec::Elt {
+                other: text("other")?,
+            },
+            "gt" => RuleSpec::Gt {
+                other: text("other")?,
+            },
+            "egt" => RuleSpec::Egt {
+                other: text("other")?,
+            },
+            "start_with" => RuleSpec::StartWith {
+                text: text("prefix")?,
+            },
+            "end_with" => RuleSpec::EndWith {
+                text: text("suffix")?,
+            },
+            "contains" => RuleSpec::Contains {
+                text: text("text")?,
+            },
+            "regex" => RuleSpec::Regex {
+                pattern: text("pattern")?,
+            },
+            "custom" => return Err("the closure rule can not be serialized".to_owned()),
+            name if info.params().iter().all(|(key, _)| *key == "not") => RuleSpec::Custom {
+                name: name.to_owned(),
+                args: Vec::new(),
+            },
+            name => {
+                return Err(format!(
+                    "the parameters of rule `{name}` can not be serialized"
+                ))
+            }
+        };
+
+        if info.param("not") == Some(&Value::Boolean(true)) {
+            Ok(RuleSpec::Not {
+                inner: Box::new(spec),
+            })
+        } else {
+            Ok(spec)
+        }
+    }
+}
+
+/// check the field path of relational rules
+fn field_path(field: &str) -> Result<String, String> {
+    match parse_names(field) {
+        Ok(_) => Ok(field.to_owned()),
+        Err(err) => Err(format!("field `{field}` is invalid: {err}")),
+    }
+}
+
+/// the condition of [`RequiredWhen`], the value is compared as text
+#[derive(Debug, Clone)]
+enum Condition {
+    If(String),
+    Unless(String),
+    With,
+}
+
+/// required when the condition about other field is matched, the condition is kept
+/// in the parameters, so it can be serialized back
+#[derive(Debug, Clone)]
+struct RequiredWhen {
+    field: FieldNames,
+    condition: Condition,
+}
+
+impl RequiredWhen {
+    fn new(field: &str, condition: Condition) -> Result<Self, String> {
+        Ok(Self {
+            field: FieldNames::new(field_path(field)?),
+            condition,
+        })
+    }
+
+    fn is_matched(&self, data: &ValueMap) -> bool {
+        fn text_eq(target: &Value, text: &str) -> bool {
+            match target {
+                Value::Option(inner) => inner.as_ref().as_ref().is_some_and(|v| text_eq(v, text)),
+                target if target.is_leaf() => target.to_string() == text,
+                _ => false,
+            }
+        }
+
+        let target = data.get(&self.field);
+        match &self.condition {
+            Condition::If(value) => target.is_some_and(|target| text_eq(target, value)),
+            Condition::Unless(value) => !target.is_some_and(|target| text_eq(target, value)),
+            Condition::With => target.is_some_and(present),
+        }
+    }
+}
+
+impl Rule for RequiredWhen {
+    type Message = Message;
+
+    const NAME: &'static str = "required";
+
+    fn message(&self) -> Self::Message {
+        Message::new(MessageKind::Required)
+    }
+
+    fn params(&self) -> Params {
+        let field = Value::String(self.field.as_str().to_owned());
+        match &self.condition {
+            Condition::If(value) => vec![
+                ("required_if", field),
+                ("value", Value::String(value.clone())),
+            ],
+            Condition::Unless(value) => vec![
+                ("required_unless", field),
+                ("value", Value::String(value.clone())),
+            ],
+            Condition::With => vec![("required_with", field)],
+        }
+    }
+
+    fn call_with_relate(&mut self, data: &mut ValueMap) -> bool {
+        !self.is_matched(data) || Rule::call_with_relate(&mut Required, data)
+    }
+
+    fn call(&mut self, _value: &mut Value) -> bool {
+        unreachable!()
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl Validator<'static, Message> {
+    /// Build validator from the rule configuration with build-in rules
+    ///
+    /// Return [`BuildError`] when the field path, the rule or the message key is invalid.
+    pub fn from_spec(spec: &ValidatorSpec) -> Result<Self, BuildError> {
+        Self::from_spec_with(spec, &RuleParser::new())
+    }
+
+    /// Build validator from the rule configuration, and the custom rules are looked up
+    /// in the registry
+    pub fn from_spec_with(spec: &ValidatorSpec, registry: &RuleParser) -> Result<Self, BuildError> {
+        let mut validator = Validator::new();
+        if spec.bail {
+            validator = validator.bail();
+        }
+
+        for (field, field_spec) in &spec.fields {
+            let names = into_names(field.as_str())?;
+
+            let mut list = RuleList::default();
+            for (index, rule) in field_spec.rules.iter().enumerate() {
+                let mut rules =
+                    rule.to_list(registry, false)
+                        .map_err(|reason| BuildError::InvalidRule {
+                            field: field.clone(),
+                            index,
+                            reason,
+                        })?;
+                list.merge(&mut rules);
+            }
+            if field_spec.bail {
+                list = list.bail();
+            }
+            validator = validator.try_rule(field.as_str(), list)?;
+
+            for (rule, message) in &field_spec.messages {
+                let rule = validator
+                    .rule_get(&names)
+                    .and_then(|list| list.iter().map(|r| r.name()).find(|name| name == rule))
+                    .ok_or_else(|| BuildError::RuleNotFound {
+                        field: field.clone(),
+                        rule: rule.clone(),
+                    })?;
+
+                validator.message.insert(
+                    MessageKey::new(names.clone(), rule),
+                    Message::fallback(message.as_str()),
+                );
+            }
+        }
+
+        Ok(validator)
+    }
+}
+
+impl<M> Validator<'_, M>
+where
+    M: Display,
+{
+    /// Convert validator into the rule configuration, it is the reverse of [`from_spec`],
+    /// e.g. the validator built from configuration is serialized back after editing.
+    ///
+    /// The custom rules are written by name without arguments, and the [`Presence`]
+    /// settings are not included. Return [`BuildError::InvalidRule`] when the rule can not
+    /// be written as configuration, e.g. closures, async rules, rules with `when` condition
+    /// and rules comparing with value.
+    ///
+    /// [`from_spec`]: Validator::from_spec
+    /// [`Presence`]: super::Presence
+    pub fn to_spec(&self) -> Result<ValidatorSpec, BuildError> {
+        let mut fields = IndexMap::new();
+
+        for (names, rules) in self.rules() {
+            let field = names.as_str().to_owned();
+            let mut field_spec = FieldSpec {
+                bail: !self.is_bail && rules.iter().any(RuleInfo::is_bail),
+                ..Default::default()
+            };
+
+            for (index, info) in rules.iter().enumerate() {
+                let rule = RuleSpec::from_info(info).map_err(|reason| BuildError::InvalidRule {
+                    field: field.clone(),
+                    index,
+                    reason,
+                })?;
+                field_spec.rules.push(rule);
+
+                if let Some(message) = info.message() {
+                    field_spec
+                        .messages
+                        .insert(info.name().to_owned(), message.to_string());
+                }
+            }
+            fields.insert(field, field_spec);
+        }
+
+        Ok(ValidatorSpec {
+            bail: self.is_bail,
+            fields,
+        })
+    }
+}
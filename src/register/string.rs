@@ -85,9 +85,9 @@
 //!
 //! > custom rule need to implement Clone.
 
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
-use crate::rule::IntoRuleList;
+use crate::rule::{IntoRuleList, Local};
 
 use super::InnerValidatorError;
 
@@ -102,7 +102,8 @@ pub type Validator<M> = InnerValidatorError<String, M>;
 impl<M> Default for Validator<M> {
     fn default() -> Self {
         Self {
-            message: HashMap::new(),
+            message: IndexMap::new(),
+            location: Default::default(),
         }
     }
 }
@@ -110,7 +111,7 @@ impl<M> Default for Validator<M> {
 impl<M> Validator<M> {
     pub fn insert<R, F: Into<String>>(mut self, field: F, value: &mut String, rules: R) -> Self
     where
-        R: IntoRuleList<String, M>,
+        R: IntoRuleList<String, M, Local>,
     {
         let res = rules.into_list().call(value);
         if !res.is_empty() {
@@ -119,6 +120,7 @@ impl<M> Validator<M> {
         self
     }
 
+    #[allow(clippy::unnecessary_unwrap)]
     pub fn insert_fn<Field, F>(mut self, field: Field, f: F) -> Self
     where
         F: FnOnce() -> Result<(), M>,
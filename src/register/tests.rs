@@ -14,6 +14,7 @@ fn test_validator_error_serialize() {
 
 #[cfg(feature = "full")]
 #[test]
+#[allow(clippy::bool_comparison)]
 fn repeat_insert_rules() {
     use crate::{
         available::{Range, Required, Trim},
@@ -101,10 +102,10 @@ fn test_total() {
 
 #[cfg(feature = "full")]
 #[test]
-#[should_panic = "field `field3` is not found"]
 fn test_check_field() {
     use serde::{Deserialize, Serialize};
 
+    use super::ValidateError;
     use crate::{available::Required, Validatable};
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -118,15 +119,24 @@ fn test_check_field() {
         field2: "foo2".into(),
     };
 
-    let _ = value.validate(Validator::new().rule("field3", Required));
+    let err = value
+        .validate(Validator::new().rule("field3", Required))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ValidateError::FieldNotFound {
+            field: "field3".into()
+        }
+    );
+    assert_eq!(err.to_string(), "field `field3` is not found");
 }
 
 #[cfg(feature = "full")]
 #[test]
-#[should_panic = "field `field3` is not found"]
 fn test_check_field_mut() {
     use serde::{Deserialize, Serialize};
 
+    use super::ValidateError;
     use crate::{available::Required, Validatable};
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -140,5 +150,2182 @@ fn test_check_field_mut() {
         field2: "foo2".into(),
     };
 
-    let _ = value.validate_mut(Validator::new().rule("field3", Required));
+    let err = value
+        .validate_mut(Validator::new().rule("field3", Required))
+        .unwrap_err();
+    assert!(matches!(err, ValidateError::FieldNotFound { field } if field == "field3"));
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn reuse_validator() {
+    use serde::Serialize;
+
+    use crate::{
+        available::{Message, Required},
+        Rule, Validatable, Value,
+    };
+
+    /// fails from the second call on the same instance
+    #[derive(Clone)]
+    struct Once(u8);
+
+    impl Rule for Once {
+        type Message = Message;
+
+        const NAME: &'static str = "once";
+
+        fn message(&self) -> Self::Message {
+            "called twice".into()
+        }
+
+        fn call(&mut self, _data: &mut Value) -> bool {
+            self.0 += 1;
+            self.0 == 1
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Foo {
+        name: String,
+    }
+
+    let validator = Validator::new()
+        .rule("name", Required)
+        .rule("name", Once(0))
+        .map(String::from)
+        .message([("name.required", "name is required")]);
+
+    for _ in 0..3 {
+        let err = validator
+            .validate(Foo {
+                name: String::new(),
+            })
+            .unwrap_err()
+            .into_invalid()
+            .unwrap();
+        assert_eq!(err["name"], vec!["name is required".to_string()]);
+    }
+
+    Foo { name: "foo".into() }.validate(&validator).unwrap();
+
+    assert_eq!(validator.message.len(), 1);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn send_sync() {
+    use std::sync::Arc;
+
+    use serde::Serialize;
+
+    use crate::{
+        available::{Message, Required, StartWith},
+        rule::RuleList,
+        RuleExt, ValidPhrase, ValueMap,
+    };
+
+    use super::ValidatorRefine;
+
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let validator = Validator::new()
+        .rule("name", Required.and(StartWith("foo")))
+        .message([("name.required", "name is required")]);
+    assert_send_sync(&validator);
+    assert_send_sync(&ValidatorRefine::<Message>::new());
+    assert_send_sync(&ValidPhrase::new());
+    assert_send_sync(&RuleList::<ValueMap, Message>::default());
+
+    #[derive(Serialize)]
+    struct Foo {
+        name: &'static str,
+    }
+
+    let validator = Arc::new(validator);
+    let handles: Vec<_> = ["foo", "bar", ""]
+        .into_iter()
+        .map(|name| {
+            let validator = Arc::clone(&validator);
+            std::thread::spawn(move || validator.validate(Foo { name }).is_ok())
+        })
+        .collect();
+
+    let res: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(res, [true, false, false]);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn local_rules() {
+    use std::{cell::RefCell, rc::Rc};
+
+    use serde::Serialize;
+
+    use crate::{
+        available::{Message, Required},
+        custom, Rule, RuleExt, ValidPhrase, Value, ValueMap,
+    };
+
+    use super::ValidatorRefine;
+
+    /// counts the calls, it is not `Send + Sync`
+    #[derive(Clone)]
+    struct Count(Rc<RefCell<u8>>);
+
+    impl Rule for Count {
+        type Message = Message;
+
+        const NAME: &'static str = "count";
+
+        fn message(&self) -> Self::Message {
+            "counted".into()
+        }
+
+        fn call(&mut self, _data: &mut Value) -> bool {
+            *self.0.borrow_mut() += 1;
+            true
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Foo {
+        name: &'static str,
+    }
+
+    let count = Rc::new(RefCell::new(0));
+    let reserved = Rc::new(vec!["admin"]);
+    let enabled = Rc::new(true);
+    let validator = Validator::local()
+        .rule("name", Required.and(Count(count.clone())))
+        .rule(
+            "name",
+            custom(move |name: &mut String| {
+                if reserved.contains(&name.as_str()) {
+                    Err(Message::fallback("is reserved"))
+                } else {
+                    Ok(())
+                }
+            })
+            .when(move |_: &ValueMap| *enabled),
+        );
+
+    validator.validate(Foo { name: "foo" }).unwrap();
+    let err = validator
+        .validate(Foo { name: "admin" })
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err["name"][0].to_string(), "is reserved");
+    assert_eq!(*count.borrow(), 2);
+
+    struct RuleName(&'static str);
+    impl super::IntoMessage for RuleName {
+        fn into_message(rule: &'static str, _: &FieldNames, _: &Value) -> Self {
+            Self(rule)
+        }
+    }
+    let refine = ValidatorRefine::from(validator);
+    let err = refine
+        .validate::<_, RuleName>(Foo { name: "admin" })
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err["name"][0].0, "custom");
+
+    let counter = count.clone();
+    let phrase = ValidPhrase::local().rule(
+        "name",
+        custom(move |_: &mut String| {
+            *counter.borrow_mut() += 1;
+            Ok::<_, String>(())
+        }),
+    );
+    phrase.validate(Foo { name: "foo" }).unwrap();
+    assert_eq!(*count.borrow(), 4);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn wildcard_array() {
+    use serde::Serialize;
+
+    use crate::{
+        available::{Length, Message, MessageKind, Required},
+        custom, ValidPhrase,
+    };
+
+    #[derive(Serialize)]
+    struct LineItem {
+        name: String,
+        tags: Vec<String>,
+    }
+
+    #[derive(Serialize)]
+    struct Order {
+        items: Vec<LineItem>,
+    }
+
+    let order = Order {
+        items: vec![
+            LineItem {
+                name: "foo".into(),
+                tags: vec!["a".into(), "bcd".into()],
+            },
+            LineItem {
+                name: "".into(),
+                tags: vec![],
+            },
+            LineItem {
+                name: "".into(),
+                tags: vec!["ef".into()],
+            },
+        ],
+    };
+
+    let validator = Validator::new()
+        .rule("items[*].name", Required)
+        .rule("items[*].tags[*]", Length(2..))
+        .message([("items[*].name.required", "item name is required")]);
+
+    let err = validator
+        .validate(&order)
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.len(), 3);
+    assert_eq!(
+        err["items[1].name"],
+        vec![Message::from("item name is required")]
+    );
+    assert_eq!(
+        err["items[2].name"],
+        vec![Message::from("item name is required")]
+    );
+    assert!(matches!(
+        err["items[0].tags[0]"][0].kind(),
+        MessageKind::Length
+    ));
+
+    let err = ValidPhrase::new()
+        .rule(
+            "items[*].name",
+            custom(|s: &mut String| {
+                if s.is_empty() {
+                    Err(String::new())
+                } else {
+                    Ok(())
+                }
+            }),
+        )
+        .message([("items[*].name.custom", "{field} is required")])
+        .validate(&order)
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err["items[2].name"], vec!["items[2].name is required"]);
+
+    let empty = Order { items: vec![] };
+    validator.validate(empty).unwrap();
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn wildcard_not_found() {
+    use serde::Serialize;
+
+    use super::ValidateError;
+    use crate::available::Required;
+
+    #[derive(Serialize)]
+    struct Foo {
+        items: Vec<u8>,
+    }
+
+    let err = Validator::new()
+        .rule("list[*]", Required)
+        .validate(Foo { items: vec![1] })
+        .unwrap_err();
+    assert!(matches!(err, ValidateError::FieldNotFound { field } if field == "list[*]"));
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn map_key() {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::available::{Range, Required, Trim};
+
+    #[derive(Serialize, Deserialize)]
+    struct Setting {
+        name: String,
+        value: u8,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Config {
+        settings: HashMap<String, Setting>,
+    }
+
+    let mut settings = HashMap::new();
+    settings.insert(
+        "max.conn".to_string(),
+        Setting {
+            name: " conn ".into(),
+            value: 200,
+        },
+    );
+    settings.insert(
+        "timeout".to_string(),
+        Setting {
+            name: "".into(),
+            value: 5,
+        },
+    );
+
+    let validator = Validator::new()
+        .rule(r#"settings["max.conn"].value"#, Range::new(1_u8..100))
+        .rule("settings[*].name", Required);
+
+    let err = validator
+        .validate(&Config { settings })
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.len(), 2);
+    assert!(err.contains_key(r#"settings["max.conn"].value"#));
+    assert!(err.contains_key(r#"settings["timeout"].name"#));
+
+    let mut settings = HashMap::new();
+    settings.insert(
+        "max.conn".to_string(),
+        Setting {
+            name: " conn ".into(),
+            value: 20,
+        },
+    );
+    let config = Validator::new()
+        .rule(r#"settings["max.conn"].name"#, Trim)
+        .validate_mut(Config { settings })
+        .unwrap();
+    assert_eq!(config.settings["max.conn"].name, "conn");
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn when_rules() {
+    use serde::Serialize;
+
+    use crate::{
+        available::{Email, MessageKind, Required},
+        RuleExt, Value, ValueMap,
+    };
+
+    #[derive(Serialize)]
+    struct Input {
+        notify: bool,
+        email: &'static str,
+    }
+
+    let validator = Validator::new()
+        .rule(
+            "email",
+            Required
+                .when(|data: &ValueMap| data.get(&"notify".into()) == Some(&Value::Boolean(true))),
+        )
+        .rule("email", Email);
+
+    let err = validator
+        .validate(Input {
+            notify: false,
+            email: "",
+        })
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    let list = err.get("email").unwrap();
+    assert_eq!(list.len(), 1);
+    assert_eq!(list[0].kind(), &MessageKind::Email);
+
+    let err = validator
+        .validate(Input {
+            notify: true,
+            email: "",
+        })
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    let list = err.get("email").unwrap();
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0].kind(), &MessageKind::Required);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn nested_validator() {
+    use serde::Serialize;
+
+    use crate::{
+        available::{required_if, Gt, MessageKind, Required},
+        Value,
+    };
+
+    #[derive(Serialize)]
+    struct Address {
+        street: &'static str,
+        lines: Vec<&'static str>,
+    }
+
+    #[derive(Serialize)]
+    struct User {
+        home: Address,
+        work: Option<Address>,
+    }
+
+    let address = Validator::new()
+        .rule("street", Required)
+        .rule("lines[*]", Required)
+        .message([("street.required", "street is required")]);
+
+    let validator = Validator::new()
+        .nested("home", address.clone())
+        .nested("work?", address);
+
+    let user = User {
+        home: Address {
+            street: "",
+            lines: vec!["foo", ""],
+        },
+        work: Some(Address {
+            street: "bar",
+            lines: vec![],
+        }),
+    };
+
+    let err = validator
+        .validate(user)
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+
+    assert_eq!(err.len(), 2);
+    assert_eq!(
+        err.get("home.street").unwrap()[0].to_string(),
+        "street is required"
+    );
+    assert_eq!(
+        err.get("home.lines[1]").unwrap()[0].kind(),
+        &MessageKind::Required
+    );
+
+    // the relational rules resolve the other fields under the mounted field
+    #[derive(Serialize)]
+    struct Range {
+        min: u8,
+        max: u8,
+        kind: &'static str,
+        unit: &'static str,
+    }
+
+    #[derive(Serialize)]
+    struct Query {
+        min: u8,
+        price: Range,
+        weight: Range,
+    }
+
+    let range = Validator::new()
+        .rule("max", Gt("min"))
+        .rule("unit", required_if("kind", "metric"))
+        .bail();
+    let validator = Validator::new()
+        .nested("price", range.clone())
+        .nested("weight", range);
+
+    let range = |min, max, kind, unit| Range {
+        min,
+        max,
+        kind,
+        unit,
+    };
+    let query = Query {
+        min: 100,
+        price: range(1, 10, "metric", "usd"),
+        weight: range(1, 10, "imperial", ""),
+    };
+    validator.validate(query).unwrap();
+
+    let rules: Vec<_> = validator
+        .rules()
+        .map(|(field, rules)| (field.as_str().to_owned(), rules[0].param("other").cloned()))
+        .collect();
+    assert_eq!(
+        rules[0],
+        (
+            "price.max".to_owned(),
+            Some(Value::String("price.min".into()))
+        )
+    );
+
+    // the bail of mounted validator stops at the first error of its sub-fields
+    let query = Query {
+        min: 0,
+        price: range(10, 1, "metric", ""),
+        weight: range(10, 1, "metric", ""),
+    };
+    let err = validator
+        .validate(query)
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.len(), 2);
+    assert!(matches!(
+        err.get("price.max").unwrap()[0].kind(),
+        MessageKind::Compare(..)
+    ));
+    assert!(err.get("weight.max").is_some());
+}
+
+#[tokio::test]
+async fn async_rules() {
+    use std::{
+        collections::HashSet,
+        sync::{Arc, Mutex},
+    };
+
+    use serde::{Deserialize, Serialize};
+
+    use super::ValidateError;
+    use crate::{
+        custom,
+        rule::asynchronous::{self, AsyncRule, BoxFuture},
+        Value,
+    };
+
+    #[derive(Clone, Default)]
+    struct Store(Arc<Mutex<HashSet<String>>>);
+
+    impl Store {
+        async fn contains(&self, value: &str) -> bool {
+            self.0.lock().unwrap().contains(value)
+        }
+    }
+
+    #[derive(Clone)]
+    struct Unique(Store);
+
+    impl AsyncRule for Unique {
+        type Message = String;
+
+        const NAME: &'static str = "unique";
+
+        fn message(&self) -> Self::Message {
+            "is taken".into()
+        }
+
+        fn call<'a>(&'a self, data: &'a Value) -> BoxFuture<'a, bool> {
+            Box::pin(async move {
+                match data {
+                    Value::String(s) => !self.0.contains(s).await,
+                    _ => false,
+                }
+            })
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Input {
+        email: String,
+        sku: String,
+    }
+
+    let store = Store::default();
+    store.0.lock().unwrap().insert("foo@example.com".into());
+
+    let sku_store = store.clone();
+    let validator = Validator::new()
+        .rule(
+            "email",
+            custom(|email: &mut String| {
+                *email = email.trim().to_string();
+                if email.contains('@') {
+                    Ok(())
+                } else {
+                    Err("is not email".to_string())
+                }
+            }),
+        )
+        .rule_async("email", Unique(store.clone()))
+        .rule_async(
+            "sku",
+            asynchronous::custom(move |sku: String| {
+                let store = sku_store.clone();
+                async move {
+                    if store.contains(&sku).await {
+                        Ok(())
+                    } else {
+                        Err("is not exist".to_string())
+                    }
+                }
+            }),
+        )
+        .message([("email.unique", "email is taken")]);
+
+    store.0.lock().unwrap().insert("sku-1".into());
+
+    let input = Input {
+        email: " bar@example.com ".into(),
+        sku: "sku-1".into(),
+    };
+    let input = validator.validate_mut_async(input).await.unwrap();
+    assert_eq!(input.email, "bar@example.com");
+
+    let input = Input {
+        email: "foo@example.com".into(),
+        sku: "sku-2".into(),
+    };
+    let err = validator
+        .validate_async(&input)
+        .await
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(
+        err.get("email").unwrap(),
+        &vec!["email is taken".to_string()]
+    );
+    assert_eq!(err.get("sku").unwrap(), &vec!["is not exist".to_string()]);
+
+    let err = validator
+        .clone()
+        .concurrent()
+        .validate_async(&input)
+        .await
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.len(), 2);
+
+    // async rules are skipped when the sync rules failed
+    let input = Input {
+        email: "foo".into(),
+        sku: "sku-1".into(),
+    };
+    let err = validator
+        .validate_async(&input)
+        .await
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.get("email").unwrap(), &vec!["is not email".to_string()]);
+
+    // the sync validation does not skip the async rules silently
+    assert!(matches!(
+        validator.validate(&input),
+        Err(ValidateError::AsyncRules { field }) if field == "email"
+    ));
+
+    struct RuleMessage(&'static str, String);
+    impl super::IntoMessage for RuleMessage {
+        fn into_message(rule: &'static str, field: &FieldNames, _: &Value) -> Self {
+            Self(rule, field.as_str().to_owned())
+        }
+    }
+    let refine = super::ValidatorRefine::from(validator.clone());
+    assert!(matches!(
+        refine.validate::<_, RuleMessage>(&input),
+        Err(ValidateError::AsyncRules { .. })
+    ));
+    let err = refine
+        .validate_async::<_, RuleMessage>(Input {
+            email: "foo@example.com".into(),
+            sku: "sku-2".into(),
+        })
+        .await
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.len(), 2);
+    assert_eq!(err.get("email").unwrap()[0].0, "unique");
+    assert_eq!(err.get("sku").unwrap()[0].1, "sku");
+    let input = refine
+        .validate_mut_async::<_, RuleMessage>(Input {
+            email: " bar@example.com ".into(),
+            sku: "sku-1".into(),
+        })
+        .await
+        .unwrap_or_else(|_| panic!("input should be valid"));
+    assert_eq!(input.email, "bar@example.com");
+
+    let err = validator
+        .bail()
+        .validate_async(Input {
+            email: "foo@example.com".into(),
+            sku: "sku-2".into(),
+        })
+        .await
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.len(), 1);
+}
+
+#[test]
+fn validate_with_context() {
+    use serde::{Deserialize, Serialize};
+
+    use super::ValidateError;
+    use crate::{custom, rule::Context, Rule, Value, ValueMap};
+
+    struct AppCtx {
+        user_id: u32,
+        reserved: Vec<&'static str>,
+    }
+
+    #[derive(Clone)]
+    struct NotReserved;
+
+    impl Rule for NotReserved {
+        type Message = String;
+
+        const NAME: &'static str = "not_reserved";
+
+        fn message(&self) -> Self::Message {
+            "is reserved".into()
+        }
+
+        fn call(&mut self, _data: &mut Value) -> bool {
+            true
+        }
+
+        fn call_with_context(&mut self, data: &mut ValueMap, context: Context<'_>) -> bool {
+            let ctx = context.get::<AppCtx>().unwrap();
+            match data.current() {
+                Some(Value::String(s)) => !ctx.reserved.contains(&s.as_str()),
+                _ => false,
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Input {
+        name: String,
+        owner: u32,
+    }
+
+    let validator = Validator::new().rule("name", NotReserved).rule(
+        "owner",
+        custom(|owner: &mut u32, ctx: &AppCtx| {
+            if *owner == ctx.user_id {
+                Ok(())
+            } else {
+                Err("is not current user".to_string())
+            }
+        }),
+    );
+
+    let ctx = AppCtx {
+        user_id: 1,
+        reserved: vec!["admin"],
+    };
+
+    validator
+        .validate_with(
+            Input {
+                name: "foo".into(),
+                owner: 1,
+            },
+            &ctx,
+        )
+        .unwrap();
+
+    let err = validator
+        .validate_with(
+            Input {
+                name: "admin".into(),
+                owner: 2,
+            },
+            &ctx,
+        )
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.get("name").unwrap()[0], "is reserved");
+    assert_eq!(err.get("owner").unwrap()[0], "is not current user");
+
+    // the context is not passed, or its type is not matched
+    let input = || Input {
+        name: "foo".into(),
+        owner: 1,
+    };
+    let missing = |err| {
+        matches!(
+            err,
+            Err(ValidateError::MissingContext { field, expected })
+                if field == "owner" && expected.ends_with("AppCtx")
+        )
+    };
+    assert!(missing(validator.validate(input())));
+    assert!(missing(validator.validate_with(input(), &1_u32)));
+    assert!(missing(validator.validate_mut(input()).map(|_| ())));
+    let phrase = crate::ValidPhrase::new().rule(
+        "owner",
+        custom(|_: &mut u32, _: &AppCtx| Ok::<_, String>(())),
+    );
+    assert!(missing(phrase.validate(input())));
+    struct RuleName(&'static str);
+    impl super::IntoMessage for RuleName {
+        fn into_message(rule: &'static str, _: &FieldNames, _: &Value) -> Self {
+            Self(rule)
+        }
+    }
+    let refine = super::ValidatorRefine::from(validator.clone());
+    assert!(matches!(
+        refine.validate::<_, RuleName>(input()),
+        Err(ValidateError::MissingContext { .. })
+    ));
+
+    // the rule list refuses to run without the context, instead of panicking
+    let rules = validator
+        .rules
+        .get(&FieldNames::new("owner".into()))
+        .unwrap();
+    let mut value_map = ValueMap::new(crate::ser::to_value(input()).unwrap());
+    value_map.index(FieldNames::new("owner".into()));
+    let expected = rules.call(&mut value_map, Context::default()).unwrap_err();
+    assert!(expected.ends_with("AppCtx"));
+    assert!(rules.call_gen_message::<RuleName>(&mut value_map).is_err());
+    assert_eq!(
+        validator.validate(input()).unwrap_err().to_string(),
+        format!(
+            "field `owner` requires a context of `{}`, please use `validate_with`",
+            std::any::type_name::<AppCtx>()
+        )
+    );
+
+    // the context is borrowed only while validating, e.g. a request-scoped handle
+    for user_id in [1, 2] {
+        let ctx = AppCtx {
+            user_id,
+            reserved: Vec::new(),
+        };
+        assert_eq!(validator.validate_with(input(), &ctx).is_ok(), user_id == 1);
+    }
+
+    let validator = Validator::new().rule(
+        "name",
+        custom(|name: &mut String, ctx: &AppCtx| {
+            *name = format!("{}-{}", name, ctx.user_id);
+            Ok::<_, String>(())
+        }),
+    );
+    let input = validator
+        .validate_mut_with(
+            Input {
+                name: "foo".into(),
+                owner: 1,
+            },
+            &ctx,
+        )
+        .unwrap();
+    assert_eq!(input.name, "foo-1");
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn keep_registration_order() {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        available::{Confirm, Length, Required, StartWith, Trim},
+        RuleExt,
+    };
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct Input {
+        zeta: String,
+        alpha: String,
+        password: String,
+        confirm: String,
+    }
+
+    let validator = Validator::new()
+        .rule("zeta", Required.and(StartWith("z")).and(Length(5..)))
+        .rule("alpha", Required)
+        .rule("zeta", Required)
+        .rule("password", Trim)
+        .rule("confirm", Confirm("password"));
+
+    let list = validator
+        .rules
+        .get(&FieldNames::new("zeta".into()))
+        .unwrap();
+    let names: Vec<_> = list.iter().map(|rule| rule.name()).collect();
+    assert_eq!(names, ["start_with", "length", "required"]);
+
+    let input = Input {
+        zeta: String::new(),
+        alpha: String::new(),
+        password: " foo ".into(),
+        confirm: "foo".into(),
+    };
+
+    let err = validator
+        .validate_mut(input)
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    let keys: Vec<_> = err.keys().map(FieldNames::as_str).collect();
+    assert_eq!(keys, ["zeta", "alpha"]);
+
+    let json = serde_json::to_string(&err.map(|msg| msg.to_string())).unwrap();
+    assert!(json.starts_with(r#"{"zeta":["#));
+    assert!(json.contains(r#"],"alpha":["#));
+}
+
+#[test]
+#[should_panic(expected = "field `name[` is invalid")]
+fn rule_invalid_field() {
+    // the malformed field panics in release build too, instead of being never matched
+    let _ = Validator::<String>::new()
+        .rule("name[", crate::custom(|_: &mut String| Ok::<_, String>(())));
+}
+
+#[test]
+fn try_build_and_validate() {
+    use serde::{Deserialize, Serialize};
+
+    use super::{BuildError, ValidateError};
+    use crate::{custom, Rule, ValidPhrase, Value};
+
+    #[derive(Clone)]
+    struct BadName;
+
+    impl Rule for BadName {
+        type Message = String;
+
+        const NAME: &'static str = "bad-name";
+
+        fn message(&self) -> Self::Message {
+            String::new()
+        }
+
+        fn call(&mut self, _data: &mut Value) -> bool {
+            true
+        }
+    }
+
+    #[allow(clippy::ptr_arg)]
+    fn not_empty(s: &mut String) -> Result<(), String> {
+        if s.is_empty() {
+            Err("empty".into())
+        } else {
+            Ok(())
+        }
+    }
+
+    let err = Validator::new()
+        .try_rule("name[", custom(not_empty))
+        .err()
+        .unwrap();
+    assert!(matches!(err, BuildError::InvalidField { field, .. } if field == "name["));
+
+    let err = Validator::new().try_rule("name", BadName).err().unwrap();
+    assert_eq!(
+        err,
+        BuildError::InvalidRuleName {
+            field: "name".into(),
+            rule: "bad-name".into()
+        }
+    );
+
+    let validator = Validator::new()
+        .try_rule("name", custom(not_empty))
+        .unwrap();
+
+    let err = validator
+        .clone()
+        .try_message([("name", "foo")])
+        .err()
+        .unwrap();
+    assert_eq!(err, BuildError::InvalidMessageKey { key: "name".into() });
+
+    let err = validator
+        .clone()
+        .try_message([("age.custom", "foo")])
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        BuildError::FieldNotFound {
+            field: "age".into()
+        }
+    );
+
+    let err = validator
+        .clone()
+        .try_message([("name.required", "foo")])
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        BuildError::RuleNotFound {
+            field: "name".into(),
+            rule: "required".into()
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "rule `required` is not found in field `name`"
+    );
+
+    let validator = validator
+        .try_message([("name.custom", "name is empty")])
+        .unwrap();
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct Input {
+        name: String,
+    }
+    #[derive(Serialize)]
+    struct Other {
+        title: String,
+    }
+
+    let input = validator
+        .validate_mut(Input { name: "foo".into() })
+        .unwrap();
+    assert_eq!(input.name, "foo");
+
+    let err = validator
+        .validate(Input {
+            name: String::new(),
+        })
+        .unwrap_err();
+    let ValidateError::Invalid(err) = err else {
+        panic!("expect invalid error");
+    };
+    assert_eq!(err.get("name").unwrap()[0], "name is empty");
+
+    let err = validator
+        .validate(Other {
+            title: String::new(),
+        })
+        .unwrap_err();
+    assert!(matches!(err, ValidateError::FieldNotFound { field } if field == "name"));
+
+    // the same methods of `ValidPhrase`
+    let err = ValidPhrase::new()
+        .try_rule("name[", custom(not_empty))
+        .err()
+        .unwrap();
+    assert!(matches!(err, BuildError::InvalidField { field, .. } if field == "name["));
+
+    let phrase = ValidPhrase::new()
+        .try_rule("name", custom(not_empty))
+        .unwrap();
+    let err = phrase
+        .clone()
+        .try_message([("name.required", "foo")])
+        .err()
+        .unwrap();
+    assert!(matches!(err, BuildError::RuleNotFound { .. }));
+
+    let phrase = phrase
+        .try_message([("name.custom", "{field} is empty")])
+        .unwrap();
+    let input = phrase.validate_mut(Input { name: "foo".into() }).unwrap();
+    assert_eq!(input.name, "foo");
+
+    let Err(ValidateError::Invalid(err)) = phrase.validate(Input {
+        name: String::new(),
+    }) else {
+        panic!("expect invalid error");
+    };
+    assert_eq!(err.get("name").unwrap()[0], "name is empty");
+
+    let err = phrase
+        .validate(Other {
+            title: String::new(),
+        })
+        .unwrap_err();
+    assert!(matches!(err, ValidateError::FieldNotFound { field } if field == "name"));
+}
+
+#[test]
+fn serialize_error_path() {
+    use super::ValidateError;
+    use crate::rule::custom;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Input {
+        #[serde(serialize_with = "fail")]
+        name: String,
+    }
+    fn fail<S: serde::Serializer>(_: &str, _: S) -> Result<S::Ok, S::Error> {
+        Err(serde::ser::Error::custom("boom"))
+    }
+
+    let validator = Validator::new().rule("name", custom(|_: &mut String| Ok::<_, String>(())));
+
+    let err = validator
+        .validate(Input {
+            name: String::new(),
+        })
+        .unwrap_err();
+    let ValidateError::Serialize(err) = err else {
+        panic!("expect serialize error");
+    };
+    assert_eq!(err.message(), "boom");
+    assert_eq!(err.path().as_str(), "name");
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn presence_mode() {
+    use super::{Presence, ValidateError};
+    use crate::{
+        available::{Length, Message, Required},
+        RuleExt,
+    };
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    struct Patch {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        email: Option<String>,
+        tags: BTreeMap<String, String>,
+    }
+
+    let validator = Validator::<Message>::new()
+        .rule("name?", Length(2..))
+        .rule("email?", Required.and(Length(3..)))
+        .rule("tags[foo]", Length(2..))
+        .sometimes("name?")
+        .presence("email?", Presence::Required)
+        .sometimes("tags[foo]");
+
+    let err = validator
+        .validate(Patch {
+            name: None,
+            email: None,
+            tags: BTreeMap::new(),
+        })
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.len(), 1);
+    assert_eq!(err.get("email?").unwrap().len(), 1);
+    assert_eq!(
+        err.get("email?").unwrap()[0].kind(),
+        &crate::available::MessageKind::Required
+    );
+
+    let err = validator
+        .validate(Patch {
+            name: Some("a".into()),
+            email: Some("foo@bar".into()),
+            tags: BTreeMap::from([("foo".into(), "b".into())]),
+        })
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.len(), 2);
+    assert!(err.get("name?").is_some());
+    assert!(err.get("tags[foo]").is_some());
+
+    validator
+        .validate(Patch {
+            name: Some("ab".into()),
+            email: Some("foo@bar".into()),
+            tags: BTreeMap::new(),
+        })
+        .unwrap();
+
+    // the default mode still treats the absent field as an error
+    let err = validator
+        .presence("email?", Presence::Strict)
+        .validate(Patch {
+            name: None,
+            email: None,
+            tags: BTreeMap::new(),
+        })
+        .unwrap_err();
+    assert!(matches!(err, ValidateError::FieldNotFound { field } if field == "email?"));
+
+    // the absent field fails without `required` rule, and the absent array at its own path
+    #[derive(Serialize)]
+    struct Post {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        tags: Option<Vec<String>>,
+    }
+
+    let validator = Validator::<Message>::new()
+        .rule("title?", Length(2..))
+        .rule("tags?[*]", Length(2..))
+        .presence("title?", Presence::Required)
+        .presence("tags?[*]", Presence::Required);
+
+    let err = validator
+        .validate(Post {
+            title: None,
+            tags: None,
+        })
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.len(), 2);
+    assert_eq!(
+        err.get("title?").unwrap()[0].kind(),
+        &crate::available::MessageKind::Required
+    );
+    assert_eq!(
+        err.get("tags?").unwrap()[0].kind(),
+        &crate::available::MessageKind::Required
+    );
+
+    validator
+        .validate(Post {
+            title: Some("foo".into()),
+            tags: Some(Vec::new()),
+        })
+        .unwrap();
+
+    struct RuleName(&'static str);
+    impl super::IntoMessage for RuleName {
+        fn into_message(rule: &'static str, _: &FieldNames, _: &crate::Value) -> Self {
+            Self(rule)
+        }
+    }
+    let refine = super::ValidatorRefine::from(validator);
+    let err = refine
+        .validate::<_, RuleName>(Post {
+            title: None,
+            tags: Some(vec!["a".into()]),
+        })
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.len(), 2);
+    assert_eq!(err.get("title?").unwrap()[0].0, "required");
+    assert_eq!(err.get("tags?[0]").unwrap()[0].0, "length");
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn option_semantics() {
+    use crate::{
+        available::{Email, Length, Message, MessageKind, Nullable, Required, Trim},
+        custom, RuleExt,
+    };
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct Home {
+        number: u8,
+    }
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct Input {
+        name: Option<String>,
+        nickname: Option<String>,
+        email: Option<String>,
+        home: Option<Home>,
+    }
+
+    let validator = Validator::new()
+        .rule("name", Trim.and(Required).and(Length(2..)))
+        .rule("nickname", Nullable.and(Length(2..)))
+        .rule("email", Email)
+        .rule(
+            "home?.number",
+            custom(|n: &mut u8| {
+                if *n > 0 {
+                    Ok(())
+                } else {
+                    Err(Message::fallback("zero"))
+                }
+            }),
+        );
+
+    let err = validator
+        .validate(Input {
+            name: None,
+            nickname: None,
+            email: Some("foo".into()),
+            home: None,
+        })
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.len(), 2);
+    assert_eq!(err.get("name").unwrap()[0].kind(), &MessageKind::Required);
+    assert_eq!(err.get("email").unwrap()[0].kind(), &MessageKind::Email);
+
+    let err = validator
+        .validate(Input {
+            name: Some(" ".into()),
+            nickname: Some("a".into()),
+            email: Some("foo@example.com".into()),
+            home: Some(Home { number: 0 }),
+        })
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.len(), 3);
+    assert_eq!(err.get("name").unwrap()[0].kind(), &MessageKind::Required);
+    assert_eq!(err.get("nickname").unwrap()[0].kind(), &MessageKind::Length);
+    assert!(err.get("home?.number").is_some());
+
+    let input = validator
+        .validate_mut(Input {
+            name: Some(" foo ".into()),
+            nickname: Some("bar".into()),
+            email: Some("foo@example.com".into()),
+            home: Some(Home { number: 1 }),
+        })
+        .unwrap();
+    assert_eq!(input.name.as_deref(), Some("foo"));
+
+    // the custom rules get the `Option` value as it is
+    #[derive(Clone)]
+    struct IsSome;
+    impl crate::Rule for IsSome {
+        type Message = Message;
+
+        const NAME: &'static str = "is_some";
+
+        fn message(&self) -> Self::Message {
+            Message::fallback("none")
+        }
+
+        fn call(&mut self, data: &mut crate::Value) -> bool {
+            matches!(data, crate::Value::Option(value) if value.is_some())
+        }
+    }
+    let validator = Validator::new().rule("nickname", IsSome);
+    let input = |nickname: Option<&str>| Input {
+        name: None,
+        nickname: nickname.map(Into::into),
+        email: None,
+        home: None,
+    };
+    assert!(validator.validate(input(Some("foo"))).is_ok());
+    assert!(validator.validate(input(None)).is_err());
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn merge_validators() {
+    use crate::{
+        available::{Length, MessageKind, Required},
+        RuleExt,
+    };
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Input {
+        id: String,
+        title: String,
+    }
+
+    let base = Validator::new()
+        .rule("id", Required)
+        .rule("title", Length(1..3))
+        .message([("id.required", "id is required")]);
+
+    let mut validator = Validator::new()
+        .rule("title", Required.and(Length(2..)))
+        .message([("title.length", "title is too short")]);
+    validator.extend(Validator::new().rule("title", Length(5..)));
+    let validator = validator.merge(base);
+
+    let err = validator
+        .validate(Input {
+            id: String::new(),
+            title: "abcd".into(),
+        })
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+
+    // the same-name rule is replaced by the merged one
+    assert_eq!(
+        err.keys().map(|k| k.as_str()).collect::<Vec<_>>(),
+        ["title", "id"]
+    );
+    assert_eq!(err.get("title").unwrap().len(), 1);
+    assert_eq!(
+        err.get("title").unwrap()[0].to_string(),
+        "title is too short"
+    );
+    assert_eq!(err.get("id").unwrap()[0].to_string(), "id is required");
+
+    let other = Validator::new()
+        .rule("title", Length(5..))
+        .validate(Input {
+            id: String::new(),
+            title: "abcd".into(),
+        })
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    let err = err.merge(other.clone()).prefix("post");
+    assert_eq!(err.len(), 2);
+    assert_eq!(err.get("post.title").unwrap().len(), 2);
+    assert_eq!(
+        err.get("post.title").unwrap()[1].kind(),
+        &MessageKind::Length
+    );
+
+    let mut err = err;
+    err.extend(other.prefix("[0]"));
+    assert_eq!(err.get("post.title").unwrap().len(), 2);
+    assert!(err.get("[0].title").is_some());
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn rules_introspection() {
+    use crate::{
+        available::{Confirm, Length, Message, Not, Range, Required, StartWith},
+        rule::asynchronous::BoxFuture,
+        AsyncRule, RuleExt, Value,
+    };
+
+    #[derive(Clone)]
+    struct Unique;
+
+    impl AsyncRule for Unique {
+        type Message = Message;
+
+        const NAME: &'static str = "unique";
+
+        fn message(&self) -> Self::Message {
+            "the value is already taken".into()
+        }
+
+        fn call<'a>(&'a self, _data: &'a Value) -> BoxFuture<'a, bool> {
+            Box::pin(async { true })
+        }
+    }
+
+    let validator = Validator::new()
+        .rule("name", Required.and(Length(6..12)).bail())
+        .rule("password", Confirm("password_confirm"))
+        .rule("age", Range::new(18_u8..=60).custom(|_: &mut u8| Ok(())))
+        .rule("title", Not(StartWith("foo")))
+        .rule_async("name", Unique)
+        .rule_async("email", Unique)
+        .message([
+            ("name.length", "name length is invalid"),
+            ("name.unique", "name is taken"),
+        ]);
+
+    let rules: Vec<_> = validator.rules().collect();
+    let fields: Vec<_> = rules.iter().map(|(field, _)| field.as_str()).collect();
+    assert_eq!(fields, ["name", "password", "age", "title", "email"]);
+
+    let name = &rules[0].1;
+    let names: Vec<_> = name.iter().map(|info| info.name()).collect();
+    assert_eq!(names, ["required", "length", "unique"]);
+    assert!(name[0].is_bail());
+    assert!(name[0].params().is_empty());
+    assert!(name[0].message().is_none());
+    assert_eq!(name[1].param("min"), Some(&Value::Uint64(6)));
+    assert_eq!(name[1].param("exclusive_max"), Some(&Value::Uint64(12)));
+    assert_eq!(name[1].param("max"), None);
+    assert_eq!(
+        name[1].message().unwrap().to_string(),
+        "name length is invalid"
+    );
+    assert!(!name[1].is_async());
+    assert!(name[2].is_async());
+    assert!(!name[2].is_bail());
+    assert_eq!(name[2].message().unwrap().to_string(), "name is taken");
+
+    let password = &rules[1].1;
+    assert!(!password[0].is_bail());
+    assert_eq!(
+        password[0].params(),
+        &vec![("other", Value::String("password_confirm".into()))]
+    );
+
+    let age = &rules[2].1;
+    assert_eq!(
+        age[0].params(),
+        &vec![("min", Value::Uint8(18)), ("max", Value::Uint8(60))]
+    );
+    assert_eq!(age[1].name(), "custom");
+    assert!(age[1].params().is_empty());
+
+    let title = &rules[3].1;
+    assert_eq!(title[0].param("prefix"), Some(&Value::String("foo".into())));
+    assert_eq!(title[0].param("not"), Some(&Value::Boolean(true)));
+
+    let email = &rules[4].1;
+    assert_eq!(email.len(), 1);
+    assert!(email[0].is_async());
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn json_schema() {
+    use serde_json::json;
+
+    use crate::{
+        available::{required_if, Confirm, Email, Length, Not, Nullable, Range, Regex, Required},
+        RuleExt,
+    };
+
+    let validator = Validator::new()
+        .rule("name", Required.and(Length(6..12)))
+        .rule("email", Required.and(Email))
+        .rule("age", Range::new(18_u8..=60))
+        .rule("score", Range::new(0.0..100.0))
+        .rule("code", Regex::new("^[a-z]+$").and(Not(Length(3..=3))))
+        .rule("password", Confirm("password_confirm"))
+        .rule("company", required_if("kind", "business"))
+        .rule("nickname", Nullable.and(Length(..=8)))
+        .rule("home?.street", Required)
+        .rule("tags", Length(1..))
+        .rule("tags[*]", Length(..=10).custom(|_: &mut String| Ok(())))
+        .rule("point.0", Range::new(-10..=10))
+        .rule(r#"labels["en"]"#, Required)
+        .rule("labels[*]", Length(..=20))
+        .rule("grade", Range::new('a'..='f'))
+        .sometimes("email");
+
+    let schema = serde_json::to_value(validator.json_schema()).unwrap();
+
+    assert_eq!(
+        schema,
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "name": { "minLength": 6, "maxLength": 11, "minItems": 6, "maxItems": 11 },
+                "email": { "type": "string", "format": "email" },
+                "age": { "type": "integer", "minimum": 18, "maximum": 60 },
+                "score": { "type": "number", "minimum": 0.0, "exclusiveMaximum": 100.0 },
+                "code": {
+                    "type": "string",
+                    "pattern": "^[a-z]+$",
+                    "not": { "minLength": 3, "maxLength": 3 }
+                },
+                "password": { "x-confirm": { "other": "password_confirm" } },
+                "company": { "x-required": { "when": true } },
+                "nickname": { "maxLength": 8, "maxItems": 8 },
+                "home": {
+                    "type": ["object", "null"],
+                    "properties": { "street": {} },
+                    "required": ["street"]
+                },
+                "tags": {
+                    "minItems": 1,
+                    "minProperties": 1,
+                    "items": { "maxLength": 10, "maxItems": 10, "x-custom": {} },
+                    "additionalProperties": { "maxLength": 10, "maxItems": 10, "x-custom": {} }
+                },
+                "point": {
+                    "type": "array",
+                    "prefixItems": [{ "type": "integer", "minimum": -10, "maximum": 10 }]
+                },
+                "labels": {
+                    "type": "object",
+                    "properties": { "en": {} },
+                    "required": ["en"],
+                    "additionalProperties": { "maxLength": 20, "maxItems": 20 }
+                },
+                "grade": { "type": "string", "x-range": { "min": "a", "max": "f" } }
+            },
+            "required": ["name"]
+        })
+    );
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn validator_spec() {
+    use serde::{Deserialize, Serialize};
+
+    use super::{
+        spec::{FieldSpec, Number, RuleSpec, ValidatorSpec},
+        BuildError,
+    };
+    use crate::{
+        available::{Lt, MessageKind, Required},
+        RuleExt,
+    };
+
+    #[derive(Serialize, Deserialize)]
+    struct Input {
+        kind: u8,
+        name: String,
+        company: String,
+        min: u8,
+        max: u8,
+    }
+
+    #[derive(Serialize)]
+    struct Id {
+        id: u64,
+    }
+
+    let json = r#"{
+        "fields": {
+            "name": {
+                "rules": [
+                    { "rule": "trim" },
+                    { "rule": "not", "inner": { "rule": "start_with", "text": "admin" } },
+                    { "rule": "length", "max": 8 }
+                ],
+                "bail": true,
+                "messages": { "start_with": "name is reserved" }
+            },
+            "company": {
+                "rules": [{ "rule": "required_if", "field": "kind", "value": "2" }]
+            },
+            "max": {
+                "rules": [{ "rule": "gt", "other": "min" }, { "rule": "range", "min": 1, "max": 100 }]
+            }
+        }
+    }"#;
+    let spec: ValidatorSpec = serde_json::from_str(json).unwrap();
+
+    // serialize back
+    let spec2: ValidatorSpec =
+        serde_json::from_str(&serde_json::to_string(&spec).unwrap()).unwrap();
+    assert_eq!(spec, spec2);
+    assert_eq!(
+        spec.fields["name"].rules[2],
+        RuleSpec::Length {
+            min: None,
+            max: Some(8)
+        }
+    );
+
+    assert_eq!(
+        spec.fields["max"].rules[1],
+        RuleSpec::Range {
+            min: Some(Number::Int(1)),
+            max: Some(Number::Int(100))
+        }
+    );
+
+    let validator = Validator::from_spec(&spec).unwrap();
+    assert_eq!(validator.to_spec().unwrap(), spec);
+
+    // the integer bound above 2^53 is kept exactly
+    let big: ValidatorSpec = serde_json::from_str(
+        r#"{ "fields": { "id": { "rules": [{ "rule": "range", "max": 9007199254740993 }] } } }"#,
+    )
+    .unwrap();
+    let big_validator = Validator::from_spec(&big).unwrap();
+    assert_eq!(big_validator.to_spec().unwrap(), big);
+    big_validator
+        .validate(Id {
+            id: 9007199254740993,
+        })
+        .unwrap();
+    assert!(big_validator
+        .validate(Id {
+            id: 9007199254740994
+        })
+        .is_err());
+
+    let input = Input {
+        kind: 1,
+        name: " jone ".into(),
+        company: String::new(),
+        min: 1,
+        max: 2,
+    };
+    let input = validator.validate_mut(input).unwrap();
+    assert_eq!(input.name, "jone");
+
+    let input = Input {
+        kind: 2,
+        name: "administrator".into(),
+        company: String::new(),
+        min: 3,
+        max: 2,
+    };
+    let err = validator
+        .validate(input)
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.get("name").unwrap().len(), 1);
+    assert_eq!(err.get("name").unwrap()[0].to_string(), "name is reserved");
+    assert_eq!(
+        err.get("company").unwrap()[0].kind(),
+        &MessageKind::Required
+    );
+    assert_eq!(err.get("max").unwrap().len(), 1);
+
+    // invalid entries
+    let spec = |rules: Vec<RuleSpec>, messages: Vec<(&str, &str)>| ValidatorSpec {
+        bail: false,
+        fields: [(
+            "name".to_owned(),
+            FieldSpec {
+                rules,
+                bail: false,
+                messages: messages
+                    .into_iter()
+                    .map(|(k, v)| (k.to_owned(), v.to_owned()))
+                    .collect(),
+            },
+        )]
+        .into_iter()
+        .collect(),
+    };
+
+    let err = Validator::from_spec(&spec(
+        vec![
+            RuleSpec::Required,
+            RuleSpec::Custom {
+                name: "foo".into(),
+                args: Vec::new(),
+            },
+        ],
+        vec![],
+    ))
+    .err()
+    .unwrap();
+    assert_eq!(
+        err,
+        BuildError::InvalidRule {
+            field: "name".into(),
+            index: 1,
+            reason: "custom rule `foo` is not registered".into()
+        }
+    );
+
+    let err = Validator::from_spec(&spec(
+        vec![RuleSpec::Length {
+            min: None,
+            max: None,
+        }],
+        vec![],
+    ))
+    .err()
+    .unwrap();
+    assert!(matches!(err, BuildError::InvalidRule { index: 0, .. }));
+
+    let err = Validator::from_spec(&spec(
+        vec![RuleSpec::Not {
+            inner: Box::new(RuleSpec::Trim),
+        }],
+        vec![],
+    ))
+    .err()
+    .unwrap();
+    assert!(matches!(err, BuildError::InvalidRule { index: 0, .. }));
+
+    let err = Validator::from_spec(&spec(vec![RuleSpec::Required], vec![("email", "foo")]))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        BuildError::RuleNotFound {
+            field: "name".into(),
+            rule: "email".into()
+        }
+    );
+
+    let err = Validator::from_spec(&spec(
+        vec![RuleSpec::Regex {
+            pattern: "^[a-z+$".into(),
+        }],
+        vec![],
+    ))
+    .err()
+    .unwrap();
+    assert!(matches!(err, BuildError::InvalidRule { index: 0, .. }));
+
+    let err = Validator::from_spec(&spec(
+        vec![RuleSpec::Gt {
+            other: "min[".into(),
+        }],
+        vec![],
+    ))
+    .err()
+    .unwrap();
+    assert!(matches!(err, BuildError::InvalidRule { index: 0, .. }));
+
+    // the missing other field is failed
+    let validator = Validator::from_spec(&spec(
+        vec![RuleSpec::Confirm {
+            other: "password".into(),
+        }],
+        vec![],
+    ))
+    .unwrap();
+    #[derive(Serialize)]
+    struct Account {
+        name: &'static str,
+    }
+    assert!(validator.validate(Account { name: "jone" }).is_err());
+
+    // the relational rules are negated with the other field
+    let mut not_gt = spec(vec![], vec![]);
+    not_gt.fields = [(
+        "max".to_owned(),
+        FieldSpec {
+            rules: vec![RuleSpec::Not {
+                inner: Box::new(RuleSpec::Gt {
+                    other: "min".into(),
+                }),
+            }],
+            ..Default::default()
+        },
+    )]
+    .into_iter()
+    .collect();
+    let validator = Validator::from_spec(&not_gt).unwrap();
+    let input = |min, max| Input {
+        kind: 1,
+        name: String::new(),
+        company: String::new(),
+        min,
+        max,
+    };
+    assert!(validator.validate(input(2, 1)).is_ok());
+    assert!(validator.validate(input(1, 2)).is_err());
+
+    // the rules can not be written as configuration
+    let err = Validator::new()
+        .rule("name", Required.custom(|_: &mut String| Ok(())))
+        .to_spec()
+        .err()
+        .unwrap();
+    assert!(matches!(err, BuildError::InvalidRule { index: 1, .. }));
+    let err = Validator::new()
+        .rule("name", Lt(10_u8))
+        .to_spec()
+        .err()
+        .unwrap();
+    assert!(matches!(err, BuildError::InvalidRule { index: 0, .. }));
+
+    let mut invalid_field = spec(vec![RuleSpec::Required], vec![]);
+    invalid_field.fields = [("name[".to_owned(), FieldSpec::default())]
+        .into_iter()
+        .collect();
+    let err = Validator::from_spec(&invalid_field).err().unwrap();
+    assert!(matches!(err, BuildError::InvalidField { .. }));
+}
+
+#[cfg(all(feature = "full", feature = "serde_json"))]
+#[test]
+fn validate_json() {
+    use serde_json::json;
+
+    use super::Presence;
+    use crate::{
+        available::{Length, MessageKind, Nullable, Range, Required, Trim},
+        RuleExt,
+    };
+
+    let validator = Validator::new()
+        .rule("user.name", Trim.and(Required).and(Length(2..)))
+        .rule("user.nickname", Nullable.and(Length(2..)))
+        .rule("user.age", Range::new(18_u64..))
+        .rule("user.balance", Range::new(..0_i64))
+        .rule("user.score", Range::new(0.0..=10.0))
+        .rule("items[*].name", Required)
+        .rule("remark", Required)
+        .presence("remark", Presence::Required);
+
+    let json = json!({
+        "user": { "name": " foo ", "nickname": null, "age": 20, "balance": -1, "score": 9.5 },
+        "items": [{ "name": "bar" }],
+        "remark": "baz",
+    });
+    let res = validator.validate_json_mut(json.clone()).unwrap();
+    assert_eq!(res["user"]["name"], "foo");
+    assert_eq!(res["user"]["nickname"], json!(null));
+    assert_eq!(res["user"]["score"], json!(9.5));
+
+    let mut json = json;
+    json["user"]["name"] = json!(null);
+    json["user"]["age"] = json!(18.0);
+    json["user"]["balance"] = json!(1);
+    json["items"]
+        .as_array_mut()
+        .unwrap()
+        .push(json!({ "name": "" }));
+    json.as_object_mut().unwrap().remove("remark");
+
+    let err = validator
+        .validate_json(json)
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.len(), 5);
+    assert_eq!(
+        err.get("user.name").unwrap()[0].kind(),
+        &MessageKind::Required
+    );
+    // float is not integer
+    assert_eq!(err.get("user.age").unwrap()[0].kind(), &MessageKind::Range);
+    assert!(err.get("user.balance").is_some());
+    assert!(err.get("items[1].name").is_some());
+    assert!(err.get("remark").is_some());
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn validate_json_str() {
+    use super::{Presence, ValidateError};
+    use crate::available::{Length, Required};
+
+    let validator = Validator::new()
+        .rule("user.name", Required)
+        .rule("user.email", Required)
+        .rule("tags[*]", Length(..=4))
+        .presence("user.email", Presence::Required);
+
+    let text = "{\n  \"user\": { \"name\": \"\" },\n  \"tags\": [\"rust\", \"valitron\"]\n}";
+    let Err(ValidateError::Invalid(err)) = validator.validate_json_str(text) else {
+        panic!("text should be invalid");
+    };
+    assert_eq!(err.len(), 3);
+
+    let location = |key: &str| {
+        let location = err.location(key).unwrap();
+        (location.line(), location.column(), location.offset())
+    };
+    assert_eq!(location("user.name"), (2, 21, 22));
+    // the absent field is located at its parent
+    assert_eq!(location("user.email"), (2, 11, 12));
+    assert_eq!(location("tags[1]"), (3, 20, 47));
+
+    let err = err.prefix("body");
+    assert!(err.location("body.tags[1]").is_some());
+
+    validator
+        .validate_json_str(r#"{"user": {"name": "foo", "email": "foo@bar"}, "tags": []}"#)
+        .unwrap();
+
+    assert!(matches!(
+        validator.validate_json_str(r#"{"user": {"email": "foo@bar"}, "tags": []}"#),
+        Err(ValidateError::FieldNotFound { field }) if field == "user.name"
+    ));
+
+    let Err(ValidateError::Syntax(err)) = validator.validate_json_str("{\"user\": {,}") else {
+        panic!("text should be malformed");
+    };
+    assert_eq!(err.reason(), "key must be a string");
+    assert_eq!(err.location().column(), 11);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn transform_rules() {
+    use crate::{
+        available::{
+            transform::{CollapseWhitespace, DefaultIfEmpty, Lowercase, Truncate},
+            Length, Message, Required,
+        },
+        custom,
+        register::string::Validator as StringValidator,
+        Rule, RuleExt, Value,
+    };
+    use serde::{Deserialize, Serialize};
+
+    /// the transform's message is never added
+    #[derive(Clone)]
+    struct Failing;
+
+    impl Rule for Failing {
+        type Message = Message;
+        const NAME: &'static str = "failing";
+        const TRANSFORM: bool = true;
+
+        fn message(&self) -> Self::Message {
+            Message::fallback("failing")
+        }
+        fn call(&mut self, _data: &mut Value) -> bool {
+            false
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct Input {
+        name: String,
+        title: Option<String>,
+    }
+
+    let validator = Validator::new()
+        .rule(
+            "name",
+            custom(|name: &mut String| {
+                if name == "jone doe" {
+                    Ok(())
+                } else {
+                    Err(Message::fallback("not lowercase"))
+                }
+            })
+            .and(Length(..=8))
+            .and(Lowercase)
+            .and(CollapseWhitespace)
+            .and(Failing),
+        )
+        .rule("title", Required.and(DefaultIfEmpty("none")));
+
+    // the rules are introspected in registration order
+    let (_, rules) = validator.rules().next().unwrap();
+    let names: Vec<_> = rules.iter().map(|rule| rule.name()).collect();
+    assert_eq!(
+        names,
+        [
+            "custom",
+            "length",
+            "lowercase",
+            "collapse_whitespace",
+            "failing"
+        ]
+    );
+
+    let input = validator
+        .validate_mut(Input {
+            name: " Jone   DOE ".into(),
+            title: None,
+        })
+        .unwrap();
+    assert_eq!(input.name, "jone doe");
+    assert_eq!(input.title.as_deref(), Some("none"));
+
+    let mut name = "Rustacean".to_owned();
+    let err = StringValidator::new()
+        .insert("name", &mut name, Length(..=4).and(Truncate(4)))
+        .validate(());
+    assert!(err.is_ok());
+    assert_eq!(name, "Rust");
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn coerce_rules() {
+    use crate::{
+        available::{coerce::ToInt, Gt, Lt, MessageKind, Trim},
+        RuleExt,
+    };
+    use std::collections::BTreeMap;
+
+    let validator = Validator::new()
+        .rule(r#"["age"]"#, ToInt.and(Gt(17_i64)).and(Trim))
+        .rule(r#"["count"]"#, ToInt.and(Lt(10_i64)).bail());
+
+    let query = BTreeMap::from([("age", " 18 "), ("count", "3")]);
+    validator.validate(query).unwrap();
+
+    let query = BTreeMap::from([("age", "17"), ("count", "three")]);
+    let err = validator
+        .validate(query)
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(
+        err.get(r#"["age"]"#).unwrap()[0].kind(),
+        &MessageKind::Compare("greater".into(), "17".into())
+    );
+    // the numeric rule is failed without panic, and skipped by bail
+    let count = err.get(r#"["count"]"#).unwrap();
+    assert_eq!(count.len(), 1);
+    assert_eq!(count[0].kind(), &MessageKind::Type("int".into()));
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn each_rule() {
+    use crate::{
+        available::{Length, MessageKind, Required, Trim},
+        rule::{Each, IntoRuleList},
+        RuleExt,
+    };
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct Input {
+        tags: Vec<String>,
+        pair: (String, String),
+        labels: BTreeMap<String, String>,
+        matrix: Vec<Vec<String>>,
+    }
+
+    let validator = Validator::new()
+        .rule("tags", Required)
+        .rule("tags", Each(Trim.and(Length(3..=20))))
+        .rule("pair", Each(Required))
+        .rule("labels", Each(Required))
+        .rule("matrix", Each(Each(Required)))
+        .message([("tags[*].length", "tag is 3 to 20 chars")]);
+
+    let input = || Input {
+        tags: vec![" rust ".into(), "go".into()],
+        pair: ("a".into(), String::new()),
+        labels: BTreeMap::from([("a".into(), "foo".into()), ("b".into(), String::new())]),
+        matrix: vec![vec!["a".into()], vec!["b".into(), String::new()]],
+    };
+
+    let err = validator
+        .validate(input())
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
+    assert_eq!(err.len(), 4);
+    assert_eq!(
+        err.get("tags[1]").unwrap()[0].to_string(),
+        "tag is 3 to 20 chars"
+    );
+    assert_eq!(err.get("pair.1").unwrap()[0].kind(), &MessageKind::Required);
+    assert!(err.get(r#"labels["b"]"#).is_some());
+    assert!(err.get("matrix[1][1]").is_some());
+
+    let names: Vec<_> = validator
+        .rules()
+        .map(|(field, _)| field.as_str().to_owned())
+        .collect();
+    assert_eq!(
+        names,
+        ["tags", "tags[*]", "pair[*]", "labels[*]", "matrix[*][*]"]
+    );
+
+    let mut data = input();
+    data.tags[1] = " python ".into();
+    data.pair.1 = "b".into();
+    data.labels.insert("b".into(), "bar".into());
+    data.matrix[1][1] = "c".into();
+    let data = validator.validate_mut(data).unwrap();
+    assert_eq!(data.tags, ["rust", "python"]);
+
+    // the rules appended later are applied to the field itself
+    let validator = Validator::new().rule("tags", Each(Length(3..)).into_list().and(Required));
+    let rules: Vec<_> = validator
+        .rules()
+        .map(|(field, rules)| (field.as_str().to_owned(), rules[0].name()))
+        .collect();
+    assert_eq!(
+        rules,
+        [
+            ("tags[*]".to_owned(), "length"),
+            ("tags".to_owned(), "required")
+        ]
+    );
+
+    // the tuple index is `u8`
+    let tuple = crate::Value::Tuple(vec![crate::Value::Unit; 257]);
+    assert_eq!(tuple.expand_names(&"[*]".into()), None);
 }
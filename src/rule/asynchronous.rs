@@ -0,0 +1,460 @@
+//! Async rules, used by I/O-backed checks, e.g. "email not taken" or "SKU exists"
+//!
+//! Async rules only read the field value, and they are run by [`validate_async`]
+//! or [`validate_mut_async`] after the sync rules of the same field passed.
+//!
+//! # A custom async rule example
+//! ```rust
+//! # use std::{collections::HashSet, sync::Arc};
+//! # use valitron::{rule::asynchronous::{AsyncRule, BoxFuture}, Value};
+//! #[derive(Clone)]
+//! struct Unique(Arc<HashSet<String>>);
+//!
+//! impl AsyncRule for Unique {
+//!     type Message = &'static str;
+//!
+//!     const NAME: &'static str = "unique";
+//!
+//!     fn message(&self) -> Self::Message {
+//!         "the value is already taken"
+//!     }
+//!
+//!     fn call<'a>(&'a self, data: &'a Value) -> BoxFuture<'a, bool> {
+//!         Box::pin(async move {
+//!             match data {
+//!                 Value::String(s) => !self.0.contains(s),
+//!                 _ => false,
+//!             }
+//!         })
+//!     }
+//! }
+//! ```
+//!
+//! [`validate_async`]: crate::Validator::validate_async
+//! [`validate_mut_async`]: crate::Validator::validate_mut_async
+
+use std::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::value::{FromValue, Value, ValueMap};
+
+/// An owned dynamically typed future, it is returned by async rules.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Trait used by creating async rule
+pub trait AsyncRule: 'static + Sized + Clone + Send + Sync {
+    /// custom define returning message type
+    type Message;
+
+    /// Named rule type, allow `a-z` | `A-Z` | `0-9` | `_` composed string, and not start with `0-9`
+    const NAME: &'static str;
+
+    /// Default rule error message, when validate fails, return the message to user
+    fn message(&self) -> Self::Message;
+
+    /// Rule specific implementation, data is current field value,
+    /// success returning true, or else returning false.
+    fn call<'a>(&'a self, data: &'a Value) -> BoxFuture<'a, bool>;
+}
+
+/// Convert an async closure to async rule list
+///
+/// the closure argument is a clone of current field value.
+///
+/// ```rust
+/// # use valitron::rule::asynchronous::custom;
+/// let rule = custom(|sku: String| async move {
+///     if sku.starts_with("SKU-") {
+///         Ok(())
+///     } else {
+///         Err("sku is not exist")
+///     }
+/// });
+/// ```
+pub fn custom<F, V, Fut, M>(f: F) -> AsyncRuleList<M>
+where
+    F: Fn(V) -> Fut + 'static + Clone + Send + Sync,
+    Fut: Future<Output = Result<(), M>> + Send + 'static,
+    V: FromValue + Clone + Send + 'static,
+    M: Send + 'static,
+{
+    AsyncRuleList::default().custom(f)
+}
+
+/// Async rule extension, it can coupling some async rules
+pub trait AsyncRuleExt<M> {
+    fn and<R>(self, other: R) -> AsyncRuleList<M>
+    where
+        R: AsyncRule<Message = M>;
+
+    fn custom<F, V, Fut>(self, other: F) -> AsyncRuleList<M>
+    where
+        F: Fn(V) -> Fut + 'static + Clone + Send + Sync,
+        Fut: Future<Output = Result<(), M>> + Send + 'static,
+        V: FromValue + Clone + Send + 'static;
+}
+
+impl<R, M> AsyncRuleExt<M> for R
+where
+    R: AsyncRule<Message = M>,
+    M: Send + 'static,
+{
+    fn and<R2>(self, other: R2) -> AsyncRuleList<M>
+    where
+        R2: AsyncRule<Message = M>,
+    {
+        AsyncRuleList::default().and(self).and(other)
+    }
+
+    fn custom<F, V, Fut>(self, other: F) -> AsyncRuleList<M>
+    where
+        F: Fn(V) -> Fut + 'static + Clone + Send + Sync,
+        Fut: Future<Output = Result<(), M>> + Send + 'static,
+        V: FromValue + Clone + Send + 'static,
+    {
+        AsyncRuleList::default().and(self).custom(other)
+    }
+}
+
+/// Async rules collection
+pub struct AsyncRuleList<M> {
+    list: Vec<ErasedAsyncRule<M>>,
+    is_bail: bool,
+}
+
+impl<M> Default for AsyncRuleList<M> {
+    fn default() -> Self {
+        Self {
+            list: Vec::new(),
+            is_bail: false,
+        }
+    }
+}
+
+impl<M> Clone for AsyncRuleList<M> {
+    fn clone(&self) -> Self {
+        Self {
+            list: self.list.clone(),
+            is_bail: self.is_bail,
+        }
+    }
+}
+
+impl<M> AsyncRuleList<M> {
+    pub fn and<R>(mut self, other: R) -> Self
+    where
+        R: AsyncRule<Message = M>,
+        M: Send + 'static,
+    {
+        self.list.retain(|rule| rule.name() != R::NAME);
+        self.list
+            .push(ErasedAsyncRule(Box::new(AsyncRuleIntoBoxed(other))));
+        self
+    }
+
+    pub fn custom<F, V, Fut>(mut self, other: F) -> Self
+    where
+        F: Fn(V) -> Fut + 'static + Clone + Send + Sync,
+        Fut: Future<Output = Result<(), M>> + Send + 'static,
+        V: FromValue + Clone + Send + 'static,
+        M: Send + 'static,
+    {
+        self.list.push(ErasedAsyncRule(Box::new(AsyncClosure {
+            handler: other,
+            _marker: PhantomData,
+        })));
+        self
+    }
+
+    /// when first validate error is encountered, right away return Err(message) in one field.
+    pub fn bail(mut self) -> Self {
+        self.is_bail = true;
+        self
+    }
+
+    pub(crate) fn set_bail(&mut self) {
+        self.is_bail = true;
+    }
+
+    pub fn is_bail(&self) -> bool {
+        self.is_bail
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub(crate) fn merge(&mut self, other: &mut AsyncRuleList<M>) {
+        for new_rule in &other.list {
+            let name = new_rule.name();
+            if name != "custom" {
+                self.list.retain(|rule| rule.name() != name);
+            }
+        }
+
+        self.list.append(&mut other.list);
+        self.is_bail = self.is_bail || other.is_bail;
+    }
+
+    /// check the rule name is existing
+    pub(crate) fn contains(&self, rule: &str) -> bool {
+        self.list.iter().any(|item| item.name() == rule)
+    }
+
+    /// the rule names in registration order
+    pub(crate) fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.list.iter().map(ErasedAsyncRule::name)
+    }
+
+    #[must_use]
+    pub(crate) fn map<M2>(self, f: fn(M) -> M2) -> AsyncRuleList<M2>
+    where
+        M: Send + 'static,
+        M2: Send + 'static,
+    {
+        AsyncRuleList {
+            list: self
+                .list
+                .into_iter()
+                .map(|rule| {
+                    ErasedAsyncRule(Box::new(AsyncMap {
+                        inner: rule,
+                        layer: f,
+                    }))
+                })
+                .collect(),
+            is_bail: self.is_bail,
+        }
+    }
+
+    /// Run all async rules on the field value one by one.
+    pub(crate) async fn call(&self, data: &Value) -> Vec<(&'static str, M)> {
+        let mut msg = Vec::new();
+
+        for endpoint in self.list.iter() {
+            if let Err(e) = endpoint.0.call(data).await {
+                msg.push((endpoint.name(), e));
+
+                if self.is_bail {
+                    break;
+                }
+            }
+        }
+
+        msg
+    }
+}
+
+/// Trait used by registering async rules
+pub trait IntoAsyncRuleList<M> {
+    fn into_list(self) -> AsyncRuleList<M>;
+}
+
+impl<M> IntoAsyncRuleList<M> for AsyncRuleList<M> {
+    fn into_list(self) -> AsyncRuleList<M> {
+        self
+    }
+}
+
+impl<R, M> IntoAsyncRuleList<M> for R
+where
+    R: AsyncRule<Message = M>,
+    M: Send + 'static,
+{
+    fn into_list(self) -> AsyncRuleList<M> {
+        AsyncRuleList::default().and(self)
+    }
+}
+
+struct ErasedAsyncRule<M>(Box<dyn BoxedAsyncRule<M> + Send + Sync>);
+
+impl<M> ErasedAsyncRule<M> {
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+}
+
+impl<M> Clone for ErasedAsyncRule<M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone_box())
+    }
+}
+
+trait BoxedAsyncRule<M> {
+    fn clone_box(&self) -> Box<dyn BoxedAsyncRule<M> + Send + Sync>;
+
+    fn call<'a>(&'a self, data: &'a Value) -> BoxFuture<'a, Result<(), M>>;
+
+    fn name(&self) -> &'static str;
+}
+
+#[derive(Clone)]
+struct AsyncRuleIntoBoxed<R>(R);
+
+impl<R, M> BoxedAsyncRule<M> for AsyncRuleIntoBoxed<R>
+where
+    R: AsyncRule<Message = M>,
+    M: Send + 'static,
+{
+    fn clone_box(&self) -> Box<dyn BoxedAsyncRule<M> + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn call<'a>(&'a self, data: &'a Value) -> BoxFuture<'a, Result<(), M>> {
+        Box::pin(async move {
+            if self.0.call(data).await {
+                Ok(())
+            } else {
+                Err(self.0.message())
+            }
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        R::NAME
+    }
+}
+
+struct AsyncClosure<F, V> {
+    handler: F,
+    _marker: PhantomData<fn() -> V>,
+}
+
+impl<F: Clone, V> Clone for AsyncClosure<F, V> {
+    fn clone(&self) -> Self {
+        Self {
+            handler: self.handler.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F, V, Fut, M> BoxedAsyncRule<M> for AsyncClosure<F, V>
+where
+    F: Fn(V) -> Fut + 'static + Clone + Send + Sync,
+    Fut: Future<Output = Result<(), M>> + Send + 'static,
+    V: FromValue + Clone + Send + 'static,
+    M: Send + 'static,
+{
+    fn clone_box(&self) -> Box<dyn BoxedAsyncRule<M> + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn call<'a>(&'a self, data: &'a Value) -> BoxFuture<'a, Result<(), M>> {
+        let mut map = ValueMap::new(data.clone());
+        let val = V::from_value(&mut map)
+            .expect("argument type can not be matched")
+            .clone();
+
+        Box::pin((self.handler)(val))
+    }
+
+    fn name(&self) -> &'static str {
+        "custom"
+    }
+}
+
+struct AsyncMap<M, M2> {
+    inner: ErasedAsyncRule<M>,
+    layer: fn(M) -> M2,
+}
+
+impl<M, M2> Clone for AsyncMap<M, M2> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            layer: self.layer,
+        }
+    }
+}
+
+impl<M, M2> BoxedAsyncRule<M2> for AsyncMap<M, M2>
+where
+    M: Send + 'static,
+    M2: Send + 'static,
+{
+    fn clone_box(&self) -> Box<dyn BoxedAsyncRule<M2> + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn call<'a>(&'a self, data: &'a Value) -> BoxFuture<'a, Result<(), M2>> {
+        let layer = self.layer;
+        Box::pin(async move { self.inner.0.call(data).await.map_err(layer) })
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+}
+
+/// Poll all futures concurrently, and return their outputs in the same order.
+pub(crate) fn join_all<'a, T>(futures: Vec<BoxFuture<'a, T>>) -> JoinAll<'a, T> {
+    JoinAll {
+        outputs: futures.iter().map(|_| None).collect(),
+        futures: futures.into_iter().map(Some).collect(),
+    }
+}
+
+pub(crate) struct JoinAll<'a, T> {
+    futures: Vec<Option<BoxFuture<'a, T>>>,
+    outputs: Vec<Option<T>>,
+}
+
+// the outputs are never pinned, and the futures are boxed
+impl<T> Unpin for JoinAll<'_, T> {}
+
+impl<T> Future for JoinAll<'_, T> {
+    type Output = Vec<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut pending = false;
+
+        for (future, output) in this.futures.iter_mut().zip(this.outputs.iter_mut()) {
+            if let Some(fut) = future {
+                match fut.as_mut().poll(cx) {
+                    Poll::Ready(value) => {
+                        *output = Some(value);
+                        *future = None;
+                    }
+                    Poll::Pending => pending = true,
+                }
+            }
+        }
+
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(this.outputs.iter_mut().filter_map(Option::take).collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{join_all, BoxFuture};
+
+    #[tokio::test]
+    async fn test_join_all() {
+        let futures: Vec<BoxFuture<'_, usize>> = (0..3)
+            .map(|i| -> BoxFuture<'_, usize> {
+                Box::pin(async move {
+                    for _ in 0..(3 - i) {
+                        tokio::task::yield_now().await;
+                    }
+                    i
+                })
+            })
+            .collect();
+
+        assert_eq!(join_all(futures).await, vec![0, 1, 2]);
+    }
+}
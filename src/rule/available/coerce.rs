@@ -0,0 +1,200 @@
+//! Coerce the string value into other types, it is useful for query strings and
+//! form bodies, which values are always string.
+//!
+//! | rule | type |
+//! |---|---|
+//! | [`ToInt`] | `Int64` |
+//! | [`ToFloat`] | `Float64` |
+//! | [`ToBool`] | `Boolean`, accepts `true` / `false`, `1` / `0`, `on` / `off` and `yes` / `no` |
+//! | [`ToChar`] | `Char`, the string should have only one char |
+//!
+//! When the string can not be parsed, or the value is other type, the [`MessageKind::Type`]
+//! is returned, and the value already of the type is untouched, except that the other
+//! integers are widened into `Int64`. The rules after the coercion are applied to the new
+//! value, so the numeric rules should use the same type, e.g. `ToInt.and(Range::new(1_i64..=100))`.
+//!
+//! The leading and trailing whitespace are not allowed, register [`Trim`] on the field to remove them,
+//! it is run before the other rules.
+//!
+//! # Examples
+//! ```
+//! # use std::collections::HashMap;
+//! # use valitron::{available::{coerce::*, MessageKind, Range}, RuleExt, Validator};
+//! let validator = Validator::new()
+//!     .rule(r#"["page"]"#, ToInt.and(Range::new(1_i64..=100)))
+//!     .rule(r#"["draft"]"#, ToBool);
+//!
+//! let query = HashMap::from([("page", "42"), ("draft", "on")]);
+//! validator.validate(query).unwrap();
+//!
+//! let query = HashMap::from([("page", "0"), ("draft", "maybe")]);
+//! let err = validator
+//!     .validate(query)
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
+//! assert_eq!(err.get(r#"["page"]"#).unwrap()[0].kind(), &MessageKind::Range);
+//! assert_eq!(
+//!     err.get(r#"["draft"]"#).unwrap()[0].kind(),
+//!     &MessageKind::Type("bool".into())
+//! );
+//! ```
+//!
+//! [`MessageKind::Type`]: super::MessageKind::Type
+//! [`Trim`]: super::trim
+
+use super::{Message, MessageKind};
+use crate::{Rule, Value};
+
+/// the body returns `Some(Some(value))` for the new value, `Some(None)` for the value
+/// already of the type, and `None` for failure
+macro_rules! coerce_rule {
+    ($(#[$doc:meta])* $ty:ident($name:literal, $label:literal), |$value:ident| $body:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy)]
+        pub struct $ty;
+
+        impl Rule for $ty {
+            type Message = Message;
+
+            const NAME: &'static str = $name;
+
+            const UNWRAP_OPTION: bool = true;
+
+            fn message(&self) -> Self::Message {
+                Message::new(MessageKind::Type($label.into()))
+            }
+
+            fn call(&mut self, data: &mut Value) -> bool {
+                let $value = &*data;
+                match $body {
+                    Some(Some(value)) => {
+                        *data = value;
+                        true
+                    }
+                    Some(None) => true,
+                    None => false,
+                }
+            }
+        }
+    };
+}
+
+coerce_rule!(
+    /// Parses the string into `Int64`, the other integers are widened into `Int64`,
+    /// it fails when the value can not fit into `Int64`, e.g. `u64::MAX`
+    ToInt("to_int", "int"),
+    |value| match value {
+        Value::String(s) => s.parse().ok().map(|n| Some(Value::Int64(n))),
+        Value::Uint8(n) => Some(Some(Value::Int64((*n).into()))),
+        Value::Int8(n) => Some(Some(Value::Int64((*n).into()))),
+        Value::Uint16(n) => Some(Some(Value::Int64((*n).into()))),
+        Value::Int16(n) => Some(Some(Value::Int64((*n).into()))),
+        Value::Uint32(n) => Some(Some(Value::Int64((*n).into()))),
+        Value::Int32(n) => Some(Some(Value::Int64((*n).into()))),
+        Value::Uint64(n) => i64::try_from(*n).ok().map(|n| Some(Value::Int64(n))),
+        Value::Int64(_) => Some(None),
+        _ => None,
+    }
+);
+
+coerce_rule!(
+    /// Parses the string into `Float64`, the other floats are untouched, `NaN` and
+    /// infinity are not allowed
+    ToFloat("to_float", "float"),
+    |value| match value {
+        Value::String(s) => s
+            .parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .map(|n| Some(Value::Float64(n.into()))),
+        Value::Float32(_) | Value::Float64(_) => Some(None),
+        _ => None,
+    }
+);
+
+coerce_rule!(
+    /// Parses the string into `Boolean`, it is case insensitive
+    ToBool("to_bool", "bool"),
+    |value| match value {
+        Value::String(s) => parse_bool(s).map(|b| Some(Value::Boolean(b))),
+        Value::Boolean(_) => Some(None),
+        _ => None,
+    }
+);
+
+coerce_rule!(
+    /// Converts the string with only one char into `Char`
+    ToChar("to_char", "char"),
+    |value| match value {
+        Value::String(s) => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(Some(Value::Char(c))),
+                _ => None,
+            }
+        }
+        Value::Char(_) => Some(None),
+        _ => None,
+    }
+);
+
+fn parse_bool(s: &str) -> Option<bool> {
+    const TRUE: [&str; 4] = ["true", "1", "on", "yes"];
+    const FALSE: [&str; 4] = ["false", "0", "off", "no"];
+
+    if TRUE.iter().any(|t| t.eq_ignore_ascii_case(s)) {
+        Some(true)
+    } else if FALSE.iter().any(|f| f.eq_ignore_ascii_case(s)) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coerce<R: Rule>(mut rule: R, value: Value) -> Option<Value> {
+        let mut value = value;
+        Rule::call(&mut rule, &mut value).then_some(value)
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(s.into())
+    }
+
+    #[test]
+    fn coercion() {
+        assert_eq!(coerce(ToInt, string("-42")), Some(Value::Int64(-42)));
+        assert_eq!(coerce(ToInt, Value::Uint8(4)), Some(Value::Int64(4)));
+        assert_eq!(coerce(ToInt, Value::Int32(-4)), Some(Value::Int64(-4)));
+        assert_eq!(coerce(ToInt, Value::Uint64(4)), Some(Value::Int64(4)));
+        assert_eq!(coerce(ToInt, Value::Uint64(u64::MAX)), None);
+        assert_eq!(coerce(ToInt, string("4.2")), None);
+        assert_eq!(coerce(ToInt, string(" 4")), None);
+        assert_eq!(coerce(ToInt, Value::Boolean(true)), None);
+
+        assert_eq!(
+            coerce(ToFloat, string("4.5")),
+            Some(Value::Float64(4.5.into()))
+        );
+        assert_eq!(
+            coerce(ToFloat, string("1e3")),
+            Some(Value::Float64(1e3.into()))
+        );
+        assert_eq!(coerce(ToFloat, string("NaN")), None);
+        assert_eq!(coerce(ToFloat, Value::Int64(1)), None);
+
+        assert_eq!(coerce(ToBool, string("ON")), Some(Value::Boolean(true)));
+        assert_eq!(coerce(ToBool, string("0")), Some(Value::Boolean(false)));
+        assert_eq!(coerce(ToBool, string("")), None);
+
+        assert_eq!(coerce(ToChar, string("中")), Some(Value::Char('中')));
+        assert_eq!(coerce(ToChar, string("ab")), None);
+        assert_eq!(coerce(ToChar, string("")), None);
+
+        assert_eq!(ToBool.message().kind(), &MessageKind::Type("bool".into()));
+    }
+}
@@ -0,0 +1,331 @@
+//! Set-like rules of array, they support `Vec`, array, tuple and set, other types always return false.
+//!
+//! - [`Unique`]: all items are distinct
+//! - [`DistinctBy`]: the values at the path of items are distinct, e.g. `DistinctBy("address")`
+//!   for `emails`, it compares every `emails[*].address`
+//! - [`ContainsItem`]: the array contains the item
+//! - [`Subset`]: all items are in the allowed list
+//!
+//! The items are compared by `Eq` of [`Value`], so their types should be same, e.g.
+//! `ContainsItem(1_u8)` does not match `1_u16`. The floats are compared by `==`, it means
+//! `NaN` is distinct from every value, including itself.
+//!
+//! # Examples
+//! ```
+//! # use serde::Serialize;
+//! # use valitron::{available::{ContainsItem, DistinctBy, MessageKind, Subset, Unique}, RuleExt, Validator};
+//! #[derive(Serialize)]
+//! struct Email {
+//!     address: &'static str,
+//!     primary: bool,
+//! }
+//!
+//! #[derive(Serialize)]
+//! struct Input {
+//!     tags: Vec<&'static str>,
+//!     emails: Vec<Email>,
+//! }
+//!
+//! let validator = Validator::new()
+//!     .rule("tags", Unique.and(ContainsItem("rust")).and(Subset(["rust", "go", "web"])))
+//!     .rule("emails", DistinctBy("address"));
+//!
+//! let input = Input {
+//!     tags: vec!["rust", "web"],
+//!     emails: vec![
+//!         Email { address: "a@example.com", primary: true },
+//!         Email { address: "b@example.com", primary: false },
+//!     ],
+//! };
+//! validator.validate(input).unwrap();
+//!
+//! let input = Input {
+//!     tags: vec!["go", "go", "java"],
+//!     emails: vec![
+//!         Email { address: "a@example.com", primary: true },
+//!         Email { address: "a@example.com", primary: false },
+//!     ],
+//! };
+//! let err = validator
+//!     .validate(input)
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
+//!
+//! let tags: Vec<_> = err.get("tags").unwrap().iter().map(|m| m.kind().clone()).collect();
+//! assert_eq!(
+//!     tags,
+//!     [
+//!         MessageKind::Unique,
+//!         MessageKind::ContainsItem("rust".into()),
+//!         MessageKind::Subset,
+//!     ]
+//! );
+//! assert_eq!(
+//!     err.get("emails").unwrap()[0].kind(),
+//!     &MessageKind::Distinct("address".into())
+//! );
+//! ```
+
+use std::{collections::BTreeSet, fmt::Display};
+
+use serde::Serialize;
+
+use super::{Message, MessageKind};
+use crate::{register::FieldNames, rule::Params, ser::to_value, Rule, Value};
+
+/// All items are distinct
+#[derive(Debug, Clone, Copy)]
+pub struct Unique;
+
+/// The values at the path of items are distinct, the items without the path are skipped,
+/// but it fails when no item has the path, so the misspelled path is not passed silently.
+///
+/// The path is relative to the item, and has the same format as field names, e.g.
+/// `"address"` or `"profile.email"`, the full form `"emails[*].address"` is also accepted.
+#[derive(Debug, Clone, Copy)]
+pub struct DistinctBy<T>(pub T);
+
+/// The array contains the item
+#[derive(Debug, Clone, Copy)]
+pub struct ContainsItem<T>(pub T);
+
+/// All items are in the allowed list, e.g. `Subset(["a", "b"])` or `Subset(vec![1, 2])`
+#[derive(Debug, Clone, Copy)]
+pub struct Subset<T>(pub T);
+
+/// the items of array, tuple or set
+fn items(value: &Value) -> Option<&[Value]> {
+    match value {
+        Value::Array(vec) | Value::Tuple(vec) | Value::TupleStruct(vec) => Some(vec),
+        _ => None,
+    }
+}
+
+/// check the value contains float, the `Ord` of float wrapper can not be invoked,
+/// so the value should be compared by `Eq` instead, e.g. it can not be put into `BTreeSet`.
+fn has_float(value: &Value) -> bool {
+    match value {
+        Value::Float32(_) | Value::Float64(_) => true,
+        Value::Option(value) => value.as_ref().as_ref().is_some_and(has_float),
+        Value::Array(vec)
+        | Value::Tuple(vec)
+        | Value::TupleStruct(vec)
+        | Value::NewtypeStruct(vec)
+        | Value::Enum(_, vec)
+        | Value::TupleVariant(_, vec) => vec.iter().any(has_float),
+        Value::Map(map) | Value::Struct(map) | Value::StructVariant(_, map) => map
+            .iter()
+            .any(|(key, value)| has_float(key) || has_float(value)),
+        _ => false,
+    }
+}
+
+/// check all values are distinct, the `Ord` of `Value` is used only when there is no
+/// float, because the `Ord` of float wrapper can not be invoked.
+fn is_distinct(values: &[&Value]) -> bool {
+    if values.iter().any(|value| has_float(value)) {
+        values
+            .iter()
+            .enumerate()
+            .all(|(index, value)| !values[..index].contains(value))
+    } else {
+        let mut set = BTreeSet::new();
+        values.iter().all(|value| set.insert(*value))
+    }
+}
+
+/// the composite value can not be displayed
+fn display(value: &Value) -> String {
+    if value.is_leaf() {
+        value.to_string()
+    } else {
+        format!("{value:?}")
+    }
+}
+
+impl Rule for Unique {
+    type Message = Message;
+
+    const NAME: &'static str = "unique";
+
+    const UNWRAP_OPTION: bool = true;
+
+    fn message(&self) -> Self::Message {
+        Message::new(MessageKind::Unique)
+    }
+
+    fn call(&mut self, data: &mut Value) -> bool {
+        match items(data) {
+            Some(items) => is_distinct(&items.iter().collect::<Vec<_>>()),
+            None => false,
+        }
+    }
+}
+
+impl<T> Rule for DistinctBy<T>
+where
+    T: Display + Clone,
+{
+    type Message = Message;
+
+    const NAME: &'static str = "distinct";
+
+    const UNWRAP_OPTION: bool = true;
+
+    fn message(&self) -> Self::Message {
+        Message::new(MessageKind::Distinct(self.0.to_string()))
+    }
+
+    fn params(&self) -> Params {
+        vec![("key", Value::String(self.0.to_string()))]
+    }
+
+    fn call(&mut self, data: &mut Value) -> bool {
+        let key = self.0.to_string();
+        // strip the `field[*].` of full form
+        let key = match key.split_once("[*].") {
+            Some((_, key)) => FieldNames::new(key.to_string()),
+            None => FieldNames::new(key),
+        };
+        match items(data) {
+            Some(items) => {
+                let values: Vec<_> = items
+                    .iter()
+                    .filter_map(|item| item.get_with_names(&key))
+                    .collect();
+                (items.is_empty() || !values.is_empty()) && is_distinct(&values)
+            }
+            None => false,
+        }
+    }
+}
+
+impl<T> Rule for ContainsItem<T>
+where
+    T: Serialize + Clone,
+{
+    type Message = Message;
+
+    const NAME: &'static str = "contains_item";
+
+    const UNWRAP_OPTION: bool = true;
+
+    fn message(&self) -> Self::Message {
+        let item = crate::panic_on_err!(to_value(&self.0));
+        Message::new(MessageKind::ContainsItem(display(&item)))
+    }
+
+    fn params(&self) -> Params {
+        vec![("item", crate::panic_on_err!(to_value(&self.0)))]
+    }
+
+    fn call(&mut self, data: &mut Value) -> bool {
+        let item = crate::panic_on_err!(to_value(&self.0));
+        items(data).is_some_and(|items| items.contains(&item))
+    }
+}
+
+impl<T> Rule for Subset<T>
+where
+    T: Serialize + Clone,
+{
+    type Message = Message;
+
+    const NAME: &'static str = "subset";
+
+    const UNWRAP_OPTION: bool = true;
+
+    fn message(&self) -> Self::Message {
+        Message::new(MessageKind::Subset)
+    }
+
+    fn params(&self) -> Params {
+        vec![("allowed", crate::panic_on_err!(to_value(&self.0)))]
+    }
+
+    fn call(&mut self, data: &mut Value) -> bool {
+        let allowed = crate::panic_on_err!(to_value(&self.0));
+        match (items(data), items(&allowed)) {
+            (Some(items), Some(allowed)) => items.iter().all(|item| allowed.contains(item)),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check<R: Rule, T: Serialize>(mut rule: R, data: T) -> bool {
+        let mut value = to_value(data).unwrap();
+        Rule::call(&mut rule, &mut value)
+    }
+
+    #[test]
+    fn unique() {
+        assert!(check(Unique, vec![1, 2, 3]));
+        assert!(!check(Unique, vec!["a", "b", "a"]));
+        assert!(check(Unique, (1_u8, 1_u16)));
+        assert!(check(Unique, Vec::<u8>::new()));
+        assert!(!check(Unique, "abc"));
+
+        // the float wrapper is compared without `Ord`
+        assert!(check(Unique, vec![1.5, 2.5]));
+        assert!(!check(Unique, vec![1.5, 2.5, 1.5]));
+        assert!(check(Unique, vec![f64::NAN, f64::NAN]));
+        assert!(!check(Unique, vec![Some(vec![0.5]), Some(vec![0.5])]));
+    }
+
+    #[test]
+    fn distinct_by() {
+        #[derive(Serialize)]
+        struct Email {
+            address: &'static str,
+            score: f32,
+            home: Option<&'static str>,
+        }
+        let email = |address, score, home| Email {
+            address,
+            score,
+            home,
+        };
+
+        let list = || {
+            vec![
+                email("a", 1.0, Some("x")),
+                email("b", 1.0, None),
+                email("c", 2.0, None),
+            ]
+        };
+        assert!(check(DistinctBy("address"), list()));
+        assert!(!check(DistinctBy("score"), list()));
+        assert!(check(DistinctBy("home?"), list()));
+        assert!(!check(DistinctBy("home"), list()));
+        assert!(!check(DistinctBy("phone"), list()));
+        assert!(check(DistinctBy("phone"), Vec::<Email>::new()));
+        assert!(check(DistinctBy("emails[*].address"), list()));
+        assert!(!check(DistinctBy("emails[*].score"), list()));
+        assert_eq!(
+            Rule::params(&DistinctBy("address")),
+            vec![("key", Value::String("address".into()))]
+        );
+    }
+
+    #[test]
+    fn contains_and_subset() {
+        assert!(check(ContainsItem("b"), ["a", "b"]));
+        assert!(!check(ContainsItem("c"), ["a", "b"]));
+        assert!(!check(ContainsItem(1_u8), vec![1_u16]));
+        assert!(check(ContainsItem(0.5), vec![0.5, 1.5]));
+        assert_eq!(
+            ContainsItem((1, "a")).message().kind(),
+            &MessageKind::ContainsItem(r#"Tuple([Int32(1), String("a")])"#.into())
+        );
+
+        assert!(check(Subset(["a", "b", "c"]), vec!["c", "a"]));
+        assert!(check(Subset(vec![1.5]), Vec::<f64>::new()));
+        assert!(!check(Subset(["a", "b"]), vec!["a", "d"]));
+        assert!(!check(Subset("ab"), vec!["a"]));
+    }
+}
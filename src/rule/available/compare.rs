@@ -26,7 +26,7 @@
 
 use std::fmt::Display;
 
-use crate::{register::FieldNames, Rule, Value, ValueMap};
+use crate::{register::FieldNames, rule::Params, ser::to_value, Rule, Value, ValueMap};
 
 use super::{Message, MessageKind};
 
@@ -57,6 +57,9 @@ macro_rules! impl_compare {
                 // greater
                 Message::new(MessageKind::Compare($label.into(), self.0.to_string()))
             }
+            fn params_in(&self) -> Params {
+                vec![("other", Value::String(self.0.to_string()))]
+            }
         }
     };
 }
@@ -66,83 +69,44 @@ impl_compare!(Elt<T>, "less and equal");
 impl_compare!(Gt<T>, "greater");
 impl_compare!(Egt<T>, "greater and equal");
 
-impl Rule for Lt<&str> {
-    type Message = Message;
-
-    const NAME: &'static str = "lt";
-
-    fn message(&self) -> Self::Message {
-        self.message_in()
-    }
-
-    fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
-        let target = self.get_target_value(value);
-
-        value.current().unwrap() < target.unwrap()
-    }
-
-    fn call(&mut self, _value: &mut Value) -> bool {
-        unreachable!()
-    }
-}
-
-impl Rule for Elt<&str> {
-    type Message = Message;
-
-    const NAME: &'static str = "elt";
-
-    fn message(&self) -> Self::Message {
-        self.message_in()
-    }
-
-    fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
-        let target = self.get_target_value(value);
-
-        value.current().unwrap() <= target.unwrap()
-    }
-
-    fn call(&mut self, _value: &mut Value) -> bool {
-        unreachable!()
-    }
-}
-impl Rule for Gt<&str> {
-    type Message = Message;
+macro_rules! impl_field_compare {
+    ($ty:ty, $name:literal, $op:tt) => {
+        impl Rule for $ty {
+            type Message = Message;
 
-    const NAME: &'static str = "gt";
+            const NAME: &'static str = $name;
 
-    fn message(&self) -> Self::Message {
-        self.message_in()
-    }
+            fn message(&self) -> Self::Message {
+                self.message_in()
+            }
 
-    fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
-        let target = self.get_target_value(value);
+            fn params(&self) -> Params {
+                self.params_in()
+            }
 
-        value.current().unwrap() > target.unwrap()
-    }
+            fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
+                // the missing target field is failed, instead of panic
+                match (value.current(), self.get_target_value(value)) {
+                    (Some(current), Some(target)) => current $op target,
+                    _ => false,
+                }
+            }
 
-    fn call(&mut self, _value: &mut Value) -> bool {
-        unreachable!()
-    }
+            fn call(&mut self, _value: &mut Value) -> bool {
+                unreachable!()
+            }
+        }
+    };
 }
-impl Rule for Egt<&str> {
-    type Message = Message;
-
-    const NAME: &'static str = "egt";
-
-    fn message(&self) -> Self::Message {
-        self.message_in()
-    }
-
-    fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
-        let target = self.get_target_value(value);
 
-        value.current().unwrap() >= target.unwrap()
-    }
-
-    fn call(&mut self, _value: &mut Value) -> bool {
-        unreachable!()
-    }
-}
+impl_field_compare!(Lt<&str>, "lt", <);
+impl_field_compare!(Lt<String>, "lt", <);
+impl_field_compare!(Elt<&str>, "elt", <=);
+impl_field_compare!(Elt<String>, "elt", <=);
+impl_field_compare!(Gt<&str>, "gt", >);
+impl_field_compare!(Gt<String>, "gt", >);
+impl_field_compare!(Egt<&str>, "egt", >=);
+impl_field_compare!(Egt<String>, "egt", >=);
 
 macro_rules! impl_lt_num {
     ($ty:ty) => {
@@ -155,6 +119,10 @@ macro_rules! impl_lt_num {
                 self.message_in()
             }
 
+            fn params(&self) -> Params {
+                vec![("value", crate::panic_on_err!(to_value(self.0)))]
+            }
+
             fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
                 value.current().unwrap() < self.0
             }
@@ -172,6 +140,8 @@ impl_lt_num!(Lt<u16>);
 impl_lt_num!(Lt<i16>);
 impl_lt_num!(Lt<u32>);
 impl_lt_num!(Lt<i32>);
+impl_lt_num!(Lt<u64>);
+impl_lt_num!(Lt<i64>);
 
 macro_rules! impl_elt_num {
     ($ty:ty) => {
@@ -184,6 +154,10 @@ macro_rules! impl_elt_num {
                 self.message_in()
             }
 
+            fn params(&self) -> Params {
+                vec![("value", crate::panic_on_err!(to_value(self.0)))]
+            }
+
             fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
                 value.current().unwrap() <= self.0
             }
@@ -201,6 +175,8 @@ impl_elt_num!(Elt<u16>);
 impl_elt_num!(Elt<i16>);
 impl_elt_num!(Elt<u32>);
 impl_elt_num!(Elt<i32>);
+impl_elt_num!(Elt<u64>);
+impl_elt_num!(Elt<i64>);
 
 macro_rules! impl_gt_num {
     ($ty:ty) => {
@@ -213,6 +189,10 @@ macro_rules! impl_gt_num {
                 self.message_in()
             }
 
+            fn params(&self) -> Params {
+                vec![("value", crate::panic_on_err!(to_value(self.0)))]
+            }
+
             fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
                 value.current().unwrap() > self.0
             }
@@ -229,6 +209,8 @@ impl_gt_num!(Gt<u16>);
 impl_gt_num!(Gt<i16>);
 impl_gt_num!(Gt<u32>);
 impl_gt_num!(Gt<i32>);
+impl_gt_num!(Gt<u64>);
+impl_gt_num!(Gt<i64>);
 
 macro_rules! impl_egt_num {
     ($ty:ty) => {
@@ -241,6 +223,10 @@ macro_rules! impl_egt_num {
                 self.message_in()
             }
 
+            fn params(&self) -> Params {
+                vec![("value", crate::panic_on_err!(to_value(self.0)))]
+            }
+
             fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
                 value.current().unwrap() >= self.0
             }
@@ -258,3 +244,5 @@ impl_egt_num!(Egt<u16>);
 impl_egt_num!(Egt<i16>);
 impl_egt_num!(Egt<u32>);
 impl_egt_num!(Egt<i32>);
+impl_egt_num!(Egt<u64>);
+impl_egt_num!(Egt<i64>);
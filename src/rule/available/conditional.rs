@@ -0,0 +1,163 @@
+//! Conditional required rules, the `Required` rule is only run when the
+//! condition about other fields is matched, or else it is skipped without message.
+//!
+//! - [`required_if`]: required when other field is equal to the value
+//! - [`required_unless`]: required unless other field is equal to the value
+//! - [`required_with`]: required when other field is present and not empty
+//!
+//! The value is compared after serialized, so it should have the same type as the field.
+//!
+//! For other conditions, use [`RuleExt::when`] or [`RuleList::when`].
+//!
+//! # Examples
+//! ```
+//! # use serde::Serialize;
+//! # use valitron::{available::{required_if, MessageKind}, Validatable, Validator};
+//! #[derive(Serialize, Debug)]
+//! struct Account {
+//!     account_type: String,
+//!     company_name: String,
+//! }
+//!
+//! let validator = Validator::new().rule("company_name", required_if("account_type", "business"));
+//!
+//! let input = Account {
+//!     account_type: "personal".into(),
+//!     company_name: String::default(),
+//! };
+//! input.validate(&validator).unwrap();
+//!
+//! let input = Account {
+//!     account_type: "business".into(),
+//!     company_name: String::default(),
+//! };
+//! let err = input
+//!     .validate(&validator)
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
+//! assert!(matches!(
+//!     err.get("company_name").unwrap()[0].kind(),
+//!     MessageKind::Required
+//! ));
+//! ```
+//!
+//! [`RuleExt::when`]: crate::RuleExt::when
+//! [`RuleList::when`]: crate::rule::RuleList::when
+
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::{
+    register::FieldNames,
+    rule::{RuleExt, RuleList},
+    ser::to_value,
+    Value, ValueMap,
+};
+
+use super::{required::filled, Message, Required};
+
+/// the field is required when the `field` is equal to the `value`
+pub fn required_if<T, V>(field: T, value: V) -> RuleList<ValueMap, Message>
+where
+    T: Display,
+    V: Serialize,
+{
+    let field = FieldNames::new(field.to_string());
+    let value = crate::panic_on_err!(to_value(value));
+
+    Required.when(move |data: &ValueMap| data.get(&field) == Some(&value))
+}
+
+/// the field is required unless the `field` is equal to the `value`
+pub fn required_unless<T, V>(field: T, value: V) -> RuleList<ValueMap, Message>
+where
+    T: Display,
+    V: Serialize,
+{
+    let field = FieldNames::new(field.to_string());
+    let value = crate::panic_on_err!(to_value(value));
+
+    Required.when(move |data: &ValueMap| data.get(&field) != Some(&value))
+}
+
+/// the field is required when the `field` is present and not empty
+pub fn required_with<T>(field: T) -> RuleList<ValueMap, Message>
+where
+    T: Display,
+{
+    let field = FieldNames::new(field.to_string());
+
+    Required.when(move |data: &ValueMap| data.get(&field).is_some_and(present))
+}
+
+pub(crate) fn present(value: &Value) -> bool {
+    !matches!(value, Value::Unit) && filled(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::{required_if, required_unless, required_with};
+    use crate::{available::MessageKind, Validator};
+
+    #[derive(Serialize)]
+    struct Account {
+        kind: &'static str,
+        company: &'static str,
+        phone: Option<&'static str>,
+        code: &'static str,
+    }
+
+    fn account(kind: &'static str, phone: Option<&'static str>) -> Account {
+        Account {
+            kind,
+            company: "",
+            phone,
+            code: "",
+        }
+    }
+
+    #[test]
+    fn test_required_if() {
+        let validator = Validator::new().rule("company", required_if("kind", "business"));
+
+        validator.validate(account("personal", None)).unwrap();
+
+        let err = validator
+            .validate(account("business", None))
+            .unwrap_err()
+            .into_invalid()
+            .unwrap();
+        assert_eq!(err.len(), 1);
+        assert_eq!(
+            err.get("company").unwrap()[0].kind(),
+            &MessageKind::Required
+        );
+    }
+
+    #[test]
+    fn test_required_unless() {
+        let validator = Validator::new().rule("company", required_unless("kind", "personal"));
+
+        validator.validate(account("personal", None)).unwrap();
+        assert!(validator.validate(account("business", None)).is_err());
+    }
+
+    #[test]
+    fn test_required_with() {
+        let validator = Validator::new().rule("code", required_with("phone"));
+
+        validator.validate(account("personal", None)).unwrap();
+        validator.validate(account("personal", Some(""))).unwrap();
+
+        let err = validator
+            .validate(account("personal", Some("123")))
+            .unwrap_err()
+            .into_invalid()
+            .unwrap();
+        assert_eq!(err.get("code").unwrap()[0].kind(), &MessageKind::Required);
+    }
+}
@@ -17,7 +17,9 @@
 //!
 //! let err = input
 //!     .validate(Validator::new().rule("confirm_password", Confirm("password")))
-//!     .unwrap_err();
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
 //! assert!(matches!(
 //!     err.get("confirm_password").unwrap()[0].kind(),
 //!     MessageKind::Confirm(_)
@@ -35,7 +37,7 @@
 
 use std::fmt::{Debug, Display};
 
-use crate::{register::FieldNames, value::ValueMap, Rule, Value};
+use crate::{register::FieldNames, rule::Params, value::ValueMap, Rule, Value};
 
 use super::{Message, MessageKind};
 
@@ -81,6 +83,10 @@ where
     fn message_in(&self) -> Message {
         Message::new(MessageKind::Confirm(self.0.to_string()))
     }
+
+    fn params_in(&self) -> Params {
+        vec![("other", Value::String(self.0.to_string()))]
+    }
 }
 
 impl Rule for Confirm<String> {
@@ -92,10 +98,16 @@ impl Rule for Confirm<String> {
         self.message_in()
     }
 
-    fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
-        let target = self.get_target_value(value);
+    fn params(&self) -> Params {
+        self.params_in()
+    }
 
-        value.current().unwrap() == target.unwrap()
+    fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
+        // the missing target field is failed, instead of panic
+        match (value.current(), self.get_target_value(value)) {
+            (Some(current), Some(target)) => current == target,
+            _ => false,
+        }
     }
 
     fn call(&mut self, _value: &mut Value) -> bool {
@@ -112,10 +124,16 @@ impl Rule for Confirm<&str> {
         self.message_in()
     }
 
-    fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
-        let target = self.get_target_value(value);
+    fn params(&self) -> Params {
+        self.params_in()
+    }
 
-        value.current().unwrap() == target.unwrap()
+    fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
+        // the missing target field is failed, instead of panic
+        match (value.current(), self.get_target_value(value)) {
+            (Some(current), Some(target)) => current == target,
+            _ => false,
+        }
     }
 
     fn call(&mut self, _value: &mut Value) -> bool {
@@ -171,6 +189,7 @@ mod tests {
     use crate::{register::FieldNames, rule::Rule, ser::to_value, Value, ValueMap};
 
     #[test]
+    #[allow(clippy::bool_comparison)]
     fn test_confirm() {
         #[derive(Serialize)]
         struct MyType {
@@ -191,5 +210,8 @@ mod tests {
         map.index(FieldNames::new("other_name".to_string()));
         let res = confirm.call_with_relate(&mut map);
         assert!(res == false);
+
+        let mut confirm = Confirm("password");
+        assert!(!confirm.call_with_relate(&mut map));
     }
 }
@@ -15,7 +15,9 @@
 //! };
 //! let err = input
 //!     .validate(Validator::new().rule("email", Contains('@')))
-//!     .unwrap_err();
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
 //!
 //! assert!(matches!(
 //!     err.get("email").unwrap()[0].kind(),
@@ -43,7 +45,10 @@
 
 use std::fmt::{Debug, Display};
 
-use crate::{rule::string::StringRule, Rule, Value};
+use crate::{
+    rule::{string::StringRule, Params},
+    Rule, Value,
+};
 
 use super::Message;
 
@@ -81,6 +86,10 @@ where
     fn message_in(&self) -> Message {
         Message::new(super::MessageKind::Contains(self.0.to_string()))
     }
+
+    fn params_in(&self) -> Params {
+        vec![("text", Value::String(self.0.to_string()))]
+    }
 }
 
 impl Rule for Contains<&str> {
@@ -88,10 +97,16 @@ impl Rule for Contains<&str> {
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         self.message_in()
     }
 
+    fn params(&self) -> Params {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.contains(self.0),
@@ -105,10 +120,16 @@ impl Rule for Contains<String> {
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         self.message_in()
     }
 
+    fn params(&self) -> Params {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.contains(&self.0),
@@ -122,10 +143,16 @@ impl Rule for Contains<char> {
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         self.message_in()
     }
 
+    fn params(&self) -> Params {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.contains(self.0),
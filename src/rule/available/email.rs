@@ -19,7 +19,9 @@
 //!         Validator::new()
 //!             .rule("email", Email)
 //!     )
-//!     .unwrap_err();
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
 //!
 //! assert!(matches!(
 //!     err.get("email").unwrap()[0].kind(),
@@ -55,6 +57,8 @@ impl Rule for Email {
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         Message::new(super::MessageKind::Email)
     }
@@ -54,6 +54,7 @@ impl<'a> Cursor<'a> {
         }
     }
 
+    #[allow(clippy::needless_return)]
     fn advance(&mut self) -> Option<EmailToken> {
         if self.is_idna_domain || self.is_ip {
             return None;
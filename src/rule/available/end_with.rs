@@ -15,7 +15,9 @@
 //! };
 //! let err = input
 //!     .validate(Validator::new().rule("email", EndsWith("gmail.com")))
-//!     .unwrap_err();
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
 //!
 //! assert!(matches!(
 //!     err.get("email").unwrap()[0].kind(),
@@ -32,7 +34,10 @@
 
 use std::fmt::{Debug, Display};
 
-use crate::{rule::string::StringRule, Rule, Value};
+use crate::{
+    rule::{string::StringRule, Params},
+    Rule, Value,
+};
 
 use super::Message;
 
@@ -70,6 +75,10 @@ where
     fn message_in(&self) -> Message {
         Message::new(super::MessageKind::EndsWith(self.0.to_string()))
     }
+
+    fn params_in(&self) -> Params {
+        vec![("suffix", Value::String(self.0.to_string()))]
+    }
 }
 
 impl Rule for EndsWith<&str> {
@@ -77,10 +86,16 @@ impl Rule for EndsWith<&str> {
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         self.message_in()
     }
 
+    fn params(&self) -> Params {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.ends_with(self.0),
@@ -94,10 +109,16 @@ impl Rule for EndsWith<String> {
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         self.message_in()
     }
 
+    fn params(&self) -> Params {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.ends_with(&self.0),
@@ -111,10 +132,16 @@ impl Rule for EndsWith<char> {
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         self.message_in()
     }
 
+    fn params(&self) -> Params {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.ends_with(self.0),
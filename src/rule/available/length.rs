@@ -20,7 +20,9 @@
 //!             .rule("title", Length(30..40))
 //!             .rule("fruit", Length::eq(4)),
 //!     )
-//!     .unwrap_err();
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
 //!
 //! assert!(matches!(
 //!     err.get("title").unwrap()[0].kind(),
@@ -54,9 +56,12 @@
 
 use std::{fmt::Debug, ops::RangeBounds};
 
-use crate::{rule::string::StringRule, Rule, Value};
+use crate::{
+    rule::{string::StringRule, Params},
+    Rule, Value,
+};
 
-use super::Message;
+use super::{range_params, Message};
 
 #[derive(Clone)]
 pub struct Length<T>(pub T);
@@ -96,9 +101,15 @@ where
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         self.message_in()
     }
+
+    fn params(&self) -> Params {
+        range_params(&self.0)
+    }
     fn call(&mut self, data: &mut Value) -> bool {
         match data {
             Value::String(str) => self.0.contains(&str.len()),
@@ -145,10 +156,17 @@ impl Rule for Length<Num> {
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         self.message_in()
     }
 
+    fn params(&self) -> Params {
+        let Num(length) = self.0;
+        range_params(&(length..=length))
+    }
+
     fn call(&mut self, data: &mut Value) -> bool {
         match data {
             Value::String(str) => self.0 == str.len(),
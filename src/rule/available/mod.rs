@@ -1,33 +1,52 @@
 //! available rules collection
 
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    ops::{Bound, RangeBounds},
+};
 
 use serde::Serialize;
 
+use crate::{rule::Params, ser::to_value};
+
+pub mod coerce;
+pub mod collection;
 pub mod compare;
+pub mod conditional;
 pub mod confirm;
 pub mod contains;
 pub mod email;
 pub mod end_with;
 pub mod length;
 pub mod not;
+pub mod nullable;
+pub mod parser;
 pub mod range;
 pub mod regex;
 pub mod required;
 pub mod start_with;
+pub mod transform;
 pub mod trim;
 
+pub use coerce::{ToBool, ToChar, ToFloat, ToInt};
+pub use collection::{ContainsItem, DistinctBy, Subset, Unique};
 pub use compare::{Egt, Elt, Gt, Lt};
+pub use conditional::{required_if, required_unless, required_with};
 pub use confirm::Confirm;
 pub use contains::Contains;
 pub use email::Email;
 pub use end_with::EndsWith;
 pub use length::Length;
 pub use not::Not;
+pub use nullable::Nullable;
 pub use range::Range;
 pub use regex::Regex;
 pub use required::Required;
 pub use start_with::StartWith;
+pub use transform::{
+    Clamp, CollapseWhitespace, DefaultIfEmpty, Lowercase, NormalizeNewlines, Round,
+    StripControlChars, Truncate, Uppercase,
+};
 pub use trim::Trim;
 
 /// Error message, it is returned when build-in rules validate fail
@@ -62,6 +81,12 @@ pub enum MessageKind {
     /// as trim rule, this is unreachable, only mark
     Trim,
 
+    /// as nullable rule, this is unreachable, only mark
+    Nullable,
+
+    /// as transform rules, e.g. `Lowercase`, this is unreachable, only mark
+    Transform,
+
     /// as range rule
     Range,
 
@@ -71,6 +96,21 @@ pub enum MessageKind {
     /// as regex rule
     Regex,
 
+    /// as coercion rules, e.g. `ToInt`, the argument is the expected type
+    Type(String),
+
+    /// as unique rule
+    Unique,
+
+    /// as distinct rule, only one argument is the path of items
+    Distinct(String),
+
+    /// as contains_item rule, only one argument is the item
+    ContainsItem(String),
+
+    /// as subset rule
+    Subset,
+
     /// other way, it used by other type converting Message stopover
     Fallback(String),
 }
@@ -90,9 +130,16 @@ impl Serialize for MessageKind {
             MessageKind::EndsWith(_) => serializer.serialize_str("end_with"),
             MessageKind::Contains(_) => serializer.serialize_str("contains"),
             MessageKind::Trim => serializer.serialize_str("trim"),
+            MessageKind::Nullable => serializer.serialize_str("nullable"),
+            MessageKind::Transform => serializer.serialize_str("transform"),
             MessageKind::Email => serializer.serialize_str("email"),
             MessageKind::Fallback(s) => serializer.serialize_str(s),
             MessageKind::Regex => serializer.serialize_str("regex"),
+            MessageKind::Type(_) => serializer.serialize_str("type"),
+            MessageKind::Unique => serializer.serialize_str("unique"),
+            MessageKind::Distinct(_) => serializer.serialize_str("distinct"),
+            MessageKind::ContainsItem(_) => serializer.serialize_str("contains_item"),
+            MessageKind::Subset => serializer.serialize_str("subset"),
         }
     }
 }
@@ -154,16 +201,50 @@ impl Display for MessageKind {
             MessageKind::StartWith(str) => write!(f, "this field must be start with `{}`", str),
             MessageKind::EndsWith(str) => write!(f, "this field must be end with `{}`", str),
             MessageKind::Contains(str) => write!(f, "this field must be contain `{}`", str),
-            MessageKind::Trim => unreachable!(),
+            MessageKind::Trim | MessageKind::Nullable | MessageKind::Transform => {
+                unreachable!()
+            }
             MessageKind::Range => "the value not in the range".fmt(f),
             MessageKind::Length => "the value's length not in the range".fmt(f),
             MessageKind::Email => "the value is not a email address".fmt(f),
             MessageKind::Fallback(s) => s.fmt(f),
             MessageKind::Regex => "regular matching failed".fmt(f),
+            MessageKind::Type(ty) => write!(f, "the value can not be converted to {}", ty),
+            MessageKind::Unique => "the items must be unique".fmt(f),
+            MessageKind::Distinct(key) => write!(f, "the `{}` of items must be unique", key),
+            MessageKind::ContainsItem(item) => write!(f, "this field must contain `{}`", item),
+            MessageKind::Subset => "the items must be in the allowed list".fmt(f),
         }
     }
 }
 
+/// the parameters of range bounds, e.g. `("min", 6)`, `("exclusive_max", 12)`,
+/// the unbounded side is omitted
+pub(crate) fn range_params<R, T>(range: &R) -> Params
+where
+    R: RangeBounds<T>,
+    T: Serialize,
+{
+    let mut params = Params::new();
+    match range.start_bound() {
+        Bound::Included(min) => params.push(("min", crate::panic_on_err!(to_value(min)))),
+        Bound::Excluded(min) => params.push(("exclusive_min", crate::panic_on_err!(to_value(min)))),
+        Bound::Unbounded => (),
+    }
+    match range.end_bound() {
+        Bound::Included(max) => params.push(("max", crate::panic_on_err!(to_value(max)))),
+        Bound::Excluded(max) => params.push(("exclusive_max", crate::panic_on_err!(to_value(max)))),
+        Bound::Unbounded => (),
+    }
+    params
+}
+
+impl crate::register::RequiredMessage for Message {
+    fn required_message() -> Self {
+        Message::new(MessageKind::Required)
+    }
+}
+
 impl PartialEq<Message> for String {
     fn eq(&self, other: &Message) -> bool {
         self == &other.to_string()
@@ -23,7 +23,9 @@
 //! };
 //! let err = input
 //!     .validate(Validator::new().rule("email", Not(Contains("example.com"))))
-//!     .unwrap_err();
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
 //!
 //! assert!(matches!(
 //!     err.get("email").unwrap()[0].kind(),
@@ -34,7 +36,10 @@
 use core::fmt;
 use std::fmt::Debug;
 
-use crate::{rule::string::StringRule, Rule, Value};
+use crate::{
+    rule::{string::StringRule, Params},
+    Rule, Value, ValueMap,
+};
 
 #[derive(Clone)]
 pub struct Not<T>(pub T);
@@ -56,6 +61,16 @@ impl<T: Rule> Rule for Not<T> {
         self.0.message()
     }
 
+    fn params(&self) -> Params {
+        let mut params = self.0.params();
+        params.push(("not", Value::Boolean(true)));
+        params
+    }
+
+    fn call_with_relate(&mut self, data: &mut ValueMap) -> bool {
+        !self.0.call_with_relate(data)
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         !self.0.call(value)
     }
@@ -0,0 +1,52 @@
+//! Allow the `Option` field to be `None`, and the other rules are only applied to the inner
+//! value of `Some`, this alway return true
+//!
+//! when the field is `None`, all rules of the field are skipped, including [`Required`].
+//!
+//! # Examples
+//! ```
+//! # use serde::Serialize;
+//! # use valitron::{available::{Length, Nullable}, RuleExt, Validatable, Validator};
+//! #[derive(Serialize, Debug)]
+//! struct Input {
+//!     nickname: Option<String>,
+//! }
+//!
+//! let validator = Validator::new().rule("nickname", Nullable.and(Length(2..)));
+//!
+//! Input { nickname: None }.validate(validator.clone()).unwrap();
+//! Input { nickname: Some("foo".into()) }.validate(validator.clone()).unwrap();
+//!
+//! let err = Input { nickname: Some("f".into()) }
+//!     .validate(validator)
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
+//! assert!(err.get("nickname").is_some());
+//! ```
+//!
+//! [`Required`]: super::Required
+
+use crate::{Rule, Value};
+
+use super::Message;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Nullable;
+
+/// all rules of the field are skipped when it is `None` and this rule is registered
+pub(crate) const NAME: &str = "nullable";
+
+impl Rule for Nullable {
+    type Message = Message;
+
+    const NAME: &'static str = NAME;
+
+    fn message(&self) -> Self::Message {
+        Message::new(super::MessageKind::Nullable)
+    }
+
+    fn call(&mut self, _: &mut Value) -> bool {
+        true
+    }
+}
@@ -0,0 +1,567 @@
+//! Parse Laravel-style rule string, e.g. `"required|length:6,12|email"`, into rules.
+//!
+//! The rules are separated by `|`, and the arguments are separated from the rule name by `:`,
+//! and separated from each other by `,`.
+//!
+//! | rule string | rule |
+//! | --- | --- |
+//! | `required` | [`Required`] |
+//! | `nullable` | [`Nullable`] |
+//! | `email` | [`Email`] |
+//! | `trim` | [`Trim`] |
+//! | `length:6` | `Length::eq(6)` |
+//! | `length:6,12` / `length:6,` / `length:,12` | `Length(6..=12)` / `Length(6..)` / `Length(..=12)` |
+//! | `range:1,10` / `range:1,` / `range:,10` | the number is in `1..=10` / `1..` / `..=10`, any number type is supported |
+//! | `confirm:password` | `Confirm("password")` |
+//! | `start_with:foo` | `StartWith("foo")` |
+//! | `end_with:foo` | `EndsWith("foo")` |
+//! | `contains:foo` | `Contains("foo")` |
+//! | `regex:^[a-z]+$` | `Regex::new("^[a-z]+$")`, the pattern can not contain `|`, and it is compiled once when parsing |
+//!
+//! Other rule names are looked up in the registry of [`RuleParser`].
+//!
+//! # Examples
+//! ```
+//! # use serde::Serialize;
+//! # use valitron::{available::{parser::RuleParser, Message}, custom, Validator};
+//! #[derive(Serialize)]
+//! struct Input {
+//!     name: String,
+//!     age: u8,
+//! }
+//!
+//! fn even(age: &mut u8) -> Result<(), Message> {
+//!     if *age % 2 == 0 {
+//!         Ok(())
+//!     } else {
+//!         Err("age should be even".into())
+//!     }
+//! }
+//!
+//! let parser = RuleParser::new().register("even", custom(even));
+//!
+//! let validator = Validator::new()
+//!     .rule("name", parser.parse("required|length:3,8").unwrap())
+//!     .rule("age", parser.parse("range:18,60|even").unwrap());
+//!
+//! assert!(validator.validate(Input { name: "Jone".into(), age: 20 }).is_ok());
+//!
+//! let err = validator
+//!     .validate(Input { name: "Jo".into(), age: 21 })
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
+//! assert_eq!(err.len(), 2);
+//!
+//! let err = parser.parse("required|lenght:3").err().unwrap();
+//! assert_eq!(err.to_string(), "unknown rule `lenght` at position 9");
+//! ```
+
+use std::{cmp::Ordering, collections::HashMap, error::Error, fmt::Display, sync::Arc};
+
+use crate::{
+    rule::{IntoRuleList, Params, RuleList},
+    Rule, Value, ValueMap,
+};
+
+use super::{
+    Confirm, Contains, Email, EndsWith, Length, Message, MessageKind, Nullable, Regex, Required,
+    StartWith, Trim,
+};
+
+/// the rule factory of registry, it receives the arguments of the rule
+type Factory = dyn Fn(&[&str]) -> Result<RuleList<ValueMap, Message>, String> + Send + Sync;
+
+/// parse rule string with build-in rules, see [module](self) document
+pub fn parse(source: &str) -> Result<RuleList<ValueMap, Message>, ParseError> {
+    RuleParser::new().parse(source)
+}
+
+/// Parser of rule string, it supports build-in rules and the registered rules.
+#[derive(Clone, Default)]
+pub struct RuleParser {
+    registry: HashMap<String, Arc<Factory>>,
+}
+
+impl RuleParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// register rule without arguments, the name is used in rule string,
+    /// the build-in rule with same name is covered.
+    pub fn register<N, R>(self, name: N, rule: R) -> Self
+    where
+        N: Into<String>,
+        R: IntoRuleList<ValueMap, Message>,
+    {
+        let list = rule.into_list();
+        self.register_with(name, move |args: &[&str]| {
+            if args.is_empty() {
+                Ok(list.clone())
+            } else {
+                Err("the rule has no arguments".to_owned())
+            }
+        })
+    }
+
+    /// register rule with arguments, e.g. `in:foo,bar`, the factory receives the arguments
+    /// and returns the rules, or the reason of invalid arguments.
+    pub fn register_with<N, F>(mut self, name: N, factory: F) -> Self
+    where
+        N: Into<String>,
+        F: Fn(&[&str]) -> Result<RuleList<ValueMap, Message>, String> + Send + Sync + 'static,
+    {
+        self.registry.insert(name.into(), Arc::new(factory));
+        self
+    }
+
+    /// build the registered rule with arguments, `None` when the name is not registered
+    pub(crate) fn registered(
+        &self,
+        name: &str,
+        args: &[&str],
+    ) -> Option<Result<RuleList<ValueMap, Message>, String>> {
+        self.registry.get(name).map(|factory| factory(args))
+    }
+
+    /// parse rule string into rules
+    pub fn parse(&self, source: &str) -> Result<RuleList<ValueMap, Message>, ParseError> {
+        let mut list = RuleList::default();
+        let mut position = 0;
+
+        for segment in source.split('|') {
+            let start = position + segment.find(|c: char| !c.is_whitespace()).unwrap_or(0);
+            position += segment.len() + 1;
+
+            let segment = segment.trim();
+            let (name, args) = match segment.split_once(':') {
+                Some((name, args)) => (name.trim_end(), args.split(',').collect()),
+                None => (segment, Vec::new()),
+            };
+            if name.is_empty() {
+                return Err(ParseError::new(start, ParseErrorKind::EmptyRule));
+            }
+
+            let mut rules = self
+                .rule(name, &args)
+                .map_err(|kind| ParseError::new(start, kind))?;
+            list.merge(&mut rules);
+        }
+
+        Ok(list)
+    }
+
+    fn rule(
+        &self,
+        name: &str,
+        args: &[&str],
+    ) -> Result<RuleList<ValueMap, Message>, ParseErrorKind> {
+        let invalid = |reason: &str| ParseErrorKind::InvalidArgument {
+            rule: name.to_owned(),
+            reason: reason.to_owned(),
+        };
+
+        if let Some(res) = self.registered(name, args) {
+            return res.map_err(|reason| invalid(&reason));
+        }
+
+        let list = match (name, args) {
+            ("required", []) => Required.into_list(),
+            ("nullable", []) => Nullable.into_list(),
+            ("email", []) => Email.into_list(),
+            ("trim", []) => Trim.into_list(),
+            ("length", [length]) => Length::eq(parse_num(length).map_err(invalid)?).into_list(),
+            ("length", [min, max]) => {
+                let min: Option<usize> = parse_bound(min).map_err(invalid)?;
+                let max: Option<usize> = parse_bound(max).map_err(invalid)?;
+                match (min, max) {
+                    (Some(min), Some(max)) => Length(min..=max).into_list(),
+                    (Some(min), None) => Length(min..).into_list(),
+                    (None, Some(max)) => Length(..=max).into_list(),
+                    (None, None) => return Err(invalid("the length need a bound")),
+                }
+            }
+            ("range", [min, max]) => {
+                let range = NumRange {
+                    min: parse_bound(min).map_err(invalid)?,
+                    max: parse_bound(max).map_err(invalid)?,
+                };
+                if range.min.is_none() && range.max.is_none() {
+                    return Err(invalid("the range need a bound"));
+                }
+                range.into_list()
+            }
+            ("confirm", [other]) => Confirm(other.to_string()).into_list(),
+            ("start_with", [text]) => StartWith(text.to_string()).into_list(),
+            ("end_with", [text]) => EndsWith(text.to_string()).into_list(),
+            ("contains", [text]) => Contains(text.to_string()).into_list(),
+            // the pattern may contain `,`
+            ("regex", [_, ..]) => {
+                let pattern = args.join(",");
+                match Regex::try_new(pattern) {
+                    Ok(regex) => regex.into_list(),
+                    Err(err) => return Err(invalid(&err.to_string())),
+                }
+            }
+            (
+                "required" | "nullable" | "email" | "trim" | "length" | "range" | "confirm"
+                | "start_with" | "end_with" | "contains" | "regex",
+                _,
+            ) => return Err(invalid("the number of arguments is not matched")),
+            _ => {
+                return Err(ParseErrorKind::UnknownRule {
+                    name: name.to_owned(),
+                })
+            }
+        };
+
+        Ok(list)
+    }
+}
+
+fn parse_num<N: std::str::FromStr>(arg: &str) -> Result<N, &'static str> {
+    arg.trim()
+        .parse()
+        .map_err(|_| "the argument is not a number")
+}
+
+/// the empty argument is unbounded
+fn parse_bound<N: std::str::FromStr>(arg: &str) -> Result<Option<N>, &'static str> {
+    if arg.trim().is_empty() {
+        Ok(None)
+    } else {
+        parse_num(arg).map(Some)
+    }
+}
+
+/// the error of parsing rule string, it contains the byte position of the rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    position: usize,
+    kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// the rule name is empty, e.g. `required||email`
+    EmptyRule,
+
+    /// the rule name is neither build-in nor registered
+    UnknownRule { name: String },
+
+    /// the arguments of rule is invalid
+    InvalidArgument { rule: String, reason: String },
+}
+
+impl ParseError {
+    fn new(position: usize, kind: ParseErrorKind) -> Self {
+        Self { position, kind }
+    }
+
+    /// the byte position of the invalid rule in the rule string
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let position = self.position;
+        match &self.kind {
+            ParseErrorKind::EmptyRule => write!(f, "empty rule at position {position}"),
+            ParseErrorKind::UnknownRule { name } => {
+                write!(f, "unknown rule `{name}` at position {position}")
+            }
+            ParseErrorKind::InvalidArgument { rule, reason } => {
+                write!(
+                    f,
+                    "invalid arguments of rule `{rule}` at position {position}: {reason}"
+                )
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// range rule of any number type, the number types are unknown in rule string
+#[derive(Clone, Debug)]
+pub(crate) struct NumRange {
+    pub(crate) min: Option<Num>,
+    pub(crate) max: Option<Num>,
+}
+
+impl Rule for NumRange {
+    type Message = Message;
+
+    const NAME: &'static str = "range";
+
+    const UNWRAP_OPTION: bool = true;
+
+    fn message(&self) -> Self::Message {
+        Message::new(MessageKind::Range)
+    }
+
+    fn params(&self) -> Params {
+        let bound = |name, num: Option<Num>| num.map(|num| (name, num.into_value()));
+        bound("min", self.min)
+            .into_iter()
+            .chain(bound("max", self.max))
+            .collect()
+    }
+
+    fn call(&mut self, data: &mut Value) -> bool {
+        let Some(num) = as_num(data) else {
+            return false;
+        };
+        let ge_min = match self.min {
+            Some(min) => num >= min,
+            None => true,
+        };
+        let le_max = match self.max {
+            Some(max) => num <= max,
+            None => true,
+        };
+        ge_min && le_max
+    }
+}
+
+/// the number of unknown type, integers are compared as integers, so that the integers
+/// above 2^53 do not lose precision
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Num {
+    Int(i128),
+    Float(f64),
+}
+
+impl Num {
+    fn into_value(self) -> Value {
+        match self {
+            Num::Int(n) => match i64::try_from(n) {
+                Ok(n) if n < 0 => Value::Int64(n),
+                _ => u64::try_from(n)
+                    .map(Value::Uint64)
+                    .unwrap_or(Value::Float64((n as f64).into())),
+            },
+            Num::Float(n) => Value::Float64(n.into()),
+        }
+    }
+}
+
+impl std::str::FromStr for Num {
+    type Err = std::num::ParseFloatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<i128>() {
+            Ok(n) => Ok(Num::Int(n)),
+            Err(_) => s.parse().map(Num::Float),
+        }
+    }
+}
+
+impl PartialEq for Num {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Num {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Num::Int(a), Num::Int(b)) => Some(a.cmp(b)),
+            (Num::Float(a), Num::Float(b)) => a.partial_cmp(b),
+            (Num::Int(a), Num::Float(b)) => cmp_int_float(*a, *b),
+            (Num::Float(a), Num::Int(b)) => cmp_int_float(*b, *a).map(Ordering::reverse),
+        }
+    }
+}
+
+/// compare without casting the integer to float
+fn cmp_int_float(int: i128, float: f64) -> Option<Ordering> {
+    // 2^127, the float out of it is out of the i128 range
+    const LIMIT: f64 = 170141183460469231731687303715884105728.0;
+    if float.is_nan() {
+        None
+    } else if float >= LIMIT {
+        Some(Ordering::Less)
+    } else if float < -LIMIT {
+        Some(Ordering::Greater)
+    } else {
+        let trunc = float.trunc();
+        Some(
+            int.cmp(&(trunc as i128))
+                .then_with(|| 0.0.partial_cmp(&(float - trunc)).unwrap_or(Ordering::Equal)),
+        )
+    }
+}
+
+pub(crate) fn as_num(value: &Value) -> Option<Num> {
+    let num = match value {
+        Value::Uint8(n) => Num::Int(*n as i128),
+        Value::Int8(n) => Num::Int(*n as i128),
+        Value::Uint16(n) => Num::Int(*n as i128),
+        Value::Int16(n) => Num::Int(*n as i128),
+        Value::Uint32(n) => Num::Int(*n as i128),
+        Value::Int32(n) => Num::Int(*n as i128),
+        Value::Uint64(n) => Num::Int(*n as i128),
+        Value::Int64(n) => Num::Int(*n as i128),
+        Value::Float32(n) => Num::Float(n.get() as f64),
+        Value::Float64(n) => Num::Float(n.get()),
+        _ => return None,
+    };
+    Some(num)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::{parse, parse_bound, NumRange, ParseErrorKind, RuleParser};
+    use crate::{available::MessageKind, custom, Rule, Validator, Value};
+
+    #[derive(Serialize)]
+    struct Input {
+        name: String,
+        password: String,
+        password_confirm: String,
+        score: f32,
+    }
+
+    fn input(name: &str, score: f32) -> Input {
+        Input {
+            name: name.into(),
+            password: "foo".into(),
+            password_confirm: "foo".into(),
+            score,
+        }
+    }
+
+    #[test]
+    fn test_build_in() {
+        let validator = Validator::new()
+            .rule(
+                "name",
+                parse("trim | required|length:3,8|start_with:J").unwrap(),
+            )
+            .rule("password_confirm", parse("confirm:password").unwrap())
+            .rule("score", parse("range:0,100.5").unwrap());
+
+        validator.validate(input("Jone", 99.5)).unwrap();
+
+        let err = validator
+            .validate(input("Jo", 101.0))
+            .unwrap_err()
+            .into_invalid()
+            .unwrap();
+        assert_eq!(err.get("name").unwrap()[0].kind(), &MessageKind::Length);
+        assert_eq!(err.get("score").unwrap()[0].kind(), &MessageKind::Range);
+
+        let (_, rules) = validator.rules().next().unwrap();
+        let names: Vec<_> = rules.iter().map(|info| info.name()).collect();
+        assert_eq!(names, ["trim", "required", "length", "start_with"]);
+
+        let list = parse("regex:^[a-z]{1,3}$").unwrap();
+        let validator = Validator::new().rule("name", list);
+        let (_, rules) = validator.rules().next().unwrap();
+        assert_eq!(
+            rules[0].param("pattern"),
+            Some(&Value::String("^[a-z]{1,3}$".into()))
+        );
+    }
+
+    #[test]
+    fn test_range() {
+        let check = |rule: &str, mut value: Value| {
+            let (min, max) = rule.split_once(',').unwrap();
+            let mut range = NumRange {
+                min: parse_bound(min).unwrap(),
+                max: parse_bound(max).unwrap(),
+            };
+            range.call(&mut value)
+        };
+        // the integer above 2^53 is not rounded
+        assert!(check("0,9007199254740993", Value::Uint64(9007199254740993)));
+        assert!(!check(
+            "0,9007199254740993",
+            Value::Uint64(9007199254740994)
+        ));
+        assert!(!check(
+            ",-9007199254740993",
+            Value::Int64(-9007199254740992)
+        ));
+
+        assert!(check("0,100.5", Value::Uint8(100)));
+        assert!(!check("0,100.5", Value::Uint8(101)));
+        assert!(!check("-1.5,", Value::Int8(-2)));
+        assert!(check("-1.5,", Value::Int8(-1)));
+        assert!(check("1,2", Value::Float32(1.5.into())));
+        assert!(!check("1,2", Value::Float64(2.5.into())));
+    }
+
+    #[test]
+    fn test_registry() {
+        let parser = RuleParser::new()
+            .register("always", custom(|_: &mut String| Ok(())))
+            .register_with("one_of", |args: &[&str]| {
+                let list: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+                Ok(custom(move |v: &mut String| {
+                    if list.contains(v) {
+                        Ok(())
+                    } else {
+                        Err("not in list".into())
+                    }
+                }))
+            });
+
+        let validator =
+            Validator::new().rule("name", parser.parse("always|one_of:Jone,Tom").unwrap());
+        validator.validate(input("Tom", 0.0)).unwrap();
+        let err = validator
+            .validate(input("Jim", 0.0))
+            .unwrap_err()
+            .into_invalid()
+            .unwrap();
+        assert_eq!(err.get("name").unwrap()[0].to_string(), "not in list");
+
+        let err = parser.parse("always:1").err().unwrap();
+        assert!(matches!(err.kind(), ParseErrorKind::InvalidArgument { .. }));
+    }
+
+    #[test]
+    fn test_error() {
+        let err = parse("required|foo").err().unwrap();
+        assert_eq!(err.position(), 9);
+        assert_eq!(
+            err.kind(),
+            &ParseErrorKind::UnknownRule { name: "foo".into() }
+        );
+
+        let err = parse("required| |email").err().unwrap();
+        assert_eq!(err.position(), 9);
+        assert_eq!(err.kind(), &ParseErrorKind::EmptyRule);
+
+        let err = parse("email|length:a,3").err().unwrap();
+        assert_eq!(err.position(), 6);
+        assert_eq!(
+            err.to_string(),
+            "invalid arguments of rule `length` at position 6: the argument is not a number"
+        );
+
+        let err = parse("required|regex:^[a-z+$").err().unwrap();
+        assert_eq!(err.position(), 9);
+        assert!(matches!(
+            err.kind(),
+            ParseErrorKind::InvalidArgument { rule, .. } if rule == "regex"
+        ));
+
+        assert!(parse("required:1").is_err());
+        assert!(parse("length:,").is_err());
+        assert!(parse("confirm").is_err());
+    }
+}
@@ -13,7 +13,9 @@
 //! let input = Input { num: 9 };
 //! let err = input
 //!     .validate(Validator::new().rule("num", Range::new(10_u8..20)))
-//!     .unwrap_err();
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
 //!
 //! assert!(matches!(
 //!     err.get("num").unwrap()[0].kind(),
@@ -28,8 +30,8 @@
 
 use std::{fmt::Debug, marker::PhantomData, ops::RangeBounds};
 
-use super::Message;
-use crate::{Rule, Value};
+use super::{range_params, Message};
+use crate::{rule::Params, Rule, Value};
 
 #[derive(Clone)]
 pub struct Range<T, Num> {
@@ -73,10 +75,16 @@ macro_rules! impl_range {
 
             const NAME: &'static str = NAME;
 
+            const UNWRAP_OPTION: bool = true;
+
             fn message(&self) -> Self::Message {
                 self.message_in()
             }
 
+            fn params(&self) -> Params {
+                range_params(&self.value)
+            }
+
             fn call(&mut self, data: &mut Value) -> bool {
                 match data {
                     Value::$val(n) => self.value.contains(n),
@@ -105,10 +113,16 @@ where
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         self.message_in()
     }
 
+    fn params(&self) -> Params {
+        range_params(&self.value)
+    }
+
     fn call(&mut self, data: &mut Value) -> bool {
         match data {
             Value::Float32(f) => self.value.contains(f.as_ref()),
@@ -125,10 +139,16 @@ where
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         self.message_in()
     }
 
+    fn params(&self) -> Params {
+        range_params(&self.value)
+    }
+
     fn call(&mut self, data: &mut Value) -> bool {
         match data {
             Value::Float64(f) => self.value.contains(f.as_ref()),
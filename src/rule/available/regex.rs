@@ -17,7 +17,9 @@
 //!         Validator::new()
 //!             .rule("title", Regex::new(r"...")),
 //!     )
-//!     .unwrap_err();
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
 //!
 //! assert!(matches!(
 //!     err.get("title").unwrap()[0].kind(),
@@ -35,16 +37,39 @@
 //!     .unwrap();
 //! ```
 
-use crate::{rule::string::StringRule, Rule};
+use std::borrow::Cow;
+
+use crate::{
+    rule::{string::StringRule, Params},
+    Rule, Value,
+};
 
 use super::Message;
 
 #[derive(Debug, Clone)]
-pub struct Regex<'a>(&'a str);
+pub struct Regex<'a> {
+    pattern: Cow<'a, str>,
+    regex: regex::Regex,
+}
 
 impl<'a> Regex<'a> {
-    pub fn new(pattern: &'a str) -> Self {
-        Self(pattern)
+    /// the pattern can be borrowed or owned, e.g. loaded from a rule string,
+    /// it is compiled once here and reused by every validation
+    ///
+    /// # Panic
+    ///
+    /// When the pattern has syntax error, use [`try_new`](Self::try_new) to check it
+    pub fn new<P: Into<Cow<'a, str>>>(pattern: P) -> Self {
+        let pattern = pattern.into();
+        Self::try_new(pattern.clone())
+            .unwrap_or_else(|_| panic!("regex \"{pattern}\" have syntax error"))
+    }
+
+    /// same as [`new`](Self::new), but return the error when the pattern has syntax error
+    pub fn try_new<P: Into<Cow<'a, str>>>(pattern: P) -> Result<Self, regex::Error> {
+        let pattern = pattern.into();
+        let regex = regex::Regex::new(&pattern)?;
+        Ok(Self { pattern, regex })
     }
 }
 
@@ -53,17 +78,19 @@ impl Rule for Regex<'_> {
 
     const NAME: &'static str = "regex";
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         Message::new(super::MessageKind::Regex)
     }
 
+    fn params(&self) -> Params {
+        vec![("pattern", Value::String(self.pattern.to_string()))]
+    }
+
     fn call(&mut self, data: &mut crate::Value) -> bool {
         match data {
-            crate::Value::String(s) => {
-                let reg = regex::Regex::new(self.0)
-                    .unwrap_or_else(|_| panic!("regex \"{}\" have syntax error", self.0));
-                reg.is_match(s)
-            }
+            crate::Value::String(s) => self.regex.is_match(s),
             _ => false,
         }
     }
@@ -79,8 +106,25 @@ impl StringRule for Regex<'_> {
     }
 
     fn call(&mut self, data: &mut String) -> bool {
-        let reg = regex::Regex::new(self.0)
-            .unwrap_or_else(|_| panic!("regex \"{}\" have syntax error", self.0));
-        reg.is_match(data)
+        self.regex.is_match(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compile_once() {
+        assert!(Regex::try_new("^[a-z+$").is_err());
+
+        let mut regex = Regex::try_new(String::from("^[a-z]+$")).unwrap();
+        assert!(Rule::call(&mut regex, &mut Value::String("abc".into())));
+        assert!(!Rule::call(&mut regex, &mut Value::String("ab1".into())));
+        assert!(StringRule::call(&mut regex, &mut "abc".to_string()));
+        assert_eq!(
+            Rule::params(&regex),
+            vec![("pattern", Value::String("^[a-z]+$".into()))]
+        );
     }
 }
@@ -1,5 +1,6 @@
 //! Value can not be empty, supported `Vec`, `String`, `HashMap`
-//! or `BTreeMap`. other types always return true.
+//! or `BTreeMap`, `Option` is rejected when it is `None` or the inner value is empty.
+//! other types always return true.
 //!
 //! # Examples
 //! ```
@@ -21,7 +22,9 @@
 //!             .rule("username", Required)
 //!             .rule("password", Required),
 //!     )
-//!     .unwrap_err();
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
 //!
 //! assert!(matches!(
 //!     err.get("username").unwrap()[0].kind(),
@@ -48,7 +51,7 @@
 use super::Message;
 use crate::{
     rule::{string::StringRule, Rule},
-    Value,
+    Value, ValueMap,
 };
 
 #[derive(Clone, Copy, Debug)]
@@ -65,13 +68,26 @@ impl Rule for Required {
         Message::new(super::MessageKind::Required)
     }
 
+    /// the absent field is not filled, see [`Presence::Required`]
+    ///
+    /// [`Presence::Required`]: crate::register::Presence::Required
+    fn call_with_relate(&mut self, data: &mut ValueMap) -> bool {
+        data.current_mut().is_some_and(|value| filled(value))
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
-        match value {
-            Value::String(s) => !s.is_empty(),
-            Value::Array(arr) => !arr.is_empty(),
-            Value::Map(map) => !map.is_empty(),
-            _ => true,
-        }
+        filled(value)
+    }
+}
+
+/// check the value is not empty, it is shared by conditional rules.
+pub(super) fn filled(value: &Value) -> bool {
+    match value {
+        Value::String(s) => !s.is_empty(),
+        Value::Array(arr) => !arr.is_empty(),
+        Value::Map(map) => !map.is_empty(),
+        Value::Option(value) => value.as_ref().as_ref().is_some_and(filled),
+        _ => true,
     }
 }
 
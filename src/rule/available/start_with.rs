@@ -21,7 +21,9 @@
 //!             .rule("title", StartWith("hello"))
 //!             .rule("other", StartWith("bar")),
 //!     )
-//!     .unwrap_err();
+//!     .unwrap_err()
+//!     .into_invalid()
+//!     .unwrap();
 //!
 //! assert!(matches!(
 //!     err.get("title").unwrap()[0].kind(),
@@ -39,7 +41,10 @@
 
 use std::fmt::{Debug, Display};
 
-use crate::{rule::string::StringRule, Rule, Value};
+use crate::{
+    rule::{string::StringRule, Params},
+    Rule, Value,
+};
 
 use super::Message;
 
@@ -77,6 +82,10 @@ where
     fn message_in(&self) -> Message {
         Message::new(super::MessageKind::StartWith(self.0.to_string()))
     }
+
+    fn params_in(&self) -> Params {
+        vec![("prefix", Value::String(self.0.to_string()))]
+    }
 }
 
 impl Rule for StartWith<&str> {
@@ -84,10 +93,16 @@ impl Rule for StartWith<&str> {
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         self.message_in()
     }
 
+    fn params(&self) -> Params {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.starts_with(self.0),
@@ -101,10 +116,16 @@ impl Rule for StartWith<String> {
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         self.message_in()
     }
 
+    fn params(&self) -> Params {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.starts_with(&self.0),
@@ -118,10 +139,16 @@ impl Rule for StartWith<char> {
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
     fn message(&self) -> Self::Message {
         self.message_in()
     }
 
+    fn params(&self) -> Params {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.starts_with(self.0),
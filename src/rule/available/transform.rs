@@ -0,0 +1,603 @@
+//! Transform rules, they modify the value and always return true, like [`Trim`].
+//!
+//! The transforms are run before the other rules of the same field, whatever the
+//! registration order is, and they never add messages. Use `validate_mut` to get
+//! the modified data.
+//!
+//! | rule | modification |
+//! |---|---|
+//! | [`Lowercase`] | converts string to lowercase |
+//! | [`Uppercase`] | converts string to uppercase |
+//! | [`CollapseWhitespace`] | replaces every whitespace run with one space, and trims both sides |
+//! | [`StripControlChars`] | removes control chars, except `\t`, `\n` and `\r` |
+//! | [`NormalizeNewlines`] | replaces `\r\n` and `\r` with `\n` |
+//! | [`Truncate`] | keeps the first `n` chars of string |
+//! | [`Clamp`] | limits the number in the range |
+//! | [`Round`] | rounds the float to the decimal places |
+//! | [`DefaultIfEmpty`] | replaces empty string or `None` with the default value |
+//!
+//! All of them also implement [`StringRule`], for the string scheme, `Clamp` and `Round`
+//! modify the string which can be parsed as number, and the others are untouched.
+//!
+//! # Examples
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! # use valitron::{available::{transform::*, Length, Required}, RuleExt, Validatable, Validator};
+//! #[derive(Deserialize, Serialize, Debug)]
+//! struct Input {
+//!     name: String,
+//!     email: String,
+//!     age: u8,
+//!     country: Option<String>,
+//! }
+//!
+//! let input = Input {
+//!     name: "  Jone   Doe ".into(),
+//!     email: "Jone@Example.com".into(),
+//!     age: 200,
+//!     country: None,
+//! };
+//! let validator = Validator::new()
+//!     // `CollapseWhitespace` is run before `Length`
+//!     .rule("name", Length(..=8).and(CollapseWhitespace))
+//!     .rule("email", Lowercase)
+//!     .rule("age", Clamp::new(0_u8..=150))
+//!     .rule("country", Required.and(DefaultIfEmpty("cn")));
+//!
+//! let input = input.validate_mut(validator).unwrap();
+//!
+//! assert_eq!(input.name, "Jone Doe");
+//! assert_eq!(input.email, "jone@example.com");
+//! assert_eq!(input.age, 150);
+//! assert_eq!(input.country.as_deref(), Some("cn"));
+//! ```
+//!
+//! [`Trim`]: super::trim
+//! [`StringRule`]: crate::rule::string::StringRule
+
+use std::{
+    fmt::{Debug, Display},
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    str::FromStr,
+};
+
+use serde::Serialize;
+
+use super::{range_params, Message, MessageKind};
+use crate::{rule::string::StringRule, rule::Params, ser::to_value, Rule, Value};
+
+pub use super::trim::Trim;
+
+fn message() -> Message {
+    Message::new(MessageKind::Transform)
+}
+
+macro_rules! string_transform {
+    ($(#[$doc:meta])* $ty:ident($name:literal), |$s:ident| $body:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy)]
+        pub struct $ty;
+
+        impl $ty {
+            fn transform($s: &mut String) {
+                $body
+            }
+        }
+
+        impl Rule for $ty {
+            type Message = Message;
+
+            const NAME: &'static str = $name;
+
+            const UNWRAP_OPTION: bool = true;
+
+            const TRANSFORM: bool = true;
+
+            fn message(&self) -> Self::Message {
+                message()
+            }
+
+            fn call(&mut self, data: &mut Value) -> bool {
+                if let Value::String(s) = data {
+                    Self::transform(s);
+                }
+                true
+            }
+        }
+
+        impl StringRule for $ty {
+            type Message = Message;
+
+            const NAME: &'static str = $name;
+
+            const TRANSFORM: bool = true;
+
+            fn message(&self) -> Self::Message {
+                message()
+            }
+
+            fn call(&mut self, data: &mut String) -> bool {
+                Self::transform(data);
+                true
+            }
+        }
+    };
+}
+
+string_transform!(
+    /// Converts string to lowercase
+    Lowercase("lowercase"),
+    |s| *s = s.to_lowercase()
+);
+
+string_transform!(
+    /// Converts string to uppercase
+    Uppercase("uppercase"),
+    |s| *s = s.to_uppercase()
+);
+
+string_transform!(
+    /// Replaces every whitespace run with one space, the leading and trailing
+    /// whitespace are removed too, e.g. `" a \t b "` to `"a b"`
+    CollapseWhitespace("collapse_whitespace"),
+    |s| *s = s.split_whitespace().collect::<Vec<_>>().join(" ")
+);
+
+string_transform!(
+    /// Removes the control chars, except `\t`, `\n` and `\r`
+    StripControlChars("strip_control_chars"),
+    |s| s.retain(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+);
+
+string_transform!(
+    /// Replaces `\r\n` and `\r` with `\n`
+    NormalizeNewlines("normalize_newlines"),
+    |s| {
+        if s.contains('\r') {
+            *s = s.replace("\r\n", "\n").replace('\r', "\n");
+        }
+    }
+);
+
+/// Keeps the first `n` chars of string
+#[derive(Debug, Clone, Copy)]
+pub struct Truncate(pub usize);
+
+impl Truncate {
+    fn transform(&self, s: &mut String) {
+        if let Some((index, _)) = s.char_indices().nth(self.0) {
+            s.truncate(index);
+        }
+    }
+}
+
+impl Rule for Truncate {
+    type Message = Message;
+
+    const NAME: &'static str = "truncate";
+
+    const UNWRAP_OPTION: bool = true;
+
+    const TRANSFORM: bool = true;
+
+    fn message(&self) -> Self::Message {
+        message()
+    }
+
+    fn params(&self) -> Params {
+        vec![("len", Value::Uint64(self.0 as u64))]
+    }
+
+    fn call(&mut self, data: &mut Value) -> bool {
+        if let Value::String(s) = data {
+            self.transform(s);
+        }
+        true
+    }
+}
+
+impl StringRule for Truncate {
+    type Message = Message;
+
+    const NAME: &'static str = "truncate";
+
+    const TRANSFORM: bool = true;
+
+    fn message(&self) -> Self::Message {
+        message()
+    }
+
+    fn call(&mut self, data: &mut String) -> bool {
+        self.transform(data);
+        true
+    }
+}
+
+/// Limits the number in the range, support `u8`, `u16`, `u32`, `u64`, `i8`,
+/// `i16`, `i32`, `i64`, `f32` and `f64`, the other types are untouched.
+///
+/// The exclusive bound can not be reached by clamping, so it is ignored, use the
+/// inclusive range, e.g. `Clamp::new(0..=100)` or `Clamp::new(..=100)`.
+#[derive(Clone)]
+pub struct Clamp<T, Num> {
+    value: T,
+    _marker: PhantomData<Num>,
+}
+
+impl<T: Debug, Num> Debug for Clamp<T, Num> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Clamp")
+            .field("value", &self.value)
+            .field("_marker", &format_args!("-"))
+            .finish()
+    }
+}
+
+impl<T: Copy, Num: Clone> Copy for Clamp<T, Num> {}
+
+impl<T, Num> Clamp<T, Num> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            _marker: PhantomData,
+        }
+    }
+
+    fn clamp(&self, n: &mut Num)
+    where
+        T: RangeBounds<Num>,
+        Num: PartialOrd + Copy,
+    {
+        if let Bound::Included(min) = self.value.start_bound() {
+            if *n < *min {
+                *n = *min;
+            }
+        }
+        if let Bound::Included(max) = self.value.end_bound() {
+            if *n > *max {
+                *n = *max;
+            }
+        }
+    }
+}
+
+macro_rules! impl_clamp {
+    ($val:ident($ty:ty)) => {
+        impl<T> Rule for Clamp<T, $ty>
+        where
+            T: RangeBounds<$ty> + Clone,
+        {
+            type Message = Message;
+
+            const NAME: &'static str = "clamp";
+
+            const UNWRAP_OPTION: bool = true;
+
+            const TRANSFORM: bool = true;
+
+            fn message(&self) -> Self::Message {
+                message()
+            }
+
+            fn params(&self) -> Params {
+                range_params(&self.value)
+            }
+
+            fn call(&mut self, data: &mut Value) -> bool {
+                if let Value::$val(n) = data {
+                    self.clamp(n);
+                }
+                true
+            }
+        }
+    };
+    ($val:ident($ty:ty) by $float:ident) => {
+        impl<T> Rule for Clamp<T, $ty>
+        where
+            T: RangeBounds<$ty> + Clone,
+        {
+            type Message = Message;
+
+            const NAME: &'static str = "clamp";
+
+            const UNWRAP_OPTION: bool = true;
+
+            const TRANSFORM: bool = true;
+
+            fn message(&self) -> Self::Message {
+                message()
+            }
+
+            fn params(&self) -> Params {
+                range_params(&self.value)
+            }
+
+            fn call(&mut self, data: &mut Value) -> bool {
+                if let Value::$val(n) = data {
+                    let mut value = n.get();
+                    self.clamp(&mut value);
+                    n.set(value);
+                }
+                true
+            }
+        }
+    };
+}
+
+impl_clamp!(Uint8(u8));
+impl_clamp!(Int8(i8));
+impl_clamp!(Uint16(u16));
+impl_clamp!(Int16(i16));
+impl_clamp!(Uint32(u32));
+impl_clamp!(Int32(i32));
+impl_clamp!(Uint64(u64));
+impl_clamp!(Int64(i64));
+impl_clamp!(Float32(f32) by Float32);
+impl_clamp!(Float64(f64) by Float64);
+
+impl<T, Num> StringRule for Clamp<T, Num>
+where
+    T: RangeBounds<Num> + Clone,
+    Num: FromStr + Display + PartialOrd + Copy,
+{
+    type Message = Message;
+
+    const NAME: &'static str = "clamp";
+
+    const TRANSFORM: bool = true;
+
+    fn message(&self) -> Self::Message {
+        message()
+    }
+
+    fn call(&mut self, data: &mut String) -> bool {
+        if let Ok(mut n) = data.parse::<Num>() {
+            let origin = n;
+            self.clamp(&mut n);
+            if n != origin {
+                *data = n.to_string();
+            }
+        }
+        true
+    }
+}
+
+/// Rounds the float to the decimal places, e.g. `Round(2)` rounds `1.23456` to `1.23`,
+/// the other types are untouched.
+#[derive(Debug, Clone, Copy)]
+pub struct Round(pub u32);
+
+impl Round {
+    fn round(&self, n: f64) -> f64 {
+        let factor = 10_f64.powi(self.0.min(i32::MAX as u32) as i32);
+        let scaled = n * factor;
+        if scaled.is_finite() {
+            scaled.round() / factor
+        } else {
+            n
+        }
+    }
+}
+
+impl Rule for Round {
+    type Message = Message;
+
+    const NAME: &'static str = "round";
+
+    const UNWRAP_OPTION: bool = true;
+
+    const TRANSFORM: bool = true;
+
+    fn message(&self) -> Self::Message {
+        message()
+    }
+
+    fn params(&self) -> Params {
+        vec![("decimals", Value::Uint32(self.0))]
+    }
+
+    fn call(&mut self, data: &mut Value) -> bool {
+        match data {
+            Value::Float32(n) => n.set(self.round(n.get() as f64) as f32),
+            Value::Float64(n) => n.set(self.round(n.get())),
+            _ => (),
+        }
+        true
+    }
+}
+
+impl StringRule for Round {
+    type Message = Message;
+
+    const NAME: &'static str = "round";
+
+    const TRANSFORM: bool = true;
+
+    fn message(&self) -> Self::Message {
+        message()
+    }
+
+    /// the string is formatted with the decimal places, e.g. `Round(2)` formats `"3"` to `"3.00"`
+    fn call(&mut self, data: &mut String) -> bool {
+        if let Ok(n) = data.parse::<f64>() {
+            if n.is_finite() {
+                *data = format!("{:.*}", self.0 as usize, self.round(n));
+            }
+        }
+        true
+    }
+}
+
+/// Replaces the empty string, `None` or unit with the default value, e.g.
+/// `DefaultIfEmpty("guest")`, the `None` is replaced with `Some` of the default value.
+#[derive(Debug, Clone, Copy)]
+pub struct DefaultIfEmpty<T>(pub T);
+
+impl<T> Rule for DefaultIfEmpty<T>
+where
+    T: Serialize + Clone,
+{
+    type Message = Message;
+
+    const NAME: &'static str = "default_if_empty";
+
+    const UNWRAP_OPTION: bool = true;
+
+    const TRANSFORM: bool = true;
+
+    fn message(&self) -> Self::Message {
+        message()
+    }
+
+    fn params(&self) -> Params {
+        vec![("default", crate::panic_on_err!(to_value(&self.0)))]
+    }
+
+    fn call(&mut self, data: &mut Value) -> bool {
+        let is_empty = match data {
+            Value::String(s) => s.is_empty(),
+            Value::Option(option) => option.is_none(),
+            Value::Unit => true,
+            _ => false,
+        };
+        if !is_empty {
+            return true;
+        }
+
+        let value = crate::panic_on_err!(to_value(&self.0));
+        match data {
+            Value::Option(option) => **option = Some(value),
+            _ => *data = value,
+        }
+        true
+    }
+}
+
+impl<T> StringRule for DefaultIfEmpty<T>
+where
+    T: Display + Clone,
+{
+    type Message = Message;
+
+    const NAME: &'static str = "default_if_empty";
+
+    const TRANSFORM: bool = true;
+
+    fn message(&self) -> Self::Message {
+        message()
+    }
+
+    fn call(&mut self, data: &mut String) -> bool {
+        if data.is_empty() {
+            *data = self.0.to_string();
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::IntoRuleList;
+
+    fn transform<R: Rule>(mut rule: R, mut value: Value) -> Value {
+        assert!(Rule::call(&mut rule, &mut value));
+        value
+    }
+
+    fn transform_string<R>(rule: R, s: &str) -> String
+    where
+        R: IntoRuleList<String, Message>,
+    {
+        let mut s = s.to_owned();
+        assert!(rule.into_list().call(&mut s).is_empty());
+        s
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(s.into())
+    }
+
+    #[test]
+    fn strings() {
+        assert_eq!(transform(Lowercase, string("FoO")), string("foo"));
+        assert_eq!(transform(Uppercase, string("FoO")), string("FOO"));
+        assert_eq!(
+            transform(CollapseWhitespace, string(" a \t\n b  c ")),
+            string("a b c")
+        );
+        assert_eq!(
+            transform(StripControlChars, string("a\u{0}b\tc\u{7f}\n")),
+            string("ab\tc\n")
+        );
+        assert_eq!(
+            transform(NormalizeNewlines, string("a\r\nb\rc\n")),
+            string("a\nb\nc\n")
+        );
+        assert_eq!(transform(Truncate(2), string("中文字")), string("中文"));
+        assert_eq!(transform(Truncate(5), string("abc")), string("abc"));
+        assert_eq!(transform(Lowercase, Value::Uint8(1)), Value::Uint8(1));
+
+        assert_eq!(transform_string(Uppercase, "foo"), "FOO");
+        assert_eq!(transform_string(Truncate(1), "foo"), "f");
+        assert_eq!(transform_string(CollapseWhitespace, " a  b"), "a b");
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(
+            transform(Clamp::new(1_u8..=10), Value::Uint8(20)),
+            Value::Uint8(10)
+        );
+        assert_eq!(
+            transform(Clamp::new(-5_i32..), Value::Int32(-8)),
+            Value::Int32(-5)
+        );
+        assert_eq!(
+            transform(Clamp::new(0.0..=1.0), Value::Float64(1.5.into())),
+            Value::Float64(1.0.into())
+        );
+        assert_eq!(
+            transform(Clamp::new(1_u8..=10), Value::Uint16(20)),
+            Value::Uint16(20)
+        );
+
+        assert_eq!(
+            transform(Round(2), Value::Float64(1.23456.into())),
+            Value::Float64(1.23.into())
+        );
+        assert_eq!(
+            transform(Round(0), Value::Float32(2.5.into())),
+            Value::Float32(3.0.into())
+        );
+        assert_eq!(transform(Round(2), Value::Uint8(3)), Value::Uint8(3));
+
+        assert_eq!(transform_string(Clamp::new(1_u8..=10), "20"), "10");
+        assert_eq!(transform_string(Clamp::new(1_u8..=10), "05"), "05");
+        assert_eq!(transform_string(Clamp::new(1_u8..=10), "foo"), "foo");
+        assert_eq!(transform_string(Round(2), "1.23456"), "1.23");
+        assert_eq!(transform_string(Round(1), "3"), "3.0");
+        assert_eq!(transform_string(Round(1), "NaN"), "NaN");
+    }
+
+    #[test]
+    fn default_if_empty() {
+        assert_eq!(transform(DefaultIfEmpty("foo"), string("")), string("foo"));
+        assert_eq!(
+            transform(DefaultIfEmpty("foo"), string("bar")),
+            string("bar")
+        );
+        assert_eq!(
+            transform(DefaultIfEmpty(1_u8), Value::Option(Box::new(None))),
+            Value::Option(Box::new(Some(Value::Uint8(1))))
+        );
+        assert_eq!(
+            Rule::params(&DefaultIfEmpty(1_u8)),
+            vec![("default", Value::Uint8(1))]
+        );
+
+        assert_eq!(transform_string(DefaultIfEmpty("foo"), ""), "foo");
+        assert_eq!(transform_string(DefaultIfEmpty(0), "1"), "1");
+    }
+}
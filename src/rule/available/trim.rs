@@ -34,6 +34,10 @@ impl Rule for Trim {
 
     const NAME: &'static str = NAME;
 
+    const UNWRAP_OPTION: bool = true;
+
+    const TRANSFORM: bool = true;
+
     fn call(&mut self, data: &mut crate::Value) -> bool {
         if let Value::String(s) = data {
             *s = s.trim().to_string()
@@ -52,6 +56,8 @@ impl StringRule for Trim {
 
     const NAME: &'static str = NAME;
 
+    const TRANSFORM: bool = true;
+
     fn call(&mut self, data: &mut String) -> bool {
         *data = data.trim().to_string();
 
@@ -64,6 +70,7 @@ impl StringRule for Trim {
 }
 
 #[test]
+#[allow(clippy::cmp_owned)]
 fn test_trim() {
     let mut value = Value::String(" hello ".to_string());
 
@@ -1,48 +1,135 @@
-use std::marker::PhantomData;
+use std::{any::TypeId, marker::PhantomData};
 
-use super::CoreRule;
+use super::{
+    kind::{Accept, Marked},
+    Context, CoreRule, Params,
+};
+use crate::{register::FieldNames, Value, ValueMap};
 
-pub struct ErasedRule<I, M>(pub(super) Box<dyn BoxedRule<I, M>>);
+pub struct ErasedRule<I, M, K> {
+    rule: Box<dyn BoxedRule<I, M>>,
+    /// the depth of [`Each`], the rule is registered under `field[*]` of every depth
+    ///
+    /// [`Each`]: super::Each
+    pub(super) each: usize,
+    _kind: PhantomData<K>,
+}
+
+// SAFETY: the rule is checked by `Accept` in `new`, or it wraps the other `ErasedRule` of the
+// same marker, so it is `Send + Sync` when the marker is `Shared`, and `Local` is neither
+// `Send` nor `Sync`.
+unsafe impl<I, M, K: Send> Send for ErasedRule<I, M, K> {}
+unsafe impl<I, M, K: Sync> Sync for ErasedRule<I, M, K> {}
 
-impl<I, M> ErasedRule<I, M> {
+impl<I, M, K> ErasedRule<I, M, K> {
     pub fn new<H, S>(handler: H) -> Self
     where
         H: CoreRule<I, S, Message = M>,
         S: 'static,
         M: 'static,
+        K: Accept<H>,
     {
-        Self(Box::new(handler.into_boxed()))
+        Self::wrap(handler.into_boxed(), 0)
+    }
+
+    /// the rule should be accepted by the marker, or only wrap the other `ErasedRule`
+    fn wrap<R>(rule: R, each: usize) -> Self
+    where
+        R: BoxedRule<I, M> + 'static,
+    {
+        Self {
+            rule: Box::new(rule),
+            each,
+            _kind: PhantomData,
+        }
     }
 
     pub fn name(&self) -> &'static str {
-        self.0.name()
+        self.rule.name()
+    }
+    pub fn params(&self) -> Params {
+        self.rule.params()
+    }
+    pub fn is_transform(&self) -> bool {
+        self.rule.is_transform()
     }
-    pub fn call(&mut self, data: &mut I) -> Result<(), M> {
-        self.0.call(data)
+    pub fn context_type(&self) -> Option<(TypeId, &'static str)> {
+        self.rule.context_type()
+    }
+    pub fn call(&mut self, data: &mut I, context: Context<'_>) -> Result<(), M> {
+        self.rule.call(data, context)
     }
 
-    pub fn map<M2>(self, layer: fn(M) -> M2) -> ErasedRule<I, M2>
+    pub fn map<M2>(self, layer: fn(M) -> M2) -> ErasedRule<I, M2, K>
     where
         M: 'static,
         M2: 'static,
         I: 'static,
+        K: 'static,
+    {
+        let each = self.each;
+        ErasedRule::wrap(Map { inner: self, layer }, each)
+    }
+
+    pub(crate) fn when(self, predicate: Marked<Predicate<I>, K>) -> Self
+    where
+        M: 'static,
+        I: 'static,
+        K: 'static,
     {
-        ErasedRule(Box::new(Map { inner: self, layer }))
+        let each = self.each;
+        ErasedRule::wrap(
+            When {
+                inner: self,
+                predicate,
+            },
+            each,
+        )
     }
 }
 
-impl<I, M> Clone for ErasedRule<I, M> {
+impl<M, K> ErasedRule<ValueMap, M, K> {
+    /// resolve the other fields of relational rules under `prefix`, see [`Rebase`]
+    pub fn rebase(self, prefix: FieldNames) -> Self
+    where
+        M: 'static,
+        K: 'static,
+    {
+        let each = self.each;
+        ErasedRule::wrap(
+            Rebase {
+                inner: self,
+                prefix,
+            },
+            each,
+        )
+    }
+}
+
+pub type Predicate<I> = dyn Fn(&I) -> bool;
+
+impl<I, M, K> Clone for ErasedRule<I, M, K> {
     fn clone(&self) -> Self {
-        Self(self.0.clone_box())
+        Self {
+            rule: self.rule.clone_box(),
+            each: self.each,
+            _kind: PhantomData,
+        }
     }
 }
 
 pub trait BoxedRule<I, M> {
     fn clone_box(&self) -> Box<dyn BoxedRule<I, M>>;
 
-    fn call(&mut self, data: &mut I) -> Result<(), M>;
+    fn call(&mut self, data: &mut I, context: Context<'_>) -> Result<(), M>;
 
     fn name(&self) -> &'static str;
+
+    fn params(&self) -> Params;
+
+    fn is_transform(&self) -> bool;
+
+    fn context_type(&self) -> Option<(TypeId, &'static str)>;
 }
 
 pub struct RuleIntoBoxed<H, M, T> {
@@ -84,21 +171,33 @@ where
         Box::new(self.clone())
     }
 
-    fn call(&mut self, data: &mut I) -> Result<(), M> {
-        self.handler.call(data)
+    fn call(&mut self, data: &mut I, context: Context<'_>) -> Result<(), M> {
+        self.handler.call_with_context(data, context)
     }
 
     fn name(&self) -> &'static str {
         H::THE_NAME
     }
+
+    fn params(&self) -> Params {
+        self.handler.params()
+    }
+
+    fn is_transform(&self) -> bool {
+        H::IS_TRANSFORM
+    }
+
+    fn context_type(&self) -> Option<(TypeId, &'static str)> {
+        H::context_type()
+    }
 }
 
-pub struct Map<I, M, M2> {
-    inner: ErasedRule<I, M>,
+pub struct Map<I, M, M2, K> {
+    inner: ErasedRule<I, M, K>,
     layer: fn(M) -> M2,
 }
 
-impl<I, M, M2> Clone for Map<I, M, M2> {
+impl<I, M, M2, K> Clone for Map<I, M, M2, K> {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
@@ -107,21 +206,149 @@ impl<I, M, M2> Clone for Map<I, M, M2> {
     }
 }
 
-impl<I, M, M2> BoxedRule<I, M2> for Map<I, M, M2>
+impl<I, M, M2, K> BoxedRule<I, M2> for Map<I, M, M2, K>
 where
     M: 'static,
     M2: 'static,
     I: 'static,
+    K: 'static,
 {
     fn clone_box(&self) -> Box<dyn BoxedRule<I, M2>> {
         Box::new(self.clone())
     }
 
-    fn call(&mut self, data: &mut I) -> Result<(), M2> {
-        self.inner.call(data).map_err(self.layer)
+    fn call(&mut self, data: &mut I, context: Context<'_>) -> Result<(), M2> {
+        self.inner.call(data, context).map_err(self.layer)
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn params(&self) -> Params {
+        self.inner.params()
+    }
+
+    fn is_transform(&self) -> bool {
+        self.inner.is_transform()
+    }
+
+    fn context_type(&self) -> Option<(TypeId, &'static str)> {
+        self.inner.context_type()
+    }
+}
+
+pub struct When<I, M, K> {
+    inner: ErasedRule<I, M, K>,
+    predicate: Marked<Predicate<I>, K>,
+}
+
+impl<I, M, K> Clone for When<I, M, K> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            predicate: self.predicate.clone(),
+        }
+    }
+}
+
+impl<I, M, K> BoxedRule<I, M> for When<I, M, K>
+where
+    M: 'static,
+    I: 'static,
+    K: 'static,
+{
+    fn clone_box(&self) -> Box<dyn BoxedRule<I, M>> {
+        Box::new(self.clone())
+    }
+
+    fn call(&mut self, data: &mut I, context: Context<'_>) -> Result<(), M> {
+        if (self.predicate)(data) {
+            self.inner.call(data, context)
+        } else {
+            Ok(())
+        }
     }
 
     fn name(&self) -> &'static str {
         self.inner.name()
     }
+
+    fn params(&self) -> Params {
+        let mut params = self.inner.params();
+        params.push(("when", Value::Boolean(true)));
+        params
+    }
+
+    fn is_transform(&self) -> bool {
+        self.inner.is_transform()
+    }
+
+    fn context_type(&self) -> Option<(TypeId, &'static str)> {
+        self.inner.context_type()
+    }
+}
+
+/// the rule mounted by [`nested`], the other fields got by [`ValueMap::get`] are relative
+/// to the mounted field while it is running, e.g. `Confirm("password")` mounted under `home`
+/// compares with `home.password`
+///
+/// [`nested`]: crate::Validator::nested
+pub struct Rebase<M, K> {
+    inner: ErasedRule<ValueMap, M, K>,
+    prefix: FieldNames,
+}
+
+/// the parameters naming the other field of the built-in relational rules
+const RELATE_PARAMS: [&str; 4] = ["other", "required_if", "required_unless", "required_with"];
+
+impl<M, K> Clone for Rebase<M, K> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            prefix: self.prefix.clone(),
+        }
+    }
+}
+
+impl<M, K> BoxedRule<ValueMap, M> for Rebase<M, K>
+where
+    M: 'static,
+    K: 'static,
+{
+    fn clone_box(&self) -> Box<dyn BoxedRule<ValueMap, M>> {
+        Box::new(self.clone())
+    }
+
+    fn call(&mut self, data: &mut ValueMap, context: Context<'_>) -> Result<(), M> {
+        let base = data.base.join(&self.prefix);
+        let outer = std::mem::replace(&mut data.base, base);
+        let res = self.inner.call(data, context);
+        data.base = outer;
+        res
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    /// the other fields are reported with the full path
+    fn params(&self) -> Params {
+        let mut params = self.inner.params();
+        for (name, value) in params.iter_mut() {
+            if let (true, Value::String(field)) = (RELATE_PARAMS.contains(name), &value) {
+                let field = self.prefix.join(&FieldNames::new(field.clone()));
+                *value = Value::String(field.as_str().to_owned());
+            }
+        }
+        params
+    }
+
+    fn is_transform(&self) -> bool {
+        self.inner.is_transform()
+    }
+
+    fn context_type(&self) -> Option<(TypeId, &'static str)> {
+        self.inner.context_type()
+    }
 }
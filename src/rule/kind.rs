@@ -0,0 +1,99 @@
+//! The markers of thread safety, the rule lists and validators are generic over them.
+//!
+//! With the default [`Shared`] marker, the rules and closures should be `Send + Sync`, so
+//! the validator is `Send + Sync` when the message type is, it can be stored in `Arc` or
+//! `static`. The [`Local`] marker accepts the rules which are not `Send + Sync`, e.g.
+//! capturing `Rc` or `RefCell`, and the validator can only be used in the current thread.
+//!
+//! ```rust
+//! # use std::{cell::RefCell, rc::Rc};
+//! # use serde::Serialize;
+//! # use valitron::{custom, Validator};
+//! #[derive(Serialize)]
+//! struct Input {
+//!     name: String,
+//! }
+//!
+//! let seen = Rc::new(RefCell::new(Vec::new()));
+//! let names = seen.clone();
+//! let validator = Validator::local().rule(
+//!     "name",
+//!     custom(move |name: &mut String| {
+//!         names.borrow_mut().push(name.clone());
+//!         Ok::<_, String>(())
+//!     }),
+//! );
+//!
+//! validator.validate(Input { name: "foo".into() }).unwrap();
+//! assert_eq!(*seen.borrow(), ["foo"]);
+//! ```
+
+use std::{marker::PhantomData, ops::Deref, sync::Arc};
+
+/// The default marker, the rules and closures should be `Send + Sync`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Shared;
+
+/// The marker accepting the rules and closures which are not `Send + Sync`,
+/// see [`Validator::local`](crate::Validator::local)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Local(PhantomData<*const ()>);
+
+/// The rule or closure of type `T` can be held under the marker, [`Shared`] accepts
+/// the `Send + Sync` types, and [`Local`] accepts all types.
+#[diagnostic::on_unimplemented(
+    message = "`{T}` is not `Send + Sync`, it can not be registered on the shared validator",
+    note = "use `Validator::local()` to register the rules which are not `Send + Sync`"
+)]
+pub trait Accept<T>: private::Sealed {}
+
+impl<T: Send + Sync> Accept<T> for Shared {}
+
+impl<T> Accept<T> for Local {}
+
+mod private {
+    pub trait Sealed: 'static {}
+
+    impl Sealed for super::Shared {}
+    impl Sealed for super::Local {}
+}
+
+/// the erased closure, it is `Send + Sync` when the marker is [`Shared`]
+pub(crate) struct Marked<T: ?Sized, K> {
+    inner: Arc<T>,
+    _kind: PhantomData<K>,
+}
+
+// SAFETY: `Marked` is only created from the closure accepted by the marker, so the closure is
+// `Send + Sync` when the marker is `Shared`, and `Local` is neither `Send` nor `Sync`.
+unsafe impl<T: ?Sized, K: Send> Send for Marked<T, K> {}
+unsafe impl<T: ?Sized, K: Sync> Sync for Marked<T, K> {}
+
+impl<T: ?Sized, K> Marked<T, K> {
+    pub(crate) fn new<F>(f: F, erase: impl FnOnce(F) -> Arc<T>) -> Self
+    where
+        K: Accept<F>,
+    {
+        Self {
+            inner: erase(f),
+            _kind: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized, K> Clone for Marked<T, K> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _kind: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized, K> Deref for Marked<T, K> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
@@ -20,18 +20,48 @@
 //! }
 //! ```
 
-use std::{collections::HashMap, fmt::Display, slice::Iter};
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt::Display,
+    slice::Iter,
+    sync::Arc,
+};
 
 use crate::{
-    register::IntoMessage,
+    register::{FieldNames, IntoMessage},
     value::{FromValue, Value, ValueMap},
 };
 
+pub use self::asynchronous::AsyncRule;
 use self::boxed::{ErasedRule, RuleIntoBoxed};
+use self::kind::Marked;
+pub use self::kind::{Accept, Local, Shared};
 
+pub mod asynchronous;
 #[cfg(feature = "full")]
 pub mod available;
+
+/// The parameters of rule, the item is a pair of parameter name and value, e.g.
+/// `[("min", Value::Uint64(6)), ("max", Value::Uint64(12))]` of `Length(6..=12)`.
+///
+/// The names used by build-in rules:
+/// - `min` / `max` / `exclusive_min` / `exclusive_max`: the bounds of range
+/// - `prefix` / `suffix` / `text`: the text for comparison
+/// - `pattern`: the regular expression
+/// - `other`: the other field for comparison
+/// - `value`: the number for comparison, e.g. `Gt(10)`
+/// - `not`: the rule is negated by `Not`
+/// - `when`: the rule is only run when the predicate is matched, e.g. `required_if`
+/// - `len`: the maximum chars of `Truncate`
+/// - `decimals`: the decimal places of `Round`
+/// - `default`: the value used by `DefaultIfEmpty`
+/// - `key`: the path of items, e.g. `DistinctBy("address")`
+/// - `item` / `allowed`: the item of `ContainsItem` and the allowed list of `Subset`
+pub type Params = Vec<(&'static str, Value)>;
+
 mod boxed;
+pub mod kind;
 pub mod string;
 
 #[cfg(test)]
@@ -39,6 +69,9 @@ mod test;
 
 /// Trait used by creating CoreRule
 ///
+/// The rules registered on the default validator should be `Send + Sync`, so that it can be
+/// shared between threads, see [`kind`].
+///
 /// # Example
 /// ```rust
 /// # use valitron::{rule::CoreRule, ValueMap};
@@ -68,17 +101,67 @@ pub trait CoreRule<I, T>: 'static + Sized + Clone {
     /// allow `a-z` | `A-Z` | `0-9` | `_` composed string, and not start with `0-9`
     const THE_NAME: &'static str;
 
+    /// Mark the rule as a transform, see [`Rule::TRANSFORM`]
+    const IS_TRANSFORM: bool = false;
+
     /// Rule specific implementation, data is gived type all field's value, and current field index.
     ///
     /// success returning Ok(()), or else returning message.
     fn call(&mut self, data: &mut I) -> Result<(), Self::Message>;
 
+    /// Same as [`call`], and the context passed by [`validate_with`] is available.
+    ///
+    /// [`call`]: Self::call
+    /// [`validate_with`]: crate::Validator::validate_with
+    fn call_with_context(
+        &mut self,
+        data: &mut I,
+        _context: Context<'_>,
+    ) -> Result<(), Self::Message> {
+        self.call(data)
+    }
+
+    /// The parameters of rule, used by introspection, see [`Params`]
+    fn params(&self) -> Params {
+        Params::new()
+    }
+
+    /// The type of context required by the rule, the validator returns an error instead of
+    /// running the rules, when the context of the type is not passed.
+    #[doc(hidden)]
+    fn context_type() -> Option<(TypeId, &'static str)> {
+        None
+    }
+
     #[doc(hidden)]
     fn into_boxed(self) -> RuleIntoBoxed<Self, Self::Message, T> {
         RuleIntoBoxed::new(self)
     }
 }
 
+/// External context of validating, it is passed by [`validate_with`],
+/// e.g. the current user id, a tenant config or a repository handle.
+///
+/// [`validate_with`]: crate::Validator::validate_with
+#[derive(Clone, Copy, Default)]
+pub struct Context<'c>(Option<&'c dyn Any>);
+
+impl<'c> Context<'c> {
+    pub(crate) fn new(context: &'c dyn Any) -> Self {
+        Self(Some(context))
+    }
+
+    /// get the typed context, return `None` when there is no context or the type is not matched.
+    pub fn get<C: 'static>(&self) -> Option<&'c C> {
+        self.0.and_then(|context| context.downcast_ref())
+    }
+
+    pub(crate) fn is(&self, type_id: TypeId) -> bool {
+        self.0
+            .is_some_and(|context| Any::type_id(context) == type_id)
+    }
+}
+
 mod private {
     use super::CoreRule;
 
@@ -91,16 +174,24 @@ mod private {
 /// ```rust,ignore
 /// Rule1.and(Rule2).and(Rule3)
 /// ```
-pub trait RuleExt<Input, Msg>: private::Sealed<Input> {
-    fn and<R>(self, other: R) -> RuleList<Input, Msg>
+pub trait RuleExt<Input, Msg>: private::Sealed<Input> + Sized {
+    fn and<R, K>(self, other: R) -> RuleList<Input, Msg, K>
     where
-        R: CoreRule<Input, (), Message = Msg>;
+        R: CoreRule<Input, (), Message = Msg>,
+        K: Accept<Self> + Accept<R>;
 
-    fn custom<F, V>(self, other: F) -> RuleList<Input, Msg>
+    fn custom<F, V, K>(self, other: F) -> RuleList<Input, Msg, K>
     where
-        F: for<'a> FnOnce(&'a mut V) -> Result<(), Msg>,
         F: CoreRule<Input, V, Message = Msg>,
-        V: FromValue + 'static;
+        V: 'static,
+        K: Accept<Self> + Accept<F>;
+
+    /// only run the rule when the predicate return true, or else it is skipped without message.
+    fn when<F, K>(self, predicate: F) -> RuleList<Input, Msg, K>
+    where
+        F: Fn(&Input) -> bool + 'static,
+        Input: 'static,
+        K: Accept<Self> + Accept<F>;
 }
 
 impl<R, Input, Msg> RuleExt<Input, Msg> for R
@@ -108,9 +199,10 @@ where
     R: CoreRule<Input, (), Message = Msg>,
     Msg: 'static,
 {
-    fn and<R2>(self, other: R2) -> RuleList<Input, Msg>
+    fn and<R2, K>(self, other: R2) -> RuleList<Input, Msg, K>
     where
         R2: CoreRule<Input, (), Message = Msg>,
+        K: Accept<Self> + Accept<R2>,
     {
         let is_dup = {
             if R::THE_NAME != R2::THE_NAME {
@@ -123,32 +215,45 @@ where
             list: if is_dup {
                 vec![ErasedRule::new(self)]
             } else {
-                vec![ErasedRule::<Input, Msg>::new(self), ErasedRule::new(other)]
+                vec![ErasedRule::new(self), ErasedRule::new(other)]
             },
             ..Default::default()
         }
     }
 
-    fn custom<F, V>(self, other: F) -> RuleList<Input, Msg>
+    fn custom<F, V, K>(self, other: F) -> RuleList<Input, Msg, K>
     where
-        F: for<'a> FnOnce(&'a mut V) -> Result<(), Msg>,
         F: CoreRule<Input, V, Message = Msg>,
-        V: FromValue + 'static,
+        V: 'static,
+        K: Accept<Self> + Accept<F>,
     {
         RuleList {
             list: vec![ErasedRule::new(self), ErasedRule::new(other)],
             ..Default::default()
         }
     }
+
+    fn when<F, K>(self, predicate: F) -> RuleList<Input, Msg, K>
+    where
+        F: Fn(&Input) -> bool + 'static,
+        Input: 'static,
+        K: Accept<Self> + Accept<F>,
+    {
+        RuleList {
+            list: vec![ErasedRule::new(self)],
+            ..Default::default()
+        }
+        .when(predicate)
+    }
 }
 
-/// Rules collection
-pub struct RuleList<I, M> {
-    pub(crate) list: Vec<ErasedRule<I, M>>,
+/// Rules collection, the marker `K` is [`Shared`] by default, see [`kind`]
+pub struct RuleList<I, M, K = Shared> {
+    pub(crate) list: Vec<ErasedRule<I, M, K>>,
     is_bail: bool,
 }
 
-impl<I, M> Default for RuleList<I, M> {
+impl<I, M, K> Default for RuleList<I, M, K> {
     fn default() -> Self {
         Self {
             list: Vec::new(),
@@ -157,7 +262,7 @@ impl<I, M> Default for RuleList<I, M> {
     }
 }
 
-impl<I, M> Clone for RuleList<I, M> {
+impl<I, M, K> Clone for RuleList<I, M, K> {
     fn clone(&self) -> Self {
         Self {
             list: self.list.clone(),
@@ -166,8 +271,8 @@ impl<I, M> Clone for RuleList<I, M> {
     }
 }
 
-impl<I, M> RuleList<I, M> {
-    pub fn remove_duplicate(&mut self, other: &ErasedRule<I, M>) {
+impl<I, M, K> RuleList<I, M, K> {
+    pub fn remove_duplicate(&mut self, other: &ErasedRule<I, M, K>) {
         let name = other.name();
 
         let duplicate_rules: Vec<usize> = self
@@ -175,7 +280,7 @@ impl<I, M> RuleList<I, M> {
             .iter()
             .enumerate()
             .filter(|(_index, exist_rule)| {
-                if exist_rule.name() != name {
+                if exist_rule.name() != name || exist_rule.each != other.each {
                     return false;
                 }
                 !matches!(name, "custom")
@@ -184,11 +289,9 @@ impl<I, M> RuleList<I, M> {
             .rev()
             .collect();
 
+        // Use `remove` to keep the registration order of the rule list.
         for index in duplicate_rules {
-            // Use `swap_remove` to get better performence because we don't
-            // mind the order of rule list. If the order should be kept in
-            // the future, please use `remove` instead of `swap_remove`.
-            self.list.swap_remove(index);
+            self.list.remove(index);
         }
     }
 
@@ -196,6 +299,7 @@ impl<I, M> RuleList<I, M> {
     where
         R: CoreRule<I, (), Message = M>,
         M: 'static,
+        K: Accept<R>,
     {
         let other = ErasedRule::new(other);
         self.remove_duplicate(&other);
@@ -206,10 +310,10 @@ impl<I, M> RuleList<I, M> {
 
     pub fn custom<F, V>(mut self, other: F) -> Self
     where
-        F: for<'a> FnOnce(&'a mut V) -> Result<(), M>,
         F: CoreRule<I, V, Message = M>,
-        V: FromValue + 'static,
+        V: 'static,
         M: 'static,
+        K: Accept<F>,
     {
         self.list.push(ErasedRule::new(other));
         self
@@ -225,10 +329,88 @@ impl<I, M> RuleList<I, M> {
         self
     }
 
+    /// only run the rules when the predicate return true, or else they are skipped without message.
+    ///
+    /// The predicate is applied to the rules registered before this method, e.g.
+    /// `Required.and(Email).when(predicate)`, the `Required` and `Email` rules are gated,
+    /// and rules appended after it are always run.
+    ///
+    /// ```rust
+    /// # use serde::Serialize;
+    /// # use valitron::{Rule, RuleExt, Validator, Value, ValueMap};
+    /// #[derive(Clone)]
+    /// struct Required;
+    ///
+    /// impl Rule for Required {
+    ///     type Message = &'static str;
+    ///     const NAME: &'static str = "required";
+    ///
+    ///     fn message(&self) -> Self::Message {
+    ///         "this field is required"
+    ///     }
+    ///     fn call(&mut self, data: &mut Value) -> bool {
+    ///         !matches!(data, Value::String(s) if s.is_empty())
+    ///     }
+    /// }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Account {
+    ///     account_type: &'static str,
+    ///     company_name: &'static str,
+    /// }
+    ///
+    /// let validator = Validator::new().rule(
+    ///     "company_name",
+    ///     Required.when(|data: &ValueMap| {
+    ///         data.get(&"account_type".into()) == Some(&Value::String("business".into()))
+    ///     }),
+    /// );
+    ///
+    /// let personal = Account { account_type: "personal", company_name: "" };
+    /// assert!(validator.validate(personal).is_ok());
+    ///
+    /// let business = Account { account_type: "business", company_name: "" };
+    /// assert!(validator.validate(business).is_err());
+    /// ```
+    pub fn when<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&I) -> bool + 'static,
+        M: 'static,
+        I: 'static,
+        K: Accept<F>,
+    {
+        let predicate = Marked::new(predicate, |f| -> Arc<boxed::Predicate<I>> { Arc::new(f) });
+        self.list = self
+            .list
+            .into_iter()
+            .map(|rule| rule.when(predicate.clone()))
+            .collect();
+        self
+    }
+
     pub(crate) fn set_bail(&mut self) {
         self.is_bail = true;
     }
 
+    /// split the rules by the depth of [`Each`], it is consumed when the rules are registered
+    pub(crate) fn split_each(self) -> Vec<(usize, Self)> {
+        let mut groups: Vec<(usize, Self)> = Vec::new();
+        for mut rule in self.list {
+            let each = std::mem::take(&mut rule.each);
+            match groups.iter_mut().find(|(depth, _)| *depth == each) {
+                Some((_, group)) => group.list.push(rule),
+                None => groups.push((
+                    each,
+                    Self {
+                        list: vec![rule],
+                        is_bail: self.is_bail,
+                    },
+                )),
+            }
+        }
+        groups
+    }
+
     pub fn is_bail(&self) -> bool {
         self.is_bail
     }
@@ -241,7 +423,7 @@ impl<I, M> RuleList<I, M> {
         self.list.is_empty()
     }
 
-    pub(crate) fn merge(&mut self, other: &mut RuleList<I, M>) {
+    pub(crate) fn merge(&mut self, other: &mut RuleList<I, M, K>) {
         for new_rule in &other.list {
             self.remove_duplicate(new_rule);
         }
@@ -250,44 +432,80 @@ impl<I, M> RuleList<I, M> {
         self.is_bail = self.is_bail || other.is_bail;
     }
 
-    fn iter(&self) -> Iter<'_, ErasedRule<I, M>> {
+    pub(crate) fn iter(&self) -> Iter<'_, ErasedRule<I, M, K>> {
         self.list.iter()
     }
 
+    /// the rules requiring a context which is not passed, see [`CoreRule::context_type`]
+    pub(crate) fn missing_context(&self, context: Context<'_>) -> Option<&'static str> {
+        self.iter()
+            .filter_map(|rule| rule.context_type())
+            .find(|(type_id, _)| !context.is(*type_id))
+            .map(|(_, name)| name)
+    }
+
+    /// the transforms are run first, then the other rules in registration order
+    fn ordered(&self) -> impl Iterator<Item = &ErasedRule<I, M, K>> {
+        let transforms = self.iter().filter(|rule| rule.is_transform());
+        transforms.chain(self.iter().filter(|rule| !rule.is_transform()))
+    }
+
+    /// the current value is `None`, and the [`Nullable`] rule is registered
+    ///
+    /// [`Nullable`]: available::Nullable
+    #[cfg(feature = "full")]
+    fn is_null_allowed(&self, data: &ValueMap) -> bool {
+        matches!(data.current(), Some(Value::Option(value)) if value.is_none())
+            && self.contains(available::nullable::NAME)
+    }
+
+    /// the `Nullable` rule is only available with `full` feature
+    #[cfg(not(feature = "full"))]
+    fn is_null_allowed(&self, _data: &ValueMap) -> bool {
+        false
+    }
+
+    /// clone the rules with the given name, the others are dropped
+    pub(crate) fn only(&self, rule: &str) -> Self {
+        Self {
+            list: self
+                .iter()
+                .filter(|item| item.name() == rule)
+                .cloned()
+                .collect(),
+            is_bail: self.is_bail,
+        }
+    }
+
     /// check the rule name is existing
     pub(crate) fn contains(&self, rule: &str) -> bool {
         self.iter().map(ErasedRule::name).any(|name| name == rule)
     }
 
-    /// check all rule names is valid or not
-    pub(crate) fn valid_name(&self) -> bool {
-        self.iter().map(ErasedRule::name).all(|name| {
+    /// find the first invalid rule name
+    pub(crate) fn invalid_name(&self) -> Option<&'static str> {
+        self.iter().map(ErasedRule::name).find(|name| {
             let mut chares = name.chars();
             let first = match chares.next() {
                 Some(ch) => ch,
-                None => return false,
+                None => return true,
             };
 
             if !(first.is_ascii_alphabetic() || first == '_') {
-                return false;
+                return true;
             }
 
-            loop {
-                match chares.next() {
-                    Some(ch) if ch.is_ascii_alphanumeric() || ch == '_' => (),
-                    None => break true,
-                    _ => break false,
-                }
-            }
+            !chares.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
         })
     }
 
     #[must_use]
-    pub(crate) fn map<M2>(self, f: fn(M) -> M2) -> RuleList<I, M2>
+    pub(crate) fn map<M2>(self, f: fn(M) -> M2) -> RuleList<I, M2, K>
     where
         M: 'static,
         M2: 'static,
         I: 'static,
+        K: 'static,
     {
         let list = self
             .list
@@ -302,56 +520,109 @@ impl<I, M> RuleList<I, M> {
     }
 }
 
-impl<M> RuleList<ValueMap, M> {
-    #[must_use]
-    pub(crate) fn call(self, data: &mut ValueMap) -> Vec<(&'static str, M)> {
-        let RuleList { mut list, .. } = self;
-        let mut msg = Vec::with_capacity(list.len());
+impl<M, K> RuleList<ValueMap, M, K> {
+    /// resolve the other fields of relational rules under `prefix`, it is used by
+    /// [`nested`]
+    ///
+    /// [`nested`]: crate::Validator::nested
+    pub(crate) fn rebase(self, prefix: &FieldNames) -> Self
+    where
+        M: 'static,
+        K: 'static,
+    {
+        Self {
+            list: self
+                .list
+                .into_iter()
+                .map(|rule| rule.rebase(prefix.clone()))
+                .collect(),
+            is_bail: self.is_bail,
+        }
+    }
 
-        for endpoint in list.iter_mut() {
+    /// Run all rules on the current field.
+    ///
+    /// Rules are called on their clones, so the state of registered rules is never
+    /// changed, and the list can be reused on any number of inputs.
+    ///
+    /// The type name of the context is returned as `Err`, when a rule requires it but it
+    /// is not passed, and no rule is run.
+    pub(crate) fn call(
+        &self,
+        data: &mut ValueMap,
+        context: Context<'_>,
+    ) -> Result<Vec<(&'static str, M)>, &'static str> {
+        if let Some(expected) = self.missing_context(context) {
+            return Err(expected);
+        }
+        if self.is_null_allowed(data) {
+            return Ok(Vec::new());
+        }
+
+        let mut msg = Vec::with_capacity(self.list.len());
+
+        for endpoint in self.ordered() {
+            if endpoint.is_transform() {
+                let _ = endpoint.clone().call(data, context);
+                continue;
+            }
             let _ = endpoint
-                .call(data)
+                .clone()
+                .call(data, context)
                 .map_err(|e| msg.push((endpoint.name(), e)));
 
             if self.is_bail && !msg.is_empty() {
                 msg.shrink_to(1);
-                return msg;
+                return Ok(msg);
             }
         }
 
         msg.shrink_to_fit();
-        msg
+        Ok(msg)
     }
 
-    #[must_use]
-    pub(crate) fn call_gen_message<M2>(self, data: &mut ValueMap) -> Vec<M2>
+    /// same as [`call`](Self::call), the messages are built by `M2`, and no context is passed
+    pub(crate) fn call_gen_message<M2>(&self, data: &mut ValueMap) -> Result<Vec<M2>, &'static str>
     where
         M2: IntoMessage,
     {
-        let RuleList { mut list, .. } = self;
-        let mut msg = Vec::with_capacity(list.len());
+        if let Some(expected) = self.missing_context(Context::default()) {
+            return Err(expected);
+        }
+        if self.is_null_allowed(data) {
+            return Ok(Vec::new());
+        }
 
-        for endpoint in list.iter_mut() {
-            let _ = endpoint.call(data).map_err(|_| {
-                let value = data.current().unwrap();
-                msg.push(M2::into_message(endpoint.name(), data.as_index(), value))
-            });
+        let mut msg = Vec::with_capacity(self.list.len());
+
+        for endpoint in self.ordered() {
+            if endpoint.is_transform() {
+                let _ = endpoint.clone().call(data, Context::default());
+                continue;
+            }
+            let _ = endpoint
+                .clone()
+                .call(data, Context::default())
+                .map_err(|_| {
+                    let value = data.current().unwrap_or(&Value::Unit);
+                    msg.push(M2::into_message(endpoint.name(), data.as_index(), value))
+                });
 
             if self.is_bail && !msg.is_empty() {
                 msg.shrink_to(1);
-                return msg;
+                return Ok(msg);
             }
         }
 
         msg.shrink_to_fit();
-        msg
+        Ok(msg)
     }
 
     pub(crate) fn call_string_message<'m>(
-        self,
+        &self,
         data: &mut ValueMap,
         message: &HashMap<&'m str, &'m str>,
-    ) -> Vec<String>
+    ) -> Result<Vec<String>, &'static str>
     where
         M: Display,
     {
@@ -360,34 +631,53 @@ impl<M> RuleList<ValueMap, M> {
             s.replace("{value}", value)
         }
 
-        let RuleList { mut list, .. } = self;
-        let mut msg = Vec::with_capacity(list.len());
+        if let Some(expected) = self.missing_context(Context::default()) {
+            return Err(expected);
+        }
+        if self.is_null_allowed(data) {
+            return Ok(Vec::new());
+        }
 
-        for endpoint in list.iter_mut() {
-            let _ = endpoint.call(data).map_err(|def_msg| {
-                let string = def_msg.to_string();
-                let mes = *(message.get(endpoint.name())).unwrap_or(&string.as_str());
-                let value = data.current().unwrap();
-                //let field = data.index;
-                msg.push(replace(mes, data.index.as_str(), &value.to_string()))
-            });
+        let mut msg = Vec::with_capacity(self.list.len());
+
+        for endpoint in self.ordered() {
+            if endpoint.is_transform() {
+                let _ = endpoint.clone().call(data, Context::default());
+                continue;
+            }
+            let _ = endpoint
+                .clone()
+                .call(data, Context::default())
+                .map_err(|def_msg| {
+                    let string = def_msg.to_string();
+                    let mes = *(message.get(endpoint.name())).unwrap_or(&string.as_str());
+                    // `None` is displayed as empty, and the composite value by `Debug`
+                    let value = match data.current().map(Value::as_inner) {
+                        Some(value) if value.is_leaf() => value.to_string(),
+                        Some(Value::Option(_)) | None => String::new(),
+                        Some(value) => format!("{value:?}"),
+                    };
+                    //let field = data.index;
+                    msg.push(replace(mes, data.index.as_str(), &value))
+                });
 
             if self.is_bail && !msg.is_empty() {
                 msg.shrink_to(1);
-                return msg;
+                return Ok(msg);
             }
         }
 
         msg.shrink_to_fit();
-        msg
+        Ok(msg)
     }
 }
 
-impl<M> RuleList<String, M> {
-    pub(crate) fn from_fn<F>(f: F) -> RuleList<String, M>
+impl<M, K> RuleList<String, M, K> {
+    pub(crate) fn from_fn<F>(f: F) -> Self
     where
         F: FnOnce(&mut String) -> Result<(), M> + Clone + 'static,
         M: 'static,
+        K: Accept<F>,
     {
         RuleList {
             list: vec![ErasedRule::new(f)],
@@ -395,11 +685,12 @@ impl<M> RuleList<String, M> {
         }
     }
 
-    pub(crate) fn append_fn<S, F>(one: S, fun: F) -> RuleList<String, M>
+    pub(crate) fn append_fn<S, F>(one: S, fun: F) -> Self
     where
         S: CoreRule<String, (), Message = M>,
         F: FnOnce(&mut String) -> Result<(), M> + Clone + 'static,
         M: 'static,
+        K: Accept<S> + Accept<F>,
     {
         RuleList {
             list: vec![ErasedRule::new(one), ErasedRule::new(fun)],
@@ -407,11 +698,12 @@ impl<M> RuleList<String, M> {
         }
     }
 
-    pub(crate) fn from_ext_and<S, S2>(one: S, two: S2) -> RuleList<String, M>
+    pub(crate) fn from_ext_and<S, S2>(one: S, two: S2) -> Self
     where
         S: CoreRule<String, (), Message = M>,
         S2: CoreRule<String, (), Message = M>,
         M: 'static,
+        K: Accept<S> + Accept<S2>,
     {
         let is_dup = {
             if S::THE_NAME != S2::THE_NAME {
@@ -424,7 +716,7 @@ impl<M> RuleList<String, M> {
             list: if is_dup {
                 vec![ErasedRule::new(one)]
             } else {
-                vec![ErasedRule::<String, M>::new(one), ErasedRule::new(two)]
+                vec![ErasedRule::new(one), ErasedRule::new(two)]
             },
             ..Default::default()
         }
@@ -432,11 +724,22 @@ impl<M> RuleList<String, M> {
 
     #[must_use]
     pub(crate) fn call(self, data: &mut String) -> Vec<M> {
-        let RuleList { mut list, is_bail } = self;
+        let RuleList {
+            mut list, is_bail, ..
+        } = self;
         let mut msg = Vec::with_capacity(list.len());
 
+        // the stable sort keeps the registration order of transforms and the other rules
+        list.sort_by_key(|rule| !rule.is_transform());
+
         for endpoint in list.iter_mut() {
-            let _ = endpoint.call(data).map_err(|m| msg.push(m));
+            if endpoint.is_transform() {
+                let _ = endpoint.call(data, Context::default());
+                continue;
+            }
+            let _ = endpoint
+                .call(data, Context::default())
+                .map_err(|m| msg.push(m));
 
             if is_bail && !msg.is_empty() {
                 msg.shrink_to(1);
@@ -449,17 +752,48 @@ impl<M> RuleList<String, M> {
     }
 }
 
-pub trait IntoRuleList<I, M> {
-    fn into_list(self) -> RuleList<I, M>;
+pub trait IntoRuleList<I, M, K = Shared> {
+    fn into_list(self) -> RuleList<I, M, K>;
 }
 
 /// load closure rule
-pub fn custom<F, V, Input, Msg>(f: F) -> RuleList<Input, Msg>
+///
+/// the closure can take a context as the second argument, it is passed by [`validate_with`]:
+/// ```rust
+/// # use serde::Serialize;
+/// # use valitron::{custom, Validator};
+/// struct AppCtx {
+///     max_len: usize,
+/// }
+///
+/// #[derive(Serialize)]
+/// struct Input {
+///     name: String,
+/// }
+///
+/// let validator = Validator::new().rule(
+///     "name",
+///     custom(|name: &mut String, ctx: &AppCtx| {
+///         if name.len() <= ctx.max_len {
+///             Ok(())
+///         } else {
+///             Err("name is too long")
+///         }
+///     }),
+/// );
+///
+/// let ctx = AppCtx { max_len: 3 };
+/// assert!(validator.validate_with(Input { name: "foo".into() }, &ctx).is_ok());
+/// assert!(validator.validate_with(Input { name: "foobar".into() }, &ctx).is_err());
+/// ```
+///
+/// [`validate_with`]: crate::Validator::validate_with
+pub fn custom<F, V, Input, Msg, K>(f: F) -> RuleList<Input, Msg, K>
 where
-    F: FnOnce(&mut V) -> Result<(), Msg>,
     F: CoreRule<Input, V, Message = Msg>,
-    V: FromValue + 'static,
+    V: 'static,
     Msg: 'static,
+    K: Accept<F>,
 {
     RuleList {
         list: vec![ErasedRule::new(f)],
@@ -467,17 +801,71 @@ where
     }
 }
 
-impl<I, M> IntoRuleList<I, M> for RuleList<I, M> {
+impl<I, M, K> IntoRuleList<I, M, K> for RuleList<I, M, K> {
     fn into_list(self) -> Self {
         self
     }
 }
-impl<R, M> IntoRuleList<ValueMap, M> for R
+
+/// Apply the rules to every element of `Array`, `Tuple` or `Map` value, instead of
+/// the value as a whole.
+///
+/// The rules are registered under `field[*]`, so the messages are attributed to the
+/// elements, e.g. `tags[1]`, and the custom message key is `tags[*].length`. The elements
+/// can be modified by `validate_mut`, e.g. trimming each element.
+///
+/// It can be nested for the multi-dimensional sequence, e.g. `Each(Each(Required))`.
+///
+/// `Each` is not a rule, so it can not be combined by `and`, e.g. `Required.and(Each(Length(3..)))`,
+/// register it separately, e.g. `.rule("tags", Required).rule("tags", Each(Length(3..)))`.
+/// The rules appended to it later, e.g. `Each(Trim).into_list().and(Required)`, are applied to
+/// the field itself.
+#[cfg_attr(feature = "full", doc = "```rust")]
+#[cfg_attr(not(feature = "full"), doc = "```ignore")]
+/// # use serde::{Deserialize, Serialize};
+/// # use valitron::{available::{Length, Trim}, rule::Each, RuleExt, Validatable, Validator};
+/// #[derive(Serialize, Deserialize)]
+/// struct Post {
+///     tags: Vec<String>,
+/// }
+///
+/// let validator = Validator::new().rule("tags", Each(Trim.and(Length(3..=20))));
+///
+/// let post = Post { tags: vec![" rust ".into(), "go".into()] };
+/// let err = post
+///     .validate(validator.clone())
+///     .unwrap_err()
+///     .into_invalid()
+///     .unwrap();
+/// assert!(err.get("tags[0]").is_none());
+/// assert!(err.get("tags[1]").is_some());
+///
+/// let post = Post { tags: vec![" rust ".into()] };
+/// let post = post.validate_mut(validator).unwrap();
+/// assert_eq!(post.tags, ["rust"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Each<R>(pub R);
+
+impl<R, M, K> IntoRuleList<ValueMap, M, K> for Each<R>
+where
+    R: IntoRuleList<ValueMap, M, K>,
+{
+    fn into_list(self) -> RuleList<ValueMap, M, K> {
+        let mut list = self.0.into_list();
+        for rule in list.list.iter_mut() {
+            rule.each += 1;
+        }
+        list
+    }
+}
+impl<R, M, K> IntoRuleList<ValueMap, M, K> for R
 where
     R: CoreRule<ValueMap, (), Message = M>,
     M: 'static,
+    K: Accept<R>,
 {
-    fn into_list(self) -> RuleList<ValueMap, M> {
+    fn into_list(self) -> RuleList<ValueMap, M, K> {
         RuleList {
             list: vec![ErasedRule::new(self)],
             ..Default::default()
@@ -485,12 +873,13 @@ where
     }
 }
 
-impl<R, M> IntoRuleList<String, M> for R
+impl<R, M, K> IntoRuleList<String, M, K> for R
 where
     R: CoreRule<String, (), Message = M>,
     M: 'static,
+    K: Accept<R>,
 {
-    fn into_list(self) -> RuleList<String, M> {
+    fn into_list(self) -> RuleList<String, M, K> {
         RuleList {
             list: vec![ErasedRule::new(self)],
             ..Default::default()
@@ -569,22 +958,64 @@ pub trait Rule: Clone {
     /// allow `a-z` | `A-Z` | `0-9` | `_` composed string, and not start with `0-9`
     const NAME: &'static str;
 
+    /// Mark the rule as a transform, e.g. [`Trim`], it only modifies the value.
+    ///
+    /// The transforms are run before the other rules of the same field, and their
+    /// messages are never added, even if [`call`] returns false.
+    ///
+    /// [`Trim`]: crate::available::trim
+    /// [`call`]: Self::call
+    const TRANSFORM: bool = false;
+
+    /// Pass the inner value of `Some` to [`call`], so the rule is applied to `Option` field
+    /// transparently, e.g. `Length` for `Option<String>`, the build-in rules turn it on.
+    ///
+    /// It is off by default, so the custom rules get the `Option` value as it is.
+    ///
+    /// [`call`]: Self::call
+    const UNWRAP_OPTION: bool = false;
+
     /// Default rule error message, when validate fails, return the message to user
     fn message(&self) -> Self::Message;
 
     /// Rule specific implementation, data is gived type all field's value, and current field index.
     /// when the method return true, call_message will return Ok(()), or else return Err(String)
     ///
+    /// when the value is `Some` and [`UNWRAP_OPTION`] is true, the inner value is passed
+    /// to [`call`].
+    ///
     /// *Panic*
     /// when not found value
+    ///
+    /// [`call`]: Self::call
+    /// [`UNWRAP_OPTION`]: Self::UNWRAP_OPTION
     #[must_use]
     fn call_with_relate(&mut self, data: &mut ValueMap) -> bool {
-        self.call(data.current_mut().expect("not found value with fields"))
+        let value = data.current_mut().expect("not found value with fields");
+        if Self::UNWRAP_OPTION {
+            self.call(value.as_inner_mut())
+        } else {
+            self.call(value)
+        }
+    }
+
+    /// Same as [`call_with_relate`], and the context passed by [`validate_with`] is available.
+    ///
+    /// [`call_with_relate`]: Self::call_with_relate
+    /// [`validate_with`]: crate::Validator::validate_with
+    #[must_use]
+    fn call_with_context(&mut self, data: &mut ValueMap, _context: Context<'_>) -> bool {
+        self.call_with_relate(data)
     }
 
     /// Rule specific implementation, data is current field's value
     #[must_use]
     fn call(&mut self, data: &mut Value) -> bool;
+
+    /// The parameters of rule, e.g. the range of `Length`, used by introspection, see [`Params`]
+    fn params(&self) -> Params {
+        Params::new()
+    }
 }
 
 impl<T> CoreRule<ValueMap, ()> for T
@@ -595,6 +1026,8 @@ where
 
     const THE_NAME: &'static str = T::NAME;
 
+    const IS_TRANSFORM: bool = T::TRANSFORM;
+
     /// Rule specific implementation, data is gived type all field's value, and current field index.
     fn call(&mut self, data: &mut ValueMap) -> Result<(), Self::Message> {
         if self.call_with_relate(data) {
@@ -603,6 +1036,22 @@ where
             Err(self.message())
         }
     }
+
+    fn call_with_context(
+        &mut self,
+        data: &mut ValueMap,
+        context: Context<'_>,
+    ) -> Result<(), Self::Message> {
+        if Rule::call_with_context(self, data, context) {
+            Ok(())
+        } else {
+            Err(self.message())
+        }
+    }
+
+    fn params(&self) -> Params {
+        Rule::params(self)
+    }
 }
 
 impl<F, V, M> CoreRule<ValueMap, V> for F
@@ -619,3 +1068,41 @@ where
         self.clone()(val)
     }
 }
+
+impl<F, V, C, M> CoreRule<ValueMap, (V, C)> for F
+where
+    F: for<'a> FnOnce(&'a mut V, &'a C) -> Result<(), M> + 'static + Clone,
+    V: FromValue,
+    C: 'static,
+{
+    type Message = M;
+
+    const THE_NAME: &'static str = "custom";
+
+    fn call(&mut self, data: &mut ValueMap) -> Result<(), Self::Message> {
+        self.call_with_context(data, Context::default())
+    }
+
+    /// # Panic
+    ///
+    /// When the context of `C` is not passed, the rule lists check it by
+    /// [`context_type`](CoreRule::context_type) before running any rule, so the validators
+    /// return [`ValidateError::MissingContext`] instead.
+    ///
+    /// [`ValidateError::MissingContext`]: crate::register::ValidateError::MissingContext
+    fn call_with_context(
+        &mut self,
+        data: &mut ValueMap,
+        context: Context<'_>,
+    ) -> Result<(), Self::Message> {
+        let context = context.get::<C>().unwrap_or_else(|| {
+            panic!("the context `{}` is not passed", std::any::type_name::<C>())
+        });
+        let val = V::from_value(data).expect("argument type can not be matched");
+        self.clone()(val, context)
+    }
+
+    fn context_type() -> Option<(TypeId, &'static str)> {
+        Some((TypeId::of::<C>(), std::any::type_name::<C>()))
+    }
+}
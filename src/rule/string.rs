@@ -1,9 +1,10 @@
-use super::{CoreRule, RuleList};
+use super::{Accept, CoreRule, RuleList};
 
-pub fn custom<F, M>(f: F) -> RuleList<String, M>
+pub fn custom<F, M, K>(f: F) -> RuleList<String, M, K>
 where
     F: FnOnce(&mut String) -> Result<(), M> + Clone + 'static,
     M: 'static,
+    K: Accept<F>,
 {
     RuleList::from_fn(f)
 }
@@ -16,14 +17,16 @@ mod private {
     impl<R, M> Sealed for R where R: CoreRule<String, (), Message = M> {}
 }
 
-pub trait StringRuleExt<M>: private::Sealed {
-    fn and<R>(self, other: R) -> RuleList<String, M>
+pub trait StringRuleExt<M>: private::Sealed + Sized {
+    fn and<R, K>(self, other: R) -> RuleList<String, M, K>
     where
-        R: CoreRule<String, (), Message = M>;
+        R: CoreRule<String, (), Message = M>,
+        K: Accept<Self> + Accept<R>;
 
-    fn custom<F>(self, other: F) -> RuleList<String, M>
+    fn custom<F, K>(self, other: F) -> RuleList<String, M, K>
     where
-        F: FnOnce(&mut String) -> Result<(), M> + Clone + 'static;
+        F: FnOnce(&mut String) -> Result<(), M> + Clone + 'static,
+        K: Accept<Self> + Accept<F>;
 }
 
 impl<S, M> StringRuleExt<M> for S
@@ -31,16 +34,18 @@ where
     S: CoreRule<String, (), Message = M>,
     M: 'static,
 {
-    fn and<S2>(self, other: S2) -> RuleList<String, M>
+    fn and<S2, K>(self, other: S2) -> RuleList<String, M, K>
     where
         S2: CoreRule<String, (), Message = M>,
+        K: Accept<Self> + Accept<S2>,
     {
         RuleList::from_ext_and(self, other)
     }
 
-    fn custom<F>(self, fun: F) -> RuleList<String, M>
+    fn custom<F, K>(self, fun: F) -> RuleList<String, M, K>
     where
         F: FnOnce(&mut String) -> Result<(), M> + Clone + 'static,
+        K: Accept<Self> + Accept<F>,
     {
         RuleList::append_fn(self, fun)
     }
@@ -55,6 +60,11 @@ pub trait StringRule: Clone {
     /// allow `a-z` | `A-Z` | `0-9` | `_` composed string, and not start with `0-9`
     const NAME: &'static str;
 
+    /// Mark the rule as a transform, see [`Rule::TRANSFORM`]
+    ///
+    /// [`Rule::TRANSFORM`]: crate::Rule::TRANSFORM
+    const TRANSFORM: bool = false;
+
     /// Default rule error message, when validate fails, return the message to user
     fn message(&self) -> Self::Message;
 
@@ -71,6 +81,8 @@ where
 
     const THE_NAME: &'static str = T::NAME;
 
+    const IS_TRANSFORM: bool = T::TRANSFORM;
+
     /// Rule specific implementation, data is gived type all field's value, and current field index.
     fn call(&mut self, data: &mut String) -> Result<(), Self::Message> {
         if self.call(data) {
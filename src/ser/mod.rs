@@ -2,12 +2,15 @@ use std::{collections::BTreeMap, fmt::Display};
 
 use serde::ser;
 
-use crate::value::Value;
+use crate::{
+    register::{FieldName, FieldNames},
+    value::Value,
+};
 
 #[cfg(test)]
 mod test;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "full"))]
 pub fn to_value<T>(value: T) -> Result<Value, Error>
 where
     T: ser::Serialize,
@@ -17,19 +20,48 @@ where
 
 pub(crate) struct Serializer;
 
-#[derive(Debug)]
-pub struct Error;
+/// error of serializing data to [`Value`], it carries the message and the path being serialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    message: String,
+    path: Vec<FieldName>,
+}
+
+impl Error {
+    /// the message reported by `Serialize` implementation
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// the path of the value being serialized, e.g. `items[1].name`
+    pub fn path(&self) -> FieldNames {
+        self.path.clone().into()
+    }
+
+    /// prepend the name of parent value
+    fn at(mut self, name: FieldName) -> Self {
+        self.path.insert(0, name);
+        self
+    }
+}
 
 impl serde::ser::Error for Error {
     fn custom<T: Display>(msg: T) -> Self {
-        todo!("{msg}")
+        Self {
+            message: msg.to_string(),
+            path: Vec::new(),
+        }
     }
 }
 
 impl std::error::Error for Error {}
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        "seralize error".fmt(f)
+        if self.path.is_empty() {
+            self.message.fmt(f)
+        } else {
+            write!(f, "{} at `{}`", self.message, self.path().as_str())
+        }
     }
 }
 
@@ -64,7 +96,10 @@ impl serde::ser::Serializer for Serializer {
     where
         T: ?Sized + serde::Serialize,
     {
-        Ok(Value::Option(Box::new(Some(value.serialize(Serializer)?))))
+        let value = value
+            .serialize(Serializer)
+            .map_err(|e| e.at(FieldName::Option))?;
+        Ok(Value::Option(Box::new(Some(value))))
     }
 
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
@@ -92,7 +127,10 @@ impl serde::ser::Serializer for Serializer {
     where
         T: serde::Serialize + ?Sized,
     {
-        Ok(Value::NewtypeStruct(vec![value.serialize(self)?]))
+        let value = value
+            .serialize(self)
+            .map_err(|e| e.at(FieldName::Tuple(0)))?;
+        Ok(Value::NewtypeStruct(vec![value]))
     }
 
     fn serialize_newtype_variant<T>(
@@ -105,7 +143,10 @@ impl serde::ser::Serializer for Serializer {
     where
         T: serde::Serialize + ?Sized,
     {
-        Ok(Value::Enum(variant, vec![value.serialize(self)?]))
+        let value = value
+            .serialize(self)
+            .map_err(|e| e.at(FieldName::Tuple(0)))?;
+        Ok(Value::Enum(variant, vec![value]))
     }
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
@@ -236,7 +277,9 @@ impl serde::ser::SerializeSeq for SerializeSeq {
     where
         T: serde::Serialize + ?Sized,
     {
-        self.0.push(value.serialize(Serializer)?);
+        let index = FieldName::Array(self.0.len());
+        self.0
+            .push(value.serialize(Serializer).map_err(|e| e.at(index))?);
         Ok(())
     }
 
@@ -262,7 +305,9 @@ impl ser::SerializeTuple for SerializeTuple {
     where
         T: serde::Serialize + ?Sized,
     {
-        self.0.push(value.serialize(Serializer)?);
+        let index = FieldName::tuple(self.0.len());
+        self.0
+            .push(value.serialize(Serializer).map_err(|e| e.at(index))?);
         Ok(())
     }
 
@@ -288,7 +333,9 @@ impl ser::SerializeTupleStruct for SerializeTupleStruct {
     where
         T: serde::Serialize + ?Sized,
     {
-        self.0.push(value.serialize(Serializer)?);
+        let index = FieldName::tuple(self.0.len());
+        self.0
+            .push(value.serialize(Serializer).map_err(|e| e.at(index))?);
         Ok(())
     }
 
@@ -318,7 +365,9 @@ impl ser::SerializeTupleVariant for SerializeTupleVariant {
     where
         T: serde::Serialize + ?Sized,
     {
-        self.map.push(value.serialize(Serializer)?);
+        let index = FieldName::tuple(self.map.len());
+        self.map
+            .push(value.serialize(Serializer).map_err(|e| e.at(index))?);
         Ok(())
     }
 
@@ -358,8 +407,13 @@ impl ser::SerializeMap for SerializeMap {
     where
         T: serde::Serialize + ?Sized,
     {
-        let key = self.next_key.take().ok_or(Error)?;
-        self.map.insert(key, value.serialize(Serializer)?);
+        let key = self.next_key.take().ok_or_else(|| {
+            <Error as ser::Error>::custom("serialize_value called before serialize_key")
+        })?;
+        let value = value
+            .serialize(Serializer)
+            .map_err(|e| e.at(FieldName::Key(key.to_string())))?;
+        self.map.insert(key, value);
 
         Ok(())
     }
@@ -379,10 +433,10 @@ impl ser::SerializeStruct for SerializeStruct {
     where
         T: serde::Serialize + ?Sized,
     {
-        self.0.insert(
-            Value::StructKey(key.to_string()),
-            value.serialize(Serializer)?,
-        );
+        let value = value
+            .serialize(Serializer)
+            .map_err(|e| e.at(FieldName::Literal(key.to_string())))?;
+        self.0.insert(Value::StructKey(key.to_string()), value);
 
         Ok(())
     }
@@ -414,10 +468,11 @@ impl ser::SerializeStructVariant for SerializeStructVariant {
     where
         T: serde::Serialize + ?Sized,
     {
-        self.map.insert(
-            Value::StructVariantKey(key.to_string()),
-            value.serialize(Serializer)?,
-        );
+        let value = value
+            .serialize(Serializer)
+            .map_err(|e| e.at(FieldName::StructVariant(key.to_string())))?;
+        self.map
+            .insert(Value::StructVariantKey(key.to_string()), value);
         Ok(())
     }
 
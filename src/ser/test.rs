@@ -344,3 +344,115 @@ fn test_option() {
         })
     );
 }
+
+#[test]
+fn test_map_key() {
+    use std::collections::{BTreeMap, HashMap};
+
+    #[derive(Serialize)]
+    struct Setting {
+        value: u8,
+    }
+
+    let mut settings = HashMap::new();
+    settings.insert("max.conn", Setting { value: 10 });
+    settings.insert("timeout", Setting { value: 20 });
+    let mut ids = BTreeMap::new();
+    ids.insert(7_u32, "seven");
+
+    let mut value = to_value((settings, ids)).unwrap();
+
+    let names = r#"0["max.conn"].value"#.into();
+    assert_eq!(value.get_with_names(&names), Some(&Value::Uint8(10)));
+    assert_eq!(
+        value.get_with_names(&"0[timeout].value".into()),
+        Some(&Value::Uint8(20))
+    );
+    assert_eq!(
+        value.get_with_names(&r#"1["7"]"#.into()),
+        Some(&Value::String("seven".into()))
+    );
+    assert!(value.get_with_names(&r#"0["foo"]"#.into()).is_none());
+
+    *value.get_with_names_mut(&names).unwrap() = Value::Uint8(11);
+    assert_eq!(value.get_with_names(&names), Some(&Value::Uint8(11)));
+
+    let list: Vec<_> = value
+        .expand_names(&"0[*].value".into())
+        .unwrap()
+        .into_iter()
+        .map(|names| names.as_str().to_owned())
+        .collect();
+    assert_eq!(list, [r#"0["max.conn"].value"#, r#"0["timeout"].value"#]);
+
+    // the invalid names are not found, instead of panic
+    assert!(value.get_with_names(&"0[foo".into()).is_none());
+    assert!(value.get_with_names_mut(&"0..value".into()).is_none());
+    assert!(value.expand_names(&"0[*".into()).is_none());
+}
+
+#[test]
+fn error_path() {
+    #[derive(Serialize)]
+    struct Order {
+        items: Vec<Item>,
+    }
+    #[derive(Serialize)]
+    struct Item {
+        #[serde(serialize_with = "fail_on_empty")]
+        name: String,
+    }
+    fn fail_on_empty<S: serde::Serializer>(name: &str, s: S) -> Result<S::Ok, S::Error> {
+        if name.is_empty() {
+            Err(serde::ser::Error::custom("boom"))
+        } else {
+            s.serialize_str(name)
+        }
+    }
+
+    let order = Order {
+        items: vec![
+            Item { name: "foo".into() },
+            Item {
+                name: String::new(),
+            },
+        ],
+    };
+    let err = to_value(order).unwrap_err();
+    assert_eq!(err.message(), "boom");
+    assert_eq!(err.path().as_str(), "items[1].name");
+    assert_eq!(err.to_string(), "boom at `items[1].name`");
+}
+
+#[test]
+fn error_path_long_tuple() {
+    use serde::ser::SerializeTuple;
+
+    struct Long;
+
+    impl Serialize for Long {
+        fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            let mut tuple = s.serialize_tuple(300)?;
+            for index in 0..300_usize {
+                if index == 256 {
+                    tuple.serialize_element(&Fail)?;
+                } else {
+                    tuple.serialize_element(&1_u8)?;
+                }
+            }
+            tuple.end()
+        }
+    }
+
+    struct Fail;
+
+    impl Serialize for Fail {
+        fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("boom"))
+        }
+    }
+
+    // the position out of `u8` is not truncated to `.0`
+    let err = to_value(Long).unwrap_err();
+    assert_eq!(err.path().as_str(), "[256]");
+}
@@ -13,7 +13,7 @@ macro_rules! primitive_eq {
                     if let Value::$val(n) = other {
                         self == n
                     } else {
-                        unreachable!("type mismatch")
+                        false
                     }
                 }
             }
@@ -22,7 +22,7 @@ macro_rules! primitive_eq {
                     if let Value::$val(n) = self {
                         n == other
                     } else {
-                        unreachable!("type mismatch")
+                        false
                     }
                 }
             }
@@ -32,7 +32,7 @@ macro_rules! primitive_eq {
                     if let Value::$val(n) = other {
                         self == n
                     } else {
-                        unreachable!("type mismatch")
+                        false
                     }
                 }
             }
@@ -41,7 +41,7 @@ macro_rules! primitive_eq {
                     if let Value::$val(n) = self {
                         n == other
                     } else {
-                        unreachable!("type mismatch")
+                        false
                     }
                 }
             }
@@ -51,7 +51,7 @@ macro_rules! primitive_eq {
                     if let Value::$val(n) = other {
                         self == n
                     } else {
-                        unreachable!("type mismatch")
+                        false
                     }
                 }
             }
@@ -60,7 +60,7 @@ macro_rules! primitive_eq {
                     if let Value::$val(n) = self {
                         n == other
                     } else {
-                        unreachable!("type mismatch")
+                        false
                     }
                 }
             }
@@ -168,7 +168,7 @@ impl PartialEq<Value> for f32 {
                 false
             }
         } else {
-            unreachable!("type mismatch")
+            false
         }
     }
 }
@@ -182,7 +182,7 @@ impl PartialEq<f32> for Value {
                 false
             }
         } else {
-            unreachable!("type mismatch")
+            false
         }
     }
 }
@@ -196,7 +196,7 @@ impl PartialEq<&Value> for f32 {
                 false
             }
         } else {
-            unreachable!("type mismatch")
+            false
         }
     }
 }
@@ -210,7 +210,7 @@ impl PartialEq<f32> for &Value {
                 false
             }
         } else {
-            unreachable!("type mismatch")
+            false
         }
     }
 }
@@ -223,7 +223,7 @@ impl PartialEq<&mut Value> for f32 {
                 false
             }
         } else {
-            unreachable!("type mismatch")
+            false
         }
     }
 }
@@ -237,7 +237,7 @@ impl PartialEq<f32> for &mut Value {
                 false
             }
         } else {
-            unreachable!("type mismatch")
+            false
         }
     }
 }
@@ -251,7 +251,7 @@ impl PartialEq<Value> for f64 {
                 false
             }
         } else {
-            unreachable!("type mismatch")
+            false
         }
     }
 }
@@ -265,7 +265,7 @@ impl PartialEq<f64> for Value {
                 false
             }
         } else {
-            unreachable!("type mismatch")
+            false
         }
     }
 }
@@ -279,7 +279,7 @@ impl PartialEq<&Value> for f64 {
                 false
             }
         } else {
-            unreachable!("type mismatch")
+            false
         }
     }
 }
@@ -293,7 +293,7 @@ impl PartialEq<f64> for &Value {
                 false
             }
         } else {
-            unreachable!("type mismatch")
+            false
         }
     }
 }
@@ -307,7 +307,7 @@ impl PartialEq<&mut Value> for f64 {
                 false
             }
         } else {
-            unreachable!("type mismatch")
+            false
         }
     }
 }
@@ -321,7 +321,7 @@ impl PartialEq<f64> for &mut Value {
                 false
             }
         } else {
-            unreachable!("type mismatch")
+            false
         }
     }
 }
@@ -347,8 +347,11 @@ fn all() {
 }
 
 #[test]
-#[should_panic]
 fn type_mismatch() {
     let value = Value::Uint8(10);
-    assert!(value == 10_i8);
+    assert!(value != 10_i8);
+    assert!(value.partial_cmp(&10_i8).is_none());
+
+    let value = Value::String("10".into());
+    assert!(value != 10_f64);
 }
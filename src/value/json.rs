@@ -0,0 +1,180 @@
+//! convert between `serde_json::Value` and [`Value`] without serialization,
+//! the JSON object is converted to struct, so its keys can be matched by field names,
+//! e.g. `user.name`.
+//!
+//! | JSON | Value |
+//! |---|---|
+//! | `null` | `Option(None)` |
+//! | boolean | `Boolean` |
+//! | integer | `Uint64`, or `Int64` when it is negative |
+//! | float | `Float64` |
+//! | string | `String` |
+//! | array | `Array` |
+//! | object | `Struct` |
+
+use serde_json::{Map, Number, Value as Json};
+
+use super::{float::Float64, Value};
+
+impl From<Json> for Value {
+    fn from(json: Json) -> Self {
+        match json {
+            Json::Null => Value::Option(Box::new(None)),
+            Json::Bool(b) => Value::Boolean(b),
+            Json::Number(n) => number(n),
+            Json::String(s) => Value::String(s),
+            Json::Array(vec) => Value::Array(vec.into_iter().map(Value::from).collect()),
+            Json::Object(map) => Value::Struct(
+                map.into_iter()
+                    .map(|(k, v)| (Value::StructKey(k), Value::from(v)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+fn number(n: Number) -> Value {
+    if let Some(u) = n.as_u64() {
+        Value::Uint64(u)
+    } else if let Some(i) = n.as_i64() {
+        Value::Int64(i)
+    } else {
+        Value::Float64(Float64::new(n.as_f64().unwrap_or(f64::NAN)))
+    }
+}
+
+/// same as `serde_json::to_value`: the non-finite float is converted to `null`, the newtype
+/// struct is transparent, and the enum variant is externally tagged.
+impl From<Value> for Json {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Uint8(n) => n.into(),
+            Value::Int8(n) => n.into(),
+            Value::Uint16(n) => n.into(),
+            Value::Int16(n) => n.into(),
+            Value::Uint32(n) => n.into(),
+            Value::Int32(n) => n.into(),
+            Value::Uint64(n) => n.into(),
+            Value::Int64(n) => n.into(),
+            Value::Float32(n) => float(n.get() as f64),
+            Value::Float64(n) => float(n.get()),
+            Value::String(s) | Value::StructKey(s) | Value::StructVariantKey(s) => Json::String(s),
+            Value::Unit => Json::Null,
+            Value::Boolean(b) => Json::Bool(b),
+            Value::Char(c) => Json::String(c.to_string()),
+            Value::Bytes(bytes) => bytes.into(),
+            Value::Option(option) => (*option).map_or(Json::Null, Json::from),
+            Value::Array(vec) | Value::Tuple(vec) | Value::TupleStruct(vec) => array(vec),
+            Value::NewtypeStruct(mut vec) if vec.len() == 1 => vec.remove(0).into(),
+            Value::NewtypeStruct(vec) => array(vec),
+            Value::EnumUnit(variant) => Json::String(variant.to_owned()),
+            Value::Enum(variant, mut vec) if vec.len() == 1 => {
+                tagged(variant, vec.remove(0).into())
+            }
+            Value::Enum(variant, vec) | Value::TupleVariant(variant, vec) => {
+                tagged(variant, array(vec))
+            }
+            Value::Map(map) | Value::Struct(map) => object(map),
+            Value::StructVariant(variant, map) => tagged(variant, object(map)),
+        }
+    }
+}
+
+fn float(n: f64) -> Json {
+    Number::from_f64(n).map_or(Json::Null, Json::Number)
+}
+
+fn array(vec: Vec<Value>) -> Json {
+    Json::Array(vec.into_iter().map(Json::from).collect())
+}
+
+fn object(map: impl IntoIterator<Item = (Value, Value)>) -> Json {
+    Json::Object(
+        map.into_iter()
+            .map(|(k, v)| {
+                let key = match k {
+                    Value::String(s) | Value::StructKey(s) | Value::StructVariantKey(s) => s,
+                    k if k.is_leaf() => k.to_string(),
+                    k => Json::from(k).to_string(),
+                };
+                (key, Json::from(v))
+            })
+            .collect::<Map<_, _>>(),
+    )
+}
+
+fn tagged(variant: &str, value: Json) -> Json {
+    let mut map = Map::with_capacity(1);
+    map.insert(variant.to_owned(), value);
+    Json::Object(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn convert() {
+        let json = json!({
+            "name": "foo",
+            "age": 18,
+            "balance": -3,
+            "score": 9.5,
+            "tags": ["a", "b"],
+            "home": null,
+            "admin": false,
+        });
+
+        let value = Value::from(json.clone());
+        let Value::Struct(map) = &value else {
+            panic!("object should be struct");
+        };
+        assert_eq!(
+            map.get(&Value::StructKey("age".into())),
+            Some(&Value::Uint64(18))
+        );
+        assert_eq!(
+            map.get(&Value::StructKey("balance".into())),
+            Some(&Value::Int64(-3))
+        );
+        assert_eq!(
+            map.get(&Value::StructKey("score".into())),
+            Some(&Value::Float64(9.5.into()))
+        );
+        assert_eq!(
+            map.get(&Value::StructKey("home".into())),
+            Some(&Value::Option(Box::new(None)))
+        );
+
+        assert_eq!(Json::from(value), json);
+    }
+
+    #[test]
+    fn from_rust_value() {
+        #[derive(serde::Serialize)]
+        enum Shape {
+            Dot,
+            Circle(f32),
+            Rect { w: u8, h: u8 },
+        }
+
+        let value = crate::ser::to_value(vec![
+            Shape::Dot,
+            Shape::Circle(1.5),
+            Shape::Rect { w: 1, h: 2 },
+        ])
+        .unwrap();
+        assert_eq!(
+            Json::from(value),
+            json!(["Dot", { "Circle": 1.5 }, { "Rect": { "w": 1, "h": 2 } }])
+        );
+
+        assert_eq!(Json::from(Value::Float64(f64::NAN.into())), Json::Null);
+        assert_eq!(
+            Json::from(crate::ser::to_value(std::collections::BTreeMap::from([(1, 'a')])).unwrap()),
+            json!({ "1": "a" })
+        );
+    }
+}
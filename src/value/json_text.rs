@@ -0,0 +1,519 @@
+//! parse JSON text into [`Value`], and keep the location of every value,
+//! the conversion is same as `serde_json::Value`, the JSON object is converted to struct.
+
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt::Display,
+};
+
+use crate::register::{FieldName, FieldNames};
+
+use super::{float::Float64, Value};
+
+/// the max depth of nested arrays and objects
+const RECURSION_LIMIT: usize = 128;
+
+/// the location of value in JSON text, `line` and `column` start at 1,
+/// and `column` is counted by chars, `offset` is the byte offset from the start
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Location {
+    line: usize,
+    column: usize,
+    offset: usize,
+}
+
+impl Location {
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {} column {}", self.line, self.column)
+    }
+}
+
+/// the JSON text is malformed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    location: Location,
+    reason: &'static str,
+}
+
+impl SyntaxError {
+    /// where the error is found
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    /// the description of the error, e.g. `expected value`
+    pub fn reason(&self) -> &str {
+        self.reason
+    }
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}", self.reason, self.location)
+    }
+}
+
+impl Error for SyntaxError {}
+
+/// the locations of a value and its items
+#[derive(Debug)]
+pub(crate) struct Spanned {
+    location: Location,
+    items: Items,
+}
+
+#[derive(Debug)]
+enum Items {
+    Leaf,
+    Array(Vec<Spanned>),
+    Object(HashMap<String, Spanned>),
+}
+
+impl Spanned {
+    /// find the location of the value by field names, e.g. `items[1].name`,
+    /// it is the location of the nearest existing parent when the value is absent
+    pub(crate) fn locate(&self, names: &FieldNames) -> Location {
+        let mut node = self;
+        let Ok(names) = names.names() else {
+            return node.location;
+        };
+        for name in names.iter() {
+            let child = match (name, &node.items) {
+                (FieldName::Option, _) => Some(node),
+                (FieldName::Array(i), Items::Array(vec)) => vec.get(*i),
+                (FieldName::Tuple(i), Items::Array(vec)) => vec.get(*i as usize),
+                (
+                    FieldName::Literal(key) | FieldName::Key(key) | FieldName::StructVariant(key),
+                    Items::Object(map),
+                ) => map.get(key),
+                _ => None,
+            };
+            match child {
+                Some(child) => node = child,
+                None => break,
+            }
+        }
+        node.location
+    }
+}
+
+/// parse JSON text into value and the locations
+pub(crate) fn parse(text: &str) -> Result<(Value, Spanned), SyntaxError> {
+    let mut parser = JsonParser {
+        text,
+        pos: 0,
+        line: 1,
+        line_start: 0,
+        column: Cell::new((0, 1)),
+        depth: 0,
+    };
+
+    let res = parser.value()?;
+    parser.whitespace();
+    if parser.pos < text.len() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(res)
+}
+
+struct JsonParser<'a> {
+    text: &'a str,
+    /// byte offset, it is always on the char boundary
+    pos: usize,
+    line: usize,
+    line_start: usize,
+    /// the byte offset and column of the last location, the column is counted from it,
+    /// so the chars of a long line are not counted again for every value
+    column: Cell<(usize, usize)>,
+    depth: usize,
+}
+
+impl JsonParser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn location(&self) -> Location {
+        let (start, column) = match self.column.get() {
+            (start, column) if (self.line_start..=self.pos).contains(&start) => (start, column),
+            _ => (self.line_start, 1),
+        };
+        let column = column + self.text[start..self.pos].chars().count();
+        self.column.set((self.pos, column));
+
+        Location {
+            line: self.line,
+            column,
+            offset: self.pos,
+        }
+    }
+
+    fn error(&self, reason: &'static str) -> SyntaxError {
+        SyntaxError {
+            location: self.location(),
+            reason,
+        }
+    }
+
+    fn whitespace(&mut self) {
+        while let Some(byte) = self.peek() {
+            match byte {
+                b' ' | b'\t' | b'\r' => self.pos += 1,
+                b'\n' => {
+                    self.pos += 1;
+                    self.line += 1;
+                    self.line_start = self.pos;
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<(Value, Spanned), SyntaxError> {
+        self.whitespace();
+        let location = self.location();
+
+        let (value, items) = match self.peek() {
+            Some(b'{') => self.object()?,
+            Some(b'[') => self.array()?,
+            Some(b'"') => (Value::String(self.string()?), Items::Leaf),
+            Some(b't') => (self.literal("true", Value::Boolean(true))?, Items::Leaf),
+            Some(b'f') => (self.literal("false", Value::Boolean(false))?, Items::Leaf),
+            Some(b'n') => (
+                self.literal("null", Value::Option(Box::new(None)))?,
+                Items::Leaf,
+            ),
+            Some(b'-' | b'0'..=b'9') => (self.number()?, Items::Leaf),
+            Some(_) => return Err(self.error("expected value")),
+            None => return Err(self.error("EOF while parsing a value")),
+        };
+
+        Ok((value, Spanned { location, items }))
+    }
+
+    fn literal(&mut self, ident: &str, value: Value) -> Result<Value, SyntaxError> {
+        for byte in ident.bytes() {
+            if !self.eat(byte) {
+                return Err(self.error("expected ident"));
+            }
+        }
+        Ok(value)
+    }
+
+    fn enter(&mut self) -> Result<(), SyntaxError> {
+        self.depth += 1;
+        if self.depth > RECURSION_LIMIT {
+            return Err(self.error("recursion limit exceeded"));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn array(&mut self) -> Result<(Value, Items), SyntaxError> {
+        self.enter()?;
+        let mut values = Vec::new();
+        let mut spans = Vec::new();
+
+        self.whitespace();
+        if !self.eat(b']') {
+            loop {
+                let (value, span) = self.value()?;
+                values.push(value);
+                spans.push(span);
+
+                self.whitespace();
+                match self.peek() {
+                    Some(b',') => self.pos += 1,
+                    Some(b']') => {
+                        self.pos += 1;
+                        break;
+                    }
+                    Some(_) => return Err(self.error("expected `,` or `]`")),
+                    None => return Err(self.error("EOF while parsing a list")),
+                }
+            }
+        }
+
+        self.depth -= 1;
+        Ok((Value::Array(values), Items::Array(spans)))
+    }
+
+    fn object(&mut self) -> Result<(Value, Items), SyntaxError> {
+        self.enter()?;
+        let mut map = BTreeMap::new();
+        let mut spans = HashMap::new();
+
+        self.whitespace();
+        if !self.eat(b'}') {
+            loop {
+                self.whitespace();
+                match self.peek() {
+                    Some(b'"') => (),
+                    Some(_) => return Err(self.error("key must be a string")),
+                    None => return Err(self.error("EOF while parsing an object")),
+                }
+                let key = self.string()?;
+
+                self.whitespace();
+                if !self.eat(b':') {
+                    return Err(self.error("expected `:`"));
+                }
+
+                let (value, span) = self.value()?;
+                map.insert(Value::StructKey(key.clone()), value);
+                spans.insert(key, span);
+
+                self.whitespace();
+                match self.peek() {
+                    Some(b',') => self.pos += 1,
+                    Some(b'}') => {
+                        self.pos += 1;
+                        break;
+                    }
+                    Some(_) => return Err(self.error("expected `,` or `}`")),
+                    None => return Err(self.error("EOF while parsing an object")),
+                }
+            }
+        }
+
+        self.depth -= 1;
+        Ok((Value::Struct(map), Items::Object(spans)))
+    }
+
+    fn string(&mut self) -> Result<String, SyntaxError> {
+        // eat `"`
+        self.pos += 1;
+        let mut string = String::new();
+
+        loop {
+            let start = self.pos;
+            while let Some(byte) = self.peek() {
+                if byte == b'"' || byte == b'\\' || byte < 0x20 {
+                    break;
+                }
+                self.pos += 1;
+            }
+            string.push_str(&self.text[start..self.pos]);
+
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(string);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    string.push(self.escape()?);
+                }
+                Some(_) => return Err(self.error("control character found while parsing a string")),
+                None => return Err(self.error("EOF while parsing a string")),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, SyntaxError> {
+        let ch = match self.peek() {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                self.pos += 1;
+                return self.unicode();
+            }
+            Some(_) => return Err(self.error("invalid escape")),
+            None => return Err(self.error("EOF while parsing a string")),
+        };
+        self.pos += 1;
+        Ok(ch)
+    }
+
+    fn unicode(&mut self) -> Result<char, SyntaxError> {
+        let high = self.hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if !(self.eat(b'\\') && self.eat(b'u')) {
+                return Err(self.error("unexpected end of hex escape"));
+            }
+            let low = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("invalid unicode code point"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode code point"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, SyntaxError> {
+        let hex = self
+            .text
+            .get(self.pos..self.pos + 4)
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("invalid escape"))?;
+        self.pos += 4;
+
+        Ok(u32::from_str_radix(hex, 16).expect("checked hex digits"))
+    }
+
+    fn digits(&mut self) -> Result<(), SyntaxError> {
+        if !matches!(self.peek(), Some(b'0'..=b'9')) {
+            return Err(self.error("invalid number"));
+        }
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        Ok(())
+    }
+
+    fn number(&mut self) -> Result<Value, SyntaxError> {
+        let start = self.pos;
+        let location = self.location();
+
+        self.eat(b'-');
+        if !self.eat(b'0') {
+            self.digits()?;
+        }
+
+        let mut is_float = false;
+        if self.eat(b'.') {
+            is_float = true;
+            self.digits()?;
+        }
+        if self.eat(b'e') || self.eat(b'E') {
+            is_float = true;
+            let _ = self.eat(b'+') || self.eat(b'-');
+            self.digits()?;
+        }
+
+        let text = &self.text[start..self.pos];
+        if !is_float {
+            if let Ok(n) = text.parse() {
+                return Ok(Value::Uint64(n));
+            }
+            if let Ok(n) = text.parse() {
+                return Ok(Value::Int64(n));
+            }
+        }
+        match text.parse::<f64>() {
+            Ok(n) if n.is_finite() => Ok(Value::Float64(Float64::new(n))),
+            _ => Err(SyntaxError {
+                location,
+                reason: "number out of range",
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(text: &str, names: &str) -> (usize, usize, usize) {
+        let (_, spanned) = parse(text).unwrap();
+        let location = spanned.locate(&FieldNames::from(names));
+        (location.line(), location.column(), location.offset())
+    }
+
+    fn error(text: &str) -> (&'static str, usize, usize) {
+        let err = parse(text).unwrap_err();
+        (err.reason, err.location.line, err.location.column)
+    }
+
+    #[test]
+    fn values() {
+        let (value, _) =
+            parse(r#"{"a": [1, -2, 1.5, 2e3, "x\n\u00e9\ud83d\ude00", true, null], "b": {}}"#)
+                .unwrap();
+
+        let Value::Struct(map) = value else {
+            panic!("object should be struct");
+        };
+        assert_eq!(
+            map[&Value::StructKey("a".into())],
+            Value::Array(vec![
+                Value::Uint64(1),
+                Value::Int64(-2),
+                Value::Float64(1.5.into()),
+                Value::Float64(2000.0.into()),
+                Value::String("x\né😀".into()),
+                Value::Boolean(true),
+                Value::Option(Box::new(None)),
+            ])
+        );
+        assert_eq!(
+            map[&Value::StructKey("b".into())],
+            Value::Struct(BTreeMap::new())
+        );
+    }
+
+    #[test]
+    fn locations() {
+        let text = "{\n  \"name\": \"中文\",\n  \"items\": [1, {\"a-b\": 2}],\n  \"x\": \"中\", \"y\": 3\n}";
+
+        assert_eq!(location(text, "name"), (2, 11, 12));
+        assert_eq!(location(text, "items"), (3, 12, 33));
+        assert_eq!(location(text, "items[1]"), (3, 16, 37));
+        assert_eq!(location(text, r#"items[1]["a-b"]"#), (3, 24, 45));
+        assert_eq!(location(text, "y"), (4, 18, 69));
+        // the absent value is located at its parent
+        assert_eq!(location(text, "z"), (1, 1, 0));
+        assert_eq!(location(text, "items[2].a"), (3, 12, 33));
+
+        // the long line is not counted again for every value
+        let text = format!("[{}\"中\", 1]", "0, ".repeat(100_000));
+        assert_eq!(location(&text, "[100001]"), (1, 300_007, 300_008));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(error(""), ("EOF while parsing a value", 1, 1));
+        assert_eq!(error("{\"a\": 1,\n  2}"), ("key must be a string", 2, 3));
+        assert_eq!(error("[1 2]"), ("expected `,` or `]`", 1, 4));
+        assert_eq!(error("{\"a\" 1}"), ("expected `:`", 1, 6));
+        assert_eq!(error("[01]"), ("expected `,` or `]`", 1, 3));
+        assert_eq!(error("[1.]"), ("invalid number", 1, 4));
+        assert_eq!(error("\"\\x\""), ("invalid escape", 1, 3));
+        assert_eq!(
+            error("\"a\nb\""),
+            ("control character found while parsing a string", 1, 3)
+        );
+        assert_eq!(error("\"\\ud800\""), ("unexpected end of hex escape", 1, 8));
+        assert_eq!(error("tru"), ("expected ident", 1, 4));
+        assert_eq!(error("1e400"), ("number out of range", 1, 1));
+        assert_eq!(error("{} x"), ("trailing characters", 1, 4));
+        assert_eq!(
+            error(&"[".repeat(200)),
+            ("recursion limit exceeded", 1, 129)
+        );
+    }
+}
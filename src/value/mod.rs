@@ -23,12 +23,15 @@
 
 use std::{collections::BTreeMap, fmt::Display, mem};
 
-use crate::register::{FieldName, FieldNames, Parser};
+use crate::register::{FieldName, FieldNames};
 
 use self::float::{Float32, Float64};
 
 mod cmp;
 mod float;
+#[cfg(feature = "serde_json")]
+mod json;
+pub(crate) mod json_text;
 
 /// # serialized resultant
 ///
@@ -104,6 +107,11 @@ pub enum Value {
 pub struct ValueMap {
     pub(crate) value: Value,
     pub(crate) index: FieldNames,
+    /// the field mounted by [`nested`], the names of [`get`] are relative to it
+    ///
+    /// [`nested`]: crate::Validator::nested
+    /// [`get`]: Self::get
+    pub(crate) base: FieldNames,
 }
 
 pub trait FromValue {
@@ -115,17 +123,18 @@ impl ValueMap {
         Self {
             value,
             index: FieldNames::default(),
+            base: FieldNames::default(),
         }
     }
 
     /// change index
+    ///
+    /// the field may be absent when it is registered with [`Presence::Required`],
+    /// then [`current`] returns `None`.
+    ///
+    /// [`Presence::Required`]: crate::register::Presence::Required
+    /// [`current`]: Self::current
     pub fn index(&mut self, index: FieldNames) {
-        debug_assert!(
-            self.value.get_with_names(&index).is_some(),
-            "field `{}` is not exist",
-            index.as_str()
-        );
-
         self.index = index;
     }
 
@@ -151,13 +160,42 @@ impl ValueMap {
     }
 
     /// get field value by field names
+    ///
+    /// In the rules of a validator mounted by [`nested`], the names are relative to
+    /// the mounted field, e.g. `password` is `home.password` under `home`.
+    ///
+    /// [`nested`]: crate::Validator::nested
     pub fn get(&self, key: &FieldNames) -> Option<&Value> {
-        self.value.get_with_names(key)
+        if self.base.as_str().is_empty() {
+            self.value.get_with_names(key)
+        } else {
+            self.value.get_with_names(&self.base.join(key))
+        }
     }
 
-    /// get field mutable value by field names
+    /// get field mutable value by field names, they are relative as [`get`]
+    ///
+    /// [`get`]: Self::get
     pub fn get_mut(&mut self, key: &FieldNames) -> Option<&mut Value> {
-        self.value.get_with_names_mut(key)
+        if self.base.as_str().is_empty() {
+            self.value.get_with_names_mut(key)
+        } else {
+            let key = self.base.join(key);
+            self.value.get_with_names_mut(&key)
+        }
+    }
+
+    /// check the field names pass through a `?` whose value is `None`
+    pub(crate) fn is_none_at(&self, names: &FieldNames) -> bool {
+        self.value.is_none_at(names)
+    }
+
+    /// expand wildcard `[*]` of registered field names to concrete field names,
+    /// the absent array or map is kept as its path, e.g. `tags` of `tags[*]`
+    pub(crate) fn expand(&self, names: &FieldNames) -> Vec<FieldNames> {
+        self.value
+            .expand_names_with(names, true)
+            .unwrap_or_default()
     }
 
     pub(crate) fn value(self) -> Value {
@@ -175,33 +213,72 @@ impl Value {
             | (FieldName::Tuple(i), Value::NewtypeStruct(vec))
             | (FieldName::Tuple(i), Value::Enum(_, vec))
             | (FieldName::Tuple(i), Value::TupleVariant(_, vec)) => vec.get(*i as usize),
-            (FieldName::Literal(str), Value::Struct(btree)) => {
+            (FieldName::Literal(str), Value::Struct(btree))
+            | (FieldName::Key(str), Value::Struct(btree)) => {
                 btree.get(&Value::StructKey(str.to_string()))
             }
             (FieldName::StructVariant(str), Value::StructVariant(_, btree)) => {
                 btree.get(&Value::StructVariantKey(str.to_string()))
             }
             (FieldName::Option, Value::Option(val)) => val.as_ref().as_ref(),
+            (FieldName::Key(key), Value::Map(map))
+            | (FieldName::StructVariant(key), Value::Map(map)) => {
+                map_key(map, key).and_then(|k| map.get(&k))
+            }
             _ => None,
         }
     }
 
-    /// get field value by field names
+    /// get field value by field names, return `None` when the names are invalid
     pub fn get_with_names(&self, names: &FieldNames) -> Option<&Value> {
-        let mut value = Some(self);
-        let mut parser = Parser::new(names.as_str());
-        loop {
-            match parser.next_name() {
-                Ok(Some(name)) => {
-                    value = match value {
-                        Some(v) => v.get_with_name(&name),
-                        None => return None,
-                    }
-                }
-                Ok(None) => break value,
-                Err(e) => panic!("{e}"),
+        let names = names.names().ok()?;
+        names
+            .iter()
+            .try_fold(self, |value, name| value.get_with_name(name))
+    }
+
+    /// check the field names pass through a `?` whose value is `None`, e.g. `home?.number`
+    /// when `home` is `None`
+    pub(crate) fn is_none_at(&self, names: &FieldNames) -> bool {
+        let Ok(names) = names.names() else {
+            return false;
+        };
+        let mut value = self;
+        for name in names.iter() {
+            if name == &FieldName::Option && matches!(value, Value::Option(v) if v.is_none()) {
+                return true;
+            }
+            match value.get_with_name(name) {
+                Some(v) => value = v,
+                None => return false,
             }
         }
+        false
+    }
+
+    /// get the inner value of `Some`, the other values are returned as it is
+    pub(crate) fn as_inner(&self) -> &Value {
+        match self {
+            Value::Option(value) => match value.as_ref() {
+                Some(inner) => inner.as_inner(),
+                None => self,
+            },
+            _ => self,
+        }
+    }
+
+    /// get the inner value of `Some`, the other values are returned as it is
+    pub(crate) fn as_inner_mut(&mut self) -> &mut Value {
+        if !matches!(self, Value::Option(value) if value.is_some()) {
+            return self;
+        }
+        match self {
+            Value::Option(value) => match value.as_mut() {
+                Some(inner) => inner.as_inner_mut(),
+                None => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
     }
 
     /// get field mutable value by field name
@@ -213,33 +290,128 @@ impl Value {
             | (FieldName::Tuple(i), Value::NewtypeStruct(vec))
             | (FieldName::Tuple(i), Value::Enum(_, vec))
             | (FieldName::Tuple(i), Value::TupleVariant(_, vec)) => vec.get_mut(*i as usize),
-            (FieldName::Literal(str), Value::Struct(btree)) => {
+            (FieldName::Literal(str), Value::Struct(btree))
+            | (FieldName::Key(str), Value::Struct(btree)) => {
                 btree.get_mut(&Value::StructKey(str.to_string()))
             }
             (FieldName::StructVariant(str), Value::StructVariant(_, btree)) => {
                 btree.get_mut(&Value::StructVariantKey(str.to_string()))
             }
             (FieldName::Option, Value::Option(val)) => val.as_mut().as_mut(),
+            (FieldName::Key(key), Value::Map(map))
+            | (FieldName::StructVariant(key), Value::Map(map)) => {
+                map_key(map, key).and_then(|k| map.get_mut(&k))
+            }
             _ => None,
         }
     }
 
-    /// get field mutable value by field names
+    /// get field mutable value by field names, return `None` when the names are invalid
     pub fn get_with_names_mut(&mut self, names: &FieldNames) -> Option<&mut Value> {
-        let mut value = Some(self);
-        let mut parser = Parser::new(names.as_str());
-        loop {
-            match parser.next_name() {
-                Ok(Some(name)) => {
-                    value = match value {
-                        Some(v) => v.get_with_name_mut(&name),
-                        None => break None,
+        let names = names.names().ok()?;
+        names
+            .iter()
+            .try_fold(self, |value, name| value.get_with_name_mut(name))
+    }
+
+    /// expand wildcard `[*]` of field names to all concrete field names in the value,
+    /// e.g. `items[*].name` to `items[0].name`, `items[1].name` ...
+    ///
+    /// map values are expanded to `["key"]`, e.g. `settings[*]` to `settings["foo"]` ...
+    /// and tuple values are expanded to `.0`, `.1` ...
+    ///
+    /// return `None` when the names are invalid, or the value matched by wildcard is not found
+    /// or not a array, tuple or map.
+    pub(crate) fn expand_names(&self, names: &FieldNames) -> Option<Vec<FieldNames>> {
+        self.expand_names_with(names, false)
+    }
+
+    /// same as [`expand_names`], when `keep_absent` is true, the absent value matched by
+    /// wildcard is kept as its path, e.g. `tags` of `tags[*]`, so it can be reported
+    ///
+    /// [`expand_names`]: Self::expand_names
+    pub(crate) fn expand_names_with(
+        &self,
+        names: &FieldNames,
+        keep_absent: bool,
+    ) -> Option<Vec<FieldNames>> {
+        fn expand(
+            value: Option<&Value>,
+            prefix: &mut Vec<FieldName>,
+            rest: &[FieldName],
+            list: &mut Vec<FieldNames>,
+            keep_absent: bool,
+        ) -> bool {
+            let (first, rest) = match rest.split_first() {
+                Some(res) => res,
+                None => {
+                    list.push(FieldNames::from(prefix.clone()));
+                    return true;
+                }
+            };
+
+            if let FieldName::Wildcard = first {
+                let items: Vec<(FieldName, &Value)> = match value {
+                    Some(Value::Array(vec)) => vec
+                        .iter()
+                        .enumerate()
+                        .map(|(index, item)| (FieldName::Array(index), item))
+                        .collect(),
+                    // the tuple index is `u8`, so the longer tuple can not be expanded
+                    Some(Value::Tuple(vec)) | Some(Value::TupleStruct(vec)) => {
+                        let items = vec.iter().enumerate().map(|(index, item)| {
+                            u8::try_from(index).map(|index| (FieldName::Tuple(index), item))
+                        });
+                        match items.collect() {
+                            Ok(items) => items,
+                            Err(_) => return false,
+                        }
+                    }
+                    Some(Value::Map(map)) => map
+                        .iter()
+                        .filter(|(key, _)| key.is_leaf())
+                        .map(|(key, item)| (FieldName::Key(key.to_string()), item))
+                        .collect(),
+                    None if keep_absent => {
+                        list.push(FieldNames::from(prefix.clone()));
+                        return true;
+                    }
+                    _ => return false,
+                };
+                for (name, item) in items {
+                    prefix.push(name);
+                    let res = expand(Some(item), prefix, rest, list, keep_absent);
+                    prefix.pop();
+                    if !res {
+                        return false;
                     }
                 }
-                Ok(None) => break value,
-                Err(e) => panic!("{e}"),
+                true
+            } else {
+                prefix.push(first.clone());
+                let res = expand(
+                    value.and_then(|v| v.get_with_name(first)),
+                    prefix,
+                    rest,
+                    list,
+                    keep_absent,
+                );
+                prefix.pop();
+                res
             }
         }
+
+        let vec = names.names().ok()?;
+        if !vec.contains(&FieldName::Wildcard) {
+            return Some(vec![names.clone()]);
+        }
+
+        let mut list = Vec::new();
+        if expand(Some(self), &mut Vec::new(), &vec, &mut list, keep_absent) {
+            Some(list)
+        } else {
+            None
+        }
     }
 
     pub fn is_leaf(&self) -> bool {
@@ -368,6 +540,19 @@ impl Value {
     }
 }
 
+/// find the key of map by the string, not only string key, but also number, char,
+/// bool key can be matched by their string form.
+fn map_key(map: &BTreeMap<Value, Value>, key: &str) -> Option<Value> {
+    let string_key = Value::String(key.to_owned());
+    if map.contains_key(&string_key) {
+        return Some(string_key);
+    }
+
+    map.keys()
+        .find(|k| k.is_leaf() && k.to_string() == key)
+        .cloned()
+}
+
 impl FromValue for ValueMap {
     fn from_value(value: &mut ValueMap) -> Option<&mut Self> {
         Some(value)
@@ -30,7 +30,11 @@ fn test_validator() {
         weight: 20.0,
     };
 
-    let res = validator.validate(person).unwrap_err();
+    let res = validator
+        .validate(person)
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
 
     assert!(res.len() == 3);
     assert_eq!(res["age"][0].to_string(), "age should be between 25 and 45");
@@ -44,6 +48,7 @@ fn test_validator() {
     );
 }
 
+#[allow(clippy::manual_range_contains)]
 fn age_limit(n: &mut u8) -> Result<(), Message> {
     if *n >= 25 && *n <= 45 {
         return Ok(());
@@ -51,6 +56,7 @@ fn age_limit(n: &mut u8) -> Result<(), Message> {
     Err("age should be between 25 and 45".into())
 }
 
+#[allow(clippy::manual_range_contains)]
 fn weight_limit(v: &mut Value) -> Result<(), Message> {
     if let Value::Float32(n) = v {
         let n = n.get();
@@ -70,7 +76,11 @@ fn test_has_tuple() {
     #[derive(Serialize, Deserialize, Debug)]
     struct Foo(&'static str, &'static str);
 
-    let res = validator.validate(Foo("heoo", "bar")).unwrap_err();
+    let res = validator
+        .validate(Foo("heoo", "bar"))
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
     assert!(res.len() == 1);
 
     assert_eq!(
@@ -83,7 +93,11 @@ fn test_has_tuple() {
 fn test_has_array() {
     let validator = Validator::new().rule([1], StartWith("hello"));
 
-    let res = validator.validate(vec!["foo", "bar"]).unwrap_err();
+    let res = validator
+        .validate(vec!["foo", "bar"])
+        .unwrap_err()
+        .into_invalid()
+        .unwrap();
 
     assert!(res.len() == 1);
     assert_eq!(
@@ -0,0 +1,404 @@
+//! parse `#[valitron(..)]` and the relevant `#[serde(..)]` attributes
+
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+use syn::{
+    ext::IdentExt, meta::ParseNestedMeta, parenthesized, punctuated::Punctuated, token, Attribute,
+    Expr, Ident, LitStr, Token,
+};
+
+/// one rule of field, e.g. `length(6..12)`
+pub(crate) struct Rule {
+    /// the rule name used in message key, it is `None` for `rule(..)`
+    pub name: Option<&'static str>,
+    pub expr: TokenStream,
+    negatable: bool,
+}
+
+/// the rules and custom messages of one field path
+#[derive(Default)]
+pub(crate) struct Rules {
+    pub rules: Vec<Rule>,
+    pub messages: Vec<(Ident, LitStr)>,
+}
+
+/// `#[valitron(..)]` of field
+#[derive(Default)]
+pub(crate) struct FieldAttr {
+    pub rules: Rules,
+    /// the rules of every item, e.g. `each(length(..5))`
+    pub each: Option<Rules>,
+    /// mount the validator of the field type
+    pub nested: bool,
+}
+
+/// `#[valitron(..)]` of struct
+#[derive(Default)]
+pub(crate) struct ContainerAttr {
+    pub bail: bool,
+}
+
+/// the `#[serde(..)]` attributes affecting the field path
+#[derive(Default)]
+pub(crate) struct SerdeAttr {
+    pub rename: Option<LitStr>,
+    pub rename_all: Option<LitStr>,
+    pub flatten: bool,
+}
+
+impl Rule {
+    fn parse(meta: &ParseNestedMeta) -> syn::Result<Self> {
+        let ident = meta.path.require_ident()?;
+        let name = ident.unraw().to_string();
+
+        if name == "not" {
+            let mut inner: Option<Rule> = None;
+            meta.parse_nested_meta(|meta| {
+                if inner.is_some() {
+                    return Err(meta.error("`not` only accepts one rule"));
+                }
+                inner = Some(Rule::parse(&meta)?);
+                Ok(())
+            })?;
+            let inner = inner.ok_or_else(|| meta.error("`not` needs one rule"))?;
+            if !inner.negatable {
+                return Err(meta.error("the rule can not be negated"));
+            }
+            let expr = inner.expr;
+            return Ok(Rule {
+                name: inner.name,
+                expr: quote_spanned!(ident.span()=> ::valitron::available::Not(#expr)),
+                negatable: false,
+            });
+        }
+
+        let args: Vec<Expr> = if meta.input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in meta.input);
+            Punctuated::<Expr, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let expect = |len: usize| {
+            if args.len() == len {
+                Ok(())
+            } else {
+                Err(syn::Error::new(
+                    ident.span(),
+                    format!("`{name}` expects {len} argument(s), found {}", args.len()),
+                ))
+            }
+        };
+
+        let (name, expr, negatable) = match name.as_str() {
+            "required" => {
+                expect(0)?;
+                (
+                    "required",
+                    quote_spanned!(ident.span()=> ::valitron::available::Required),
+                    true,
+                )
+            }
+            "nullable" => {
+                expect(0)?;
+                (
+                    "nullable",
+                    quote_spanned!(ident.span()=> ::valitron::available::Nullable),
+                    false,
+                )
+            }
+            "email" => {
+                expect(0)?;
+                (
+                    "email",
+                    quote_spanned!(ident.span()=> ::valitron::available::Email),
+                    true,
+                )
+            }
+            "trim" => {
+                expect(0)?;
+                (
+                    "trim",
+                    quote_spanned!(ident.span()=> ::valitron::available::Trim),
+                    false,
+                )
+            }
+            "length" => {
+                expect(1)?;
+                let arg = &args[0];
+                (
+                    "length",
+                    quote_spanned!(ident.span()=> ::valitron::available::Length(#arg)),
+                    true,
+                )
+            }
+            "range" => {
+                expect(1)?;
+                let arg = &args[0];
+                (
+                    "range",
+                    quote_spanned!(ident.span()=> ::valitron::available::Range::new(#arg)),
+                    true,
+                )
+            }
+            "start_with" => {
+                expect(1)?;
+                let arg = &args[0];
+                (
+                    "start_with",
+                    quote_spanned!(ident.span()=> ::valitron::available::StartWith(#arg)),
+                    true,
+                )
+            }
+            "end_with" => {
+                expect(1)?;
+                let arg = &args[0];
+                (
+                    "end_with",
+                    quote_spanned!(ident.span()=> ::valitron::available::EndsWith(#arg)),
+                    true,
+                )
+            }
+            "contains" => {
+                expect(1)?;
+                let arg = &args[0];
+                (
+                    "contains",
+                    quote_spanned!(ident.span()=> ::valitron::available::Contains(#arg)),
+                    true,
+                )
+            }
+            "confirm" => {
+                expect(1)?;
+                let arg = &args[0];
+                (
+                    "confirm",
+                    quote_spanned!(ident.span()=> ::valitron::available::Confirm(#arg)),
+                    true,
+                )
+            }
+            "regex" => {
+                expect(1)?;
+                let arg = &args[0];
+                (
+                    "regex",
+                    quote_spanned!(ident.span()=> ::valitron::available::Regex::new(#arg)),
+                    true,
+                )
+            }
+            "lt" => {
+                expect(1)?;
+                let arg = &args[0];
+                (
+                    "lt",
+                    quote_spanned!(ident.span()=> ::valitron::available::Lt(#arg)),
+                    true,
+                )
+            }
+            "elt" => {
+                expect(1)?;
+                let arg = &args[0];
+                (
+                    "elt",
+                    quote_spanned!(ident.span()=> ::valitron::available::Elt(#arg)),
+                    true,
+                )
+            }
+            "gt" => {
+                expect(1)?;
+                let arg = &args[0];
+                (
+                    "gt",
+                    quote_spanned!(ident.span()=> ::valitron::available::Gt(#arg)),
+                    true,
+                )
+            }
+            "egt" => {
+                expect(1)?;
+                let arg = &args[0];
+                (
+                    "egt",
+                    quote_spanned!(ident.span()=> ::valitron::available::Egt(#arg)),
+                    true,
+                )
+            }
+            "required_if" => {
+                expect(2)?;
+                let (field, value) = (&args[0], &args[1]);
+                (
+                    "required",
+                    quote_spanned!(ident.span()=> ::valitron::available::required_if(#field, #value)),
+                    false,
+                )
+            }
+            "required_unless" => {
+                expect(2)?;
+                let (field, value) = (&args[0], &args[1]);
+                (
+                    "required",
+                    quote_spanned!(ident.span()=> ::valitron::available::required_unless(#field, #value)),
+                    false,
+                )
+            }
+            "required_with" => {
+                expect(1)?;
+                let field = &args[0];
+                (
+                    "required",
+                    quote_spanned!(ident.span()=> ::valitron::available::required_with(#field)),
+                    false,
+                )
+            }
+            "custom" => {
+                expect(1)?;
+                let arg = &args[0];
+                (
+                    "custom",
+                    quote_spanned!(ident.span()=> ::valitron::custom(#arg)),
+                    false,
+                )
+            }
+            "rule" => {
+                expect(1)?;
+                let arg = &args[0];
+                return Ok(Rule {
+                    name: None,
+                    expr: quote_spanned!(ident.span()=> #arg),
+                    negatable: false,
+                });
+            }
+            _ => return Err(meta.error(format!("unknown rule `{name}`"))),
+        };
+
+        Ok(Rule {
+            name: Some(name),
+            expr,
+            negatable,
+        })
+    }
+}
+
+impl Rules {
+    /// parse one item, it is a rule or `message(..)`
+    fn parse(&mut self, meta: &ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("message") {
+            return meta.parse_nested_meta(|meta| {
+                let rule = meta.path.require_ident()?.clone();
+                let message: LitStr = meta.value()?.parse()?;
+                self.messages.push((rule, message));
+                Ok(())
+            });
+        }
+
+        self.rules.push(Rule::parse(meta)?);
+        Ok(())
+    }
+
+    /// the message key should be one of rule names, unless the name of some rule is unknown
+    pub fn check_messages(&self) -> syn::Result<()> {
+        if self.rules.iter().any(|rule| rule.name.is_none()) {
+            return Ok(());
+        }
+        for (rule, _) in &self.messages {
+            let name = rule.unraw().to_string();
+            if !self.rules.iter().any(|r| r.name == Some(name.as_str())) {
+                return Err(syn::Error::new(
+                    rule.span(),
+                    format!("the message of rule `{name}` is set, but the rule is not found"),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FieldAttr {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut field = FieldAttr::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("valitron")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("nested") {
+                    field.nested = true;
+                    Ok(())
+                } else if meta.path.is_ident("each") {
+                    let each = field.each.get_or_insert_with(Rules::default);
+                    meta.parse_nested_meta(|meta| each.parse(&meta))
+                } else {
+                    field.rules.parse(&meta)
+                }
+            })?;
+        }
+        Ok(field)
+    }
+}
+
+impl ContainerAttr {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut container = ContainerAttr::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("valitron")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("bail") {
+                    container.bail = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown attribute of struct, expected `bail`"))
+                }
+            })?;
+        }
+        Ok(container)
+    }
+}
+
+impl SerdeAttr {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut serde = SerdeAttr::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    serde.rename = serialize_name(&meta)?;
+                } else if meta.path.is_ident("rename_all") {
+                    serde.rename_all = serialize_name(&meta)?;
+                } else if meta.path.is_ident("flatten") {
+                    serde.flatten = true;
+                } else {
+                    skip(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(serde)
+    }
+}
+
+/// `rename = "a"` or `rename(serialize = "a", deserialize = "b")`
+fn serialize_name(meta: &ParseNestedMeta) -> syn::Result<Option<LitStr>> {
+    if meta.input.peek(Token![=]) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+
+    let mut name = None;
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("serialize") {
+            name = Some(meta.value()?.parse()?);
+        } else {
+            skip(&meta)?;
+        }
+        Ok(())
+    })?;
+    Ok(name)
+}
+
+/// skip the other serde attributes, e.g. `default`, `with = ".."` or `bound(..)`
+fn skip(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(token::Paren) {
+        let content;
+        parenthesized!(content in meta.input);
+        content.parse::<TokenStream>()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,383 @@
+//! Derive macro of [valitron](https://docs.rs/valitron), it is re-exported by `valitron`
+//! with `derive` feature:
+//!
+//! ```toml
+//! valitron = { version = "0.5", features = ["derive"] }
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    ext::IdentExt, parse_macro_input, Data, DeriveInput, Fields, GenericArgument, GenericParam,
+    LitStr, PathArguments, Type,
+};
+
+mod attr;
+
+use attr::{ContainerAttr, FieldAttr, Rules, SerdeAttr};
+
+/// # Derive validator from field attributes
+///
+/// It generates two inherent methods:
+///
+/// - `fn validator() -> Validator<'static, Message>`, build the validator from attributes
+/// - `fn validate(&self) -> Result<(), ValidateError<Message>>`, validate self with it
+///
+/// The validator used by `validate` is built once and cached in a `static`, except for the
+/// struct with type or const generics, whose validator is built on every call, keep it by
+/// `Self::validator()` to reuse it.
+///
+/// For modifying data, e.g. `trim`, use `Self::validator().validate_mut(data)`.
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use valitron::Validate;
+///
+/// #[derive(Serialize, Deserialize, Validate)]
+/// struct User {
+///     #[valitron(required, length(6..12))]
+///     #[valitron(message(required = "name is required"))]
+///     name: String,
+///
+///     #[valitron(trim, email)]
+///     email: String,
+///
+///     #[valitron(each(length(..5)))]
+///     tags: Vec<String>,
+///
+///     #[valitron(nested)]
+///     addresses: Vec<Address>,
+/// }
+///
+/// #[derive(Serialize, Deserialize, Validate)]
+/// struct Address {
+///     #[valitron(required)]
+///     street: String,
+/// }
+///
+/// let user = User {
+///     name: String::new(),
+///     email: "foo@example.com".into(),
+///     tags: vec!["rust".into()],
+///     addresses: vec![Address { street: String::new() }],
+/// };
+/// let err = user.validate().unwrap_err().into_invalid().unwrap();
+///
+/// assert_eq!(err.get("name").unwrap()[0].to_string(), "name is required");
+/// assert!(err.get("addresses[0].street").is_some());
+/// ```
+///
+/// ## Field attributes
+///
+/// - rules: `required`, `nullable`, `email`, `trim`, `length(range)`, `range(range)`,
+///   `start_with(text)`, `end_with(text)`, `contains(text)`, `confirm(field)`, `regex(pattern)`,
+///   `lt(value)`, `elt(value)`, `gt(value)`, `egt(value)`, `required_if(field, value)`,
+///   `required_unless(field, value)`, `required_with(field)` and `not(rule)`
+/// - `custom(function)`: the closure or function rule, see [`custom`]
+/// - `rule(expr)`: any expression implemented `IntoRuleList`
+/// - `message(rule = "..")`: the custom message of the rule
+/// - `each(..)`: the rules and messages of every item in collection
+/// - `nested`: mount the validator of field type, `Option`, `Vec`, map and so on
+///   are supported, e.g. `Vec<Address>` is mounted under `addresses[*]`
+///
+/// Field names respect `#[serde(rename)]` and `#[serde(rename_all)]`, the `#[serde(flatten)]`
+/// field can not have any rule.
+///
+/// ## Struct attributes
+///
+/// - `bail`: stop at the first failure, see [`bail`]
+///
+/// The misspelled rule is a compile error:
+///
+/// ```compile_fail
+/// # use serde::Serialize;
+/// # use valitron::Validate;
+/// #[derive(Serialize, Validate)]
+/// struct User {
+///     #[valitron(lenght(6..12))]
+///     name: String,
+/// }
+/// ```
+///
+/// [`custom`]: https://docs.rs/valitron/latest/valitron/rule/fn.custom.html
+/// [`bail`]: https://docs.rs/valitron/latest/valitron/register/struct.InnerValidator.html#method.bail
+#[proc_macro_derive(Validate, attributes(valitron))]
+pub fn derive_validate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "`Validate` only supports struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`Validate` only supports struct with named fields",
+            ))
+        }
+    };
+
+    let container = ContainerAttr::parse(&input.attrs)?;
+    let rename_all = SerdeAttr::parse(&input.attrs)?.rename_all;
+
+    // struct with flatten field is serialized as map, so its fields are map keys
+    let mut is_map = false;
+    for field in fields {
+        is_map |= SerdeAttr::parse(&field.attrs)?.flatten;
+    }
+
+    let mut body = Vec::new();
+    for field in fields {
+        let attr = FieldAttr::parse(&field.attrs)?;
+        let serde = SerdeAttr::parse(&field.attrs)?;
+        let ident = field.ident.as_ref().expect("named field");
+
+        if serde.flatten {
+            if attr.nested || !attr.rules.rules.is_empty() || attr.each.is_some() {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "`#[serde(flatten)]` field is not supported",
+                ));
+            }
+            continue;
+        }
+
+        let name = match (&serde.rename, &rename_all) {
+            (Some(rename), _) => rename.value(),
+            (None, Some(rule)) => rename_field(&ident.unraw().to_string(), rule)?,
+            (None, None) => ident.unraw().to_string(),
+        };
+        let path = field_path(&name, is_map);
+
+        body.push(rules(&path, &attr.rules)?);
+
+        if let Some(each) = &attr.each {
+            let mut path = path.clone();
+            let mut ty = &field.ty;
+            while let Some(inner) = generic_arg(ty, &["Option"]) {
+                path.push('?');
+                ty = inner;
+            }
+            path.push_str("[*]");
+            body.push(rules(&path, each)?);
+        }
+
+        if attr.nested {
+            let (suffix, ty) = nested_type(&field.ty);
+            let path = format!("{path}{suffix}");
+            body.push(quote!(.nested(#path, <#ty>::validator())));
+        }
+    }
+
+    if container.bail {
+        body.push(quote!(.bail()));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // the static is shared by all instances of generic function, so the validator of
+    // generic struct can not be cached, it is built on every call
+    let is_generic = input
+        .generics
+        .params
+        .iter()
+        .any(|param| !matches!(param, GenericParam::Lifetime(_)));
+    let validate = if is_generic {
+        quote!(Self::validator().validate(self))
+    } else {
+        quote! {
+            static VALIDATOR: ::std::sync::OnceLock<
+                ::valitron::Validator<'static, ::valitron::available::Message>,
+            > = ::std::sync::OnceLock::new();
+
+            VALIDATOR.get_or_init(Self::validator).validate(self)
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// the validator built from `#[valitron(..)]` attributes
+            pub fn validator() -> ::valitron::Validator<'static, ::valitron::available::Message> {
+                ::valitron::Validator::new()
+                    #(#body)*
+            }
+
+            /// validate self with the [`validator`](Self::validator)
+            pub fn validate(
+                &self,
+            ) -> ::core::result::Result<
+                (),
+                ::valitron::register::ValidateError<::valitron::available::Message>,
+            > {
+                #validate
+            }
+        }
+    })
+}
+
+/// register the rules and messages under the path
+fn rules(path: &str, rules: &Rules) -> syn::Result<TokenStream2> {
+    if rules.rules.is_empty() {
+        return match rules.messages.first() {
+            Some((rule, _)) => Err(syn::Error::new(
+                rule.span(),
+                "the message is set, but the field has no rule",
+            )),
+            None => Ok(TokenStream2::new()),
+        };
+    }
+    rules.check_messages()?;
+
+    let exprs = rules.rules.iter().map(|rule| &rule.expr);
+    let messages = rules.messages.iter().map(|(rule, message)| {
+        let key = format!("{path}.{}", rule.unraw());
+        quote!((#key, #message))
+    });
+    let messages = if rules.messages.is_empty() {
+        TokenStream2::new()
+    } else {
+        quote!(.message([#(#messages),*]))
+    };
+
+    Ok(quote! {
+        #(.rule(#path, #exprs))*
+        #messages
+    })
+}
+
+/// the field path of the serialized name, e.g. `name` or `["first-name"]`
+fn field_path(name: &str, is_map: bool) -> String {
+    let mut chars = name.chars();
+    let is_plain = !is_map
+        && chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if is_plain {
+        name.to_owned()
+    } else {
+        let escaped = name.replace('\\', "\\\\").replace('"', "\\\"");
+        format!("[\"{escaped}\"]")
+    }
+}
+
+/// same as `#[serde(rename_all = "..")]`, the field name is snake_case
+fn rename_field(name: &str, rule: &LitStr) -> syn::Result<String> {
+    let pascal = || -> String {
+        name.split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect()
+    };
+
+    Ok(match rule.value().as_str() {
+        "lowercase" | "snake_case" => name.to_owned(),
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => name.to_ascii_uppercase(),
+        "PascalCase" => pascal(),
+        "camelCase" => {
+            let pascal = pascal();
+            let mut chars = pascal.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        }
+        "kebab-case" => name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => name.to_ascii_uppercase().replace('_', "-"),
+        other => {
+            return Err(syn::Error::new(
+                rule.span(),
+                format!("unknown rename rule `{other}`"),
+            ))
+        }
+    })
+}
+
+/// the path suffix and the type of nested validator, e.g. `[*]` and `Address` of `Vec<Address>`
+fn nested_type(ty: &Type) -> (String, &Type) {
+    const SEQUENCE: &[&str] = &[
+        "Vec",
+        "VecDeque",
+        "LinkedList",
+        "HashSet",
+        "BTreeSet",
+        "IndexSet",
+    ];
+    const MAP: &[&str] = &["HashMap", "BTreeMap", "IndexMap"];
+    const POINTER: &[&str] = &["Box", "Rc", "Arc"];
+
+    let (prefix, inner) = match ty {
+        Type::Array(array) => ("[*]", &*array.elem),
+        Type::Slice(slice) => ("[*]", &*slice.elem),
+        Type::Reference(reference) => ("", &*reference.elem),
+        Type::Paren(paren) => ("", &*paren.elem),
+        Type::Group(group) => ("", &*group.elem),
+        _ => {
+            if let Some(inner) = generic_arg(ty, &["Option"]) {
+                ("?", inner)
+            } else if let Some(inner) = generic_arg(ty, SEQUENCE) {
+                ("[*]", inner)
+            } else if let Some(inner) = map_value(ty, MAP) {
+                ("[*]", inner)
+            } else if let Some(inner) = generic_arg(ty, POINTER) {
+                ("", inner)
+            } else {
+                return (String::new(), ty);
+            }
+        }
+    };
+
+    let (suffix, inner) = nested_type(inner);
+    (format!("{prefix}{suffix}"), inner)
+}
+
+/// the first generic type of the type named in `names`, e.g. `T` of `Vec<T>`
+fn generic_arg<'a>(ty: &'a Type, names: &[&str]) -> Option<&'a Type> {
+    generic_types(ty, names).and_then(|mut types| types.next())
+}
+
+/// the second generic type of map, e.g. `V` of `HashMap<K, V>`
+fn map_value<'a>(ty: &'a Type, names: &[&str]) -> Option<&'a Type> {
+    generic_types(ty, names).and_then(|mut types| types.nth(1))
+}
+
+fn generic_types<'a>(ty: &'a Type, names: &[&str]) -> Option<impl Iterator<Item = &'a Type> + 'a> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    if path.qself.is_some() {
+        return None;
+    }
+    let segment = path.path.segments.last()?;
+    if !names.iter().any(|name| segment.ident == name) {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    Some(args.args.iter().filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }))
+}
@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use valitron::{available::MessageKind, Validate};
+
+#[derive(Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+struct User {
+    #[valitron(required, length(2..8))]
+    #[valitron(message(required = "name is required", length = "name is 2 to 8 chars"))]
+    user_name: String,
+
+    #[valitron(trim, email)]
+    email: String,
+
+    #[valitron(not(start_with("admin")))]
+    #[serde(rename = "first-name")]
+    first_name: String,
+
+    #[valitron(range(18_u8..=60), gt("min_age"))]
+    age: u8,
+
+    #[serde(rename = "min_age")]
+    min_age: u8,
+
+    #[valitron(custom(not_seven))]
+    lucky: u8,
+
+    #[valitron(each(length(..5), message(length = "tag is too long")))]
+    tags: Vec<String>,
+
+    #[valitron(nested)]
+    home: Address,
+
+    #[valitron(nested)]
+    work: Option<Address>,
+
+    #[valitron(nested)]
+    history: Vec<Address>,
+
+    #[valitron(nested)]
+    others: BTreeMap<String, Address>,
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+struct Address {
+    #[valitron(required)]
+    street: String,
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+#[valitron(bail)]
+struct Login {
+    #[serde(flatten)]
+    extra: BTreeMap<String, String>,
+
+    #[valitron(required)]
+    account: String,
+
+    #[valitron(required, rule(valitron::available::Length(6..)))]
+    password: String,
+}
+
+fn not_seven(n: &mut u8) -> Result<(), valitron::available::Message> {
+    if *n != 7 {
+        Ok(())
+    } else {
+        Err("it should not be 7".into())
+    }
+}
+
+fn address(street: &str) -> Address {
+    Address {
+        street: street.into(),
+    }
+}
+
+fn user() -> User {
+    User {
+        user_name: "jone".into(),
+        email: " jone@example.com ".into(),
+        first_name: "jone".into(),
+        age: 20,
+        min_age: 18,
+        lucky: 8,
+        tags: vec!["rust".into()],
+        home: address("foo"),
+        work: None,
+        history: vec![address("bar")],
+        others: [("a".to_owned(), address("baz"))].into_iter().collect(),
+    }
+}
+
+#[test]
+fn validate() {
+    user().validate().unwrap();
+
+    let user = User::validator().validate_mut(user()).unwrap();
+    assert_eq!(user.email, "jone@example.com");
+}
+
+#[test]
+fn field_rules() {
+    let mut input = user();
+    input.user_name = String::new();
+    input.first_name = "administrator".into();
+    input.age = 17;
+    input.min_age = 17;
+    input.lucky = 7;
+    input.tags = vec!["rust".into(), "valitron".into()];
+
+    let err = input.validate().unwrap_err().into_invalid().unwrap();
+    assert_eq!(err.len(), 5);
+
+    let name = err.get("userName").unwrap();
+    assert_eq!(name[0].to_string(), "name is required");
+    assert_eq!(name[1].to_string(), "name is 2 to 8 chars");
+
+    assert!(err.get(r#"["first-name"]"#).is_some());
+    assert_eq!(err.get("age").unwrap().len(), 2);
+    assert_eq!(
+        err.get("lucky").unwrap()[0].to_string(),
+        "it should not be 7"
+    );
+    assert!(err.get("tags[0]").is_none());
+    assert_eq!(
+        err.get("tags[1]").unwrap()[0].to_string(),
+        "tag is too long"
+    );
+}
+
+#[test]
+fn nested() {
+    let mut input = user();
+    input.home = address("");
+    input.work = Some(address(""));
+    input.history.push(address(""));
+    input.others.insert("b".into(), address(""));
+
+    let err = input.validate().unwrap_err().into_invalid().unwrap();
+    assert_eq!(err.len(), 4);
+    for key in [
+        "home.street",
+        "work?.street",
+        "history[1].street",
+        r#"others["b"].street"#,
+    ] {
+        assert_eq!(
+            err.get(key).unwrap()[0].kind(),
+            &MessageKind::Required,
+            "{key}"
+        );
+    }
+}
+
+#[test]
+fn bail() {
+    let login = Login {
+        extra: BTreeMap::new(),
+        account: String::new(),
+        password: String::new(),
+    };
+    let err = login.validate().unwrap_err().into_invalid().unwrap();
+    assert_eq!(err.len(), 1);
+    assert_eq!(err.get(r#"["account"]"#).unwrap().len(), 1);
+}
+
+#[derive(Serialize, Validate)]
+struct Borrowed<'a> {
+    #[valitron(required)]
+    name: &'a str,
+}
+
+#[derive(Serialize, Validate)]
+struct Generic<T: Serialize> {
+    #[valitron(required)]
+    name: String,
+    value: T,
+}
+
+#[test]
+fn reuse() {
+    for name in ["", "foo", ""] {
+        let res = Borrowed { name }.validate();
+        assert_eq!(res.is_ok(), !name.is_empty());
+
+        let generic = Generic {
+            name: name.to_owned(),
+            value: 1_u8,
+        };
+        assert_eq!(generic.validate().is_ok(), !name.is_empty());
+        let generic = Generic {
+            name: name.to_owned(),
+            value: "foo",
+        };
+        assert_eq!(generic.validate().is_ok(), !name.is_empty());
+    }
+}